@@ -0,0 +1,153 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Expr;
+use syn::Ident;
+use syn::LitFloat;
+use syn::LitInt;
+use syn::Token;
+use syn::Type;
+
+pub(crate) struct KeyPathInput {
+    crate_name: TokenStream,
+    ty: Option<Type>,
+    keys: Vec<Key>,
+}
+
+enum Key {
+    Field(Ident),
+    Index(LitInt),
+    Get(Expr),
+    Variant(Ident),
+}
+
+impl Parse for KeyPathInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        // `$crate` from the `macro_rules!` wrapper in `mirror-mirror`
+        let mut crate_name = TokenStream::new();
+        while !input.peek(Token![;]) {
+            crate_name.extend([input.parse::<TokenTree>()?]);
+        }
+        input.parse::<Token![;]>()?;
+
+        let ty = if is_typed(input) {
+            let ty = input.parse::<Type>()?;
+            input.parse::<Token![,]>()?;
+            Some(ty)
+        } else {
+            None
+        };
+
+        let mut keys = Vec::new();
+        while !input.is_empty() {
+            parse_key(input, &mut keys)?;
+        }
+
+        Ok(Self {
+            crate_name,
+            ty,
+            keys,
+        })
+    }
+}
+
+/// Whether the input starts with `SomeType,`
+fn is_typed(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    fork.parse::<Type>().is_ok() && fork.peek(Token![,])
+}
+
+fn parse_key(input: ParseStream<'_>, keys: &mut Vec<Key>) -> syn::Result<()> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+        if input.peek(LitFloat) {
+            // `.0.1` is tokenized as `.` followed by the float `0.1`
+            let float = input.parse::<LitFloat>()?;
+            for index in split_float(&float)? {
+                keys.push(Key::Index(index));
+            }
+        } else if input.peek(LitInt) {
+            let index = input.parse::<LitInt>()?;
+            if !index.suffix().is_empty() {
+                return Err(syn::Error::new_spanned(
+                    index,
+                    "field indices cannot have a suffix",
+                ));
+            }
+            keys.push(Key::Index(index));
+        } else {
+            keys.push(Key::Field(input.call(Ident::parse_any)?));
+        }
+    } else if lookahead.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        keys.push(Key::Get(content.parse()?));
+    } else if lookahead.peek(Token![::]) {
+        input.parse::<Token![::]>()?;
+        keys.push(Key::Variant(input.call(Ident::parse_any)?));
+    } else {
+        return Err(lookahead.error());
+    }
+    Ok(())
+}
+
+fn split_float(float: &LitFloat) -> syn::Result<Vec<LitInt>> {
+    let digits = float.to_string();
+    let parts = digits.split('.').collect::<Vec<_>>();
+    let is_valid = float.suffix().is_empty()
+        && parts.len() == 2
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    if !is_valid {
+        return Err(syn::Error::new_spanned(float, "expected field index"));
+    }
+    Ok(parts
+        .into_iter()
+        // not using the span of the float since clippy then thinks `0.1` is the zero prefixed
+        // integer `01`
+        .map(|part| LitInt::new(part, Span::call_site()))
+        .collect())
+}
+
+pub(crate) fn expand(input: KeyPathInput) -> syn::Result<TokenStream> {
+    let KeyPathInput {
+        crate_name,
+        ty,
+        keys,
+    } = input;
+
+    let keys = keys.into_iter().map(|key| match key {
+        Key::Field(ident) => {
+            let name = ident.unraw().to_string();
+            quote_spanned! {ident.span()=> .field(#name) }
+        }
+        Key::Index(index) => {
+            let index = LitInt::new(&format!("{}_usize", index.base10_digits()), index.span());
+            quote! { .field(#index) }
+        }
+        Key::Get(expr) => quote_spanned! {expr.span()=> .get(#expr) },
+        Key::Variant(ident) => {
+            let name = ident.unraw().to_string();
+            quote_spanned! {ident.span()=> .variant(#name) }
+        }
+    });
+
+    let path = quote! {
+        #crate_name::key_path::KeyPath::default() #(#keys)*
+    };
+
+    Ok(match ty {
+        Some(ty) => quote! {
+            #crate_name::__private::typed_key_path::<#ty>(#path)
+        },
+        None => path,
+    })
+}
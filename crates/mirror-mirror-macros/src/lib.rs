@@ -2,7 +2,7 @@
     clippy::all,
     clippy::dbg_macro,
     clippy::todo,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::enum_glob_use,
     clippy::mem_forget,
     clippy::unused_self,
@@ -11,9 +11,7 @@
     clippy::needless_borrow,
     clippy::match_wildcard_for_single_variants,
     clippy::if_let_mutex,
-    clippy::mismatched_target_os,
     clippy::await_holding_lock,
-    clippy::match_on_vec_items,
     clippy::imprecise_flops,
     clippy::suboptimal_flops,
     clippy::lossy_float_literal,
@@ -47,6 +45,7 @@ use quote::ToTokens;
 use syn::parse::Parse;
 
 mod derive_reflect;
+mod key_path;
 
 /// Derive an implementation of `Reflect` and other appropriate traits.
 ///
@@ -258,6 +257,13 @@ pub fn __private_derive_reflect_foreign(item: TokenStream) -> TokenStream {
     expand_with(item, derive_reflect::expand)
 }
 
+/// Private API: Do not use!
+#[proc_macro]
+#[doc(hidden)]
+pub fn __private_key_path(item: TokenStream) -> TokenStream {
+    expand_with(item, key_path::expand)
+}
+
 fn expand_with<F, I, K>(input: TokenStream, f: F) -> TokenStream
 where
     F: FnOnce(I) -> syn::Result<K>,
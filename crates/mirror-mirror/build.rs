@@ -0,0 +1,24 @@
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(has_saturating)");
+
+    // `core::num::Saturating` was stabilized in Rust 1.74
+    if matches!(rustc_minor_version(), Some(minor) if minor >= 74) {
+        println!("cargo:rustc-cfg=has_saturating");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // such as "rustc 1.74.0 (79e9716c9 2023-11-13)"
+    let mut parts = version.split(' ').nth(1)?.split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use core::fmt;
 use core::iter::FusedIterator;
 
@@ -25,15 +26,35 @@ impl fmt::Debug for dyn Array {
     }
 }
 
+/// An iterator over the elements of an [`Array`].
 #[derive(Debug)]
 pub struct Iter<'a> {
-    index: usize,
-    array: &'a dyn Array,
+    inner: IterInner<'a>,
+}
+
+enum IterInner<'a> {
+    Indexed { index: usize, array: &'a dyn Array },
+    Boxed(Box<dyn ExactSizeIterator<Item = &'a dyn Reflect> + 'a>),
 }
 
 impl<'a> Iter<'a> {
+    /// Iterate over `array` by calling [`Array::get`] with increasing indices.
     pub fn new(array: &'a dyn Array) -> Self {
-        Self { index: 0, array }
+        Self {
+            inner: IterInner::Indexed { index: 0, array },
+        }
+    }
+
+    /// Iterate over the elements yielded by `iter`.
+    ///
+    /// Useful for types such as `LinkedList` where [`Array::get`] isn't O(1).
+    pub fn boxed<I>(iter: I) -> Self
+    where
+        I: ExactSizeIterator<Item = &'a dyn Reflect> + 'a,
+    {
+        Self {
+            inner: IterInner::Boxed(Box::new(iter.fuse())),
+        }
     }
 }
 
@@ -41,16 +62,37 @@ impl<'a> Iterator for Iter<'a> {
     type Item = &'a dyn Reflect;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let value = self.array.get(self.index)?;
-        self.index += 1;
-        Some(value)
+        match &mut self.inner {
+            IterInner::Indexed { index, array } => {
+                let value = array.get(*index)?;
+                *index += 1;
+                Some(value)
+            }
+            IterInner::Boxed(iter) => iter.next(),
+        }
     }
 }
 
 impl<'a> ExactSizeIterator for Iter<'a> {
     fn len(&self) -> usize {
-        self.array.len()
+        match &self.inner {
+            IterInner::Indexed { array, .. } => array.len(),
+            IterInner::Boxed(iter) => iter.len(),
+        }
     }
 }
 
 impl<'a> FusedIterator for Iter<'a> {}
+
+impl fmt::Debug for IterInner<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IterInner::Indexed { index, array } => f
+                .debug_struct("Indexed")
+                .field("index", index)
+                .field("array", array)
+                .finish(),
+            IterInner::Boxed(iter) => f.debug_struct("Boxed").field("len", &iter.len()).finish(),
+        }
+    }
+}
@@ -0,0 +1,1019 @@
+//! [`Value`] is self-describing, so its serialized form contains field names, variant names, and
+//! a tag for every value. When both sides know the type, that information is redundant.
+//! [`serialize`] writes only the data, in the order given by the type, and [`deserialize`] reads
+//! it back into a [`Value`] using the same type:
+//!
+//! - Integers are [LEB128] varints, with signed integers zigzag encoded. `usize` is encoded like
+//!   `u64` so the data is portable between platforms.
+//! - `f32` and `f64` are little-endian bytes, `bool` is a single byte, and `char` is a varint.
+//! - Strings, lists, maps, and sets are prefixed with their length as a varint. Arrays aren't,
+//!   since the length is part of the type.
+//! - Map entries and set elements are sorted by their keys, so equal maps and sets are encoded
+//!   to the same bytes however they iterate.
+//! - Structs, tuple structs, and tuples are their fields in order, without names.
+//! - Enums are the index of the variant as a varint, followed by its fields.
+//!
+//! Types like `Duration`, `Uuid`, and `NonZeroU32` are encoded as the scalar they're described
+//! as. Opaque types don't describe their contents and cannot be encoded.
+//!
+//! The encoding isn't versioned. Data can only be read with the type it was written with, so
+//! adding, removing, or reordering fields or variants makes existing data unreadable.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::{compact, DescribeType, FromReflect, Reflect};
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     health: u32,
+//!     position: [f32; 2],
+//! }
+//!
+//! let player = Player {
+//!     name: "Ferris".to_owned(),
+//!     health: 100,
+//!     position: [1.0, 2.0],
+//! };
+//!
+//! let ty = <Player as DescribeType>::type_descriptor();
+//! let bytes = compact::serialize(&player, ty.get_type()).unwrap();
+//! assert_eq!(bytes.len(), 16);
+//!
+//! let value = compact::deserialize(&bytes, ty.get_type()).unwrap();
+//! assert_eq!(Player::from_reflect(&value).unwrap(), player);
+//! ```
+//!
+//! [`Value`]: crate::Value
+//! [`serialize`]: crate::compact::serialize
+//! [`deserialize`]: crate::compact::deserialize
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::enum_::EnumValue;
+use crate::key_path::Key;
+use crate::key_path::KeyPath;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::type_info::ScalarType;
+use crate::type_info::Type;
+use crate::type_info::Variant;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// Encode `value` as type `ty`.
+///
+/// Values behind locks and other guards are looked through, so `value` doesn't have to be
+/// exactly of type `ty`, only shaped like it. A [`Value`] of the right shape works as well.
+pub fn serialize(value: &dyn Reflect, ty: Type<'_>) -> Result<Vec<u8>, SerializeError> {
+    let mut out = Vec::new();
+    serialize_into(value, ty, &mut out)?;
+    Ok(out)
+}
+
+/// Like [`serialize`] but appends the bytes to `out`.
+///
+/// `out` might contain a partially encoded value if an error is returned.
+pub fn serialize_into(
+    value: &dyn Reflect,
+    ty: Type<'_>,
+    out: &mut Vec<u8>,
+) -> Result<(), SerializeError> {
+    Serializer {
+        out,
+        key_path: KeyPath::default(),
+    }
+    .serialize(value, ty)
+}
+
+/// Decode a value of type `ty` from `bytes`, written by [`serialize`].
+///
+/// All of `bytes` must be used. Values can be nested at most [`DEFAULT_MAX_DEPTH`] levels deep,
+/// use [`deserialize_with_max_depth`] to change that.
+pub fn deserialize(bytes: &[u8], ty: Type<'_>) -> Result<Value, DeserializeError> {
+    deserialize_with_max_depth(bytes, ty, DEFAULT_MAX_DEPTH)
+}
+
+/// How deeply values can be nested by default when deserializing.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Like [`deserialize`] but values can be nested at most `max_depth` levels deep.
+///
+/// Recursive types such as `enum List { Cons(Box<List>), Nil }` can be nested as deeply as the
+/// input says, so the limit keeps untrusted input from overflowing the stack. Each level of
+/// nesting uses a few stack frames, so very large limits don't protect against that.
+pub fn deserialize_with_max_depth(
+    bytes: &[u8],
+    ty: Type<'_>,
+    max_depth: usize,
+) -> Result<Value, DeserializeError> {
+    let mut deserializer = Deserializer {
+        bytes,
+        offset: 0,
+        depth: 0,
+        max_depth,
+    };
+    let value = deserializer.deserialize(ty)?;
+    if deserializer.offset != bytes.len() {
+        return Err(deserializer.error(DeserializeErrorKind::TrailingBytes));
+    }
+    Ok(value)
+}
+
+/// The error returned by [`serialize`] and [`serialize_into`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerializeError {
+    path: KeyPath,
+    kind: SerializeErrorKind,
+}
+
+impl SerializeError {
+    /// The key path of the value that couldn't be serialized.
+    pub fn path(&self) -> &KeyPath {
+        &self.path
+    }
+
+    /// Why the value couldn't be serialized.
+    pub fn kind(&self) -> &SerializeErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SerializeErrorKind::TypeMismatch { expected, actual } => {
+                write!(f, "expected `{expected}`, found `{actual}`")?
+            }
+            SerializeErrorKind::MissingField => write!(f, "missing field")?,
+            SerializeErrorKind::UnknownVariant(name) => write!(f, "unknown variant `{name}`")?,
+            SerializeErrorKind::LengthMismatch { expected, actual } => write!(
+                f,
+                "expected an array of length {expected}, found length {actual}"
+            )?,
+            SerializeErrorKind::Opaque(type_name) => {
+                write!(f, "`{type_name}` is opaque and cannot be serialized")?
+            }
+        }
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerializeError {}
+
+/// Why a value couldn't be serialized. See [`SerializeError::kind`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SerializeErrorKind {
+    /// The value has a different shape than the type.
+    TypeMismatch {
+        /// The type name that was expected.
+        expected: String,
+        /// The type name of the value.
+        actual: String,
+    },
+    /// A field the type has is missing from the value.
+    MissingField,
+    /// The value is an enum variant the type doesn't have.
+    UnknownVariant(String),
+    /// An array has a different length than the type.
+    LengthMismatch {
+        /// The length of the array type.
+        expected: usize,
+        /// The length of the value.
+        actual: usize,
+    },
+    /// The type is opaque so it isn't known how to encode the value.
+    Opaque(String),
+}
+
+/// The error returned by [`deserialize`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeserializeError {
+    offset: usize,
+    kind: DeserializeErrorKind,
+}
+
+impl DeserializeError {
+    /// The offset in the input where the error happened.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Why the input couldn't be deserialized.
+    pub fn kind(&self) -> &DeserializeErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            DeserializeErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            DeserializeErrorKind::TrailingBytes => write!(f, "trailing bytes after the value")?,
+            DeserializeErrorKind::Overflow(type_name) => {
+                write!(f, "number doesn't fit in `{type_name}`")?
+            }
+            DeserializeErrorKind::InvalidBool(byte) => write!(f, "invalid bool `{byte}`")?,
+            DeserializeErrorKind::InvalidChar(n) => write!(f, "invalid char `{n:#x}`")?,
+            DeserializeErrorKind::InvalidUtf8 => write!(f, "string isn't valid UTF-8")?,
+            DeserializeErrorKind::InvalidVariant { index, type_name } => {
+                write!(f, "`{type_name}` doesn't have a variant with index {index}")?
+            }
+            DeserializeErrorKind::Opaque(type_name) => {
+                write!(f, "`{type_name}` is opaque and cannot be deserialized")?
+            }
+            DeserializeErrorKind::Zero(type_name) => write!(f, "`{type_name}` cannot be zero")?,
+            DeserializeErrorKind::TooDeep => write!(f, "values are nested too deeply")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializeError {}
+
+/// Why the input couldn't be deserialized. See [`DeserializeError::kind`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeserializeErrorKind {
+    /// The input ended before the value did.
+    UnexpectedEnd,
+    /// The input continues after the value.
+    TrailingBytes,
+    /// A number, or the length of a string or collection, doesn't fit in the given type.
+    Overflow(&'static str),
+    /// A `bool` that isn't `0` or `1`.
+    InvalidBool(u8),
+    /// A `char` that isn't a valid Unicode scalar value.
+    InvalidChar(u32),
+    /// A string that isn't valid UTF-8.
+    InvalidUtf8,
+    /// An enum variant index that's out of bounds.
+    InvalidVariant {
+        /// The index that was read.
+        index: u128,
+        /// The name of the enum type.
+        type_name: String,
+    },
+    /// The type is opaque so it isn't known how to decode the value.
+    Opaque(String),
+    /// A zero for one of the `NonZero*` types.
+    Zero(&'static str),
+    /// Values are nested more deeply than the maximum depth.
+    TooDeep,
+}
+
+struct Serializer<'a> {
+    out: &'a mut Vec<u8>,
+    key_path: KeyPath,
+}
+
+impl Serializer<'_> {
+    fn serialize(&mut self, value: &dyn Reflect, ty: Type<'_>) -> Result<(), SerializeError> {
+        let guard = value.reflect_guard();
+        let value = &*guard;
+
+        match (ty, value.reflect_ref()) {
+            (Type::Scalar(scalar_ty), ReflectRef::Scalar(scalar)) => {
+                self.serialize_scalar(scalar_ty, scalar, value)
+            }
+            (Type::Struct(struct_ty), ReflectRef::Struct(struct_)) => {
+                for field_ty in struct_ty.field_types() {
+                    self.key_path.push(Key::named_field(field_ty.name()));
+                    let field = struct_
+                        .field(field_ty.name())
+                        .ok_or_else(|| self.error(SerializeErrorKind::MissingField))?;
+                    self.serialize(field, field_ty.get_type())?;
+                    self.key_path.pop();
+                }
+                Ok(())
+            }
+            (Type::TupleStruct(tuple_struct_ty), ReflectRef::TupleStruct(tuple_struct)) => {
+                for (index, field_ty) in tuple_struct_ty.field_types().enumerate() {
+                    self.key_path.push(Key::numbered_field(index));
+                    let field = tuple_struct
+                        .field_at(index)
+                        .ok_or_else(|| self.error(SerializeErrorKind::MissingField))?;
+                    self.serialize(field, field_ty.get_type())?;
+                    self.key_path.pop();
+                }
+                Ok(())
+            }
+            (Type::Tuple(tuple_ty), ReflectRef::Tuple(tuple)) => {
+                for (index, field_ty) in tuple_ty.field_types().enumerate() {
+                    self.key_path.push(Key::numbered_field(index));
+                    let field = tuple
+                        .field_at(index)
+                        .ok_or_else(|| self.error(SerializeErrorKind::MissingField))?;
+                    self.serialize(field, field_ty.get_type())?;
+                    self.key_path.pop();
+                }
+                Ok(())
+            }
+            (Type::Enum(enum_ty), ReflectRef::Enum(enum_)) => {
+                let (index, variant_ty) = enum_ty
+                    .variants()
+                    .enumerate()
+                    .find(|(_, variant_ty)| variant_ty.name() == enum_.variant_name())
+                    .ok_or_else(|| {
+                        self.error(SerializeErrorKind::UnknownVariant(
+                            enum_.variant_name().to_owned(),
+                        ))
+                    })?;
+                write_varint(self.out, index as u128);
+
+                self.key_path
+                    .push(Key::Variant(variant_ty.name().to_owned()));
+                for (index, field_ty) in variant_ty.field_types().enumerate() {
+                    let (key, field) = match field_ty.name() {
+                        Some(name) => (Key::named_field(name), enum_.field(name)),
+                        None => (Key::numbered_field(index), enum_.field_at(index)),
+                    };
+                    self.key_path.push(key);
+                    let field =
+                        field.ok_or_else(|| self.error(SerializeErrorKind::MissingField))?;
+                    self.serialize(field, field_ty.get_type())?;
+                    self.key_path.pop();
+                }
+                self.key_path.pop();
+                Ok(())
+            }
+            (Type::List(list_ty), ReflectRef::List(list)) => {
+                write_varint(self.out, list.len() as u128);
+                self.serialize_elements(list.iter(), list_ty.element_type())
+            }
+            (Type::List(list_ty), ReflectRef::Array(array)) => {
+                write_varint(self.out, array.len() as u128);
+                self.serialize_elements(array.iter(), list_ty.element_type())
+            }
+            (Type::Array(array_ty), ReflectRef::Array(array)) => {
+                self.check_len(array_ty.len(), array.len())?;
+                self.serialize_elements(array.iter(), array_ty.element_type())
+            }
+            // arrays are converted to lists by `to_value`
+            (Type::Array(array_ty), ReflectRef::List(list)) => {
+                self.check_len(array_ty.len(), list.len())?;
+                self.serialize_elements(list.iter(), array_ty.element_type())
+            }
+            // entries are sorted so maps and sets that iterate in any order, such as `HashMap`
+            // and `HashSet`, encode equal values to the same bytes
+            (Type::Map(map_ty), ReflectRef::Map(map)) => {
+                write_varint(self.out, map.len() as u128);
+                let mut entries = map
+                    .iter()
+                    .map(|(key, value)| (key.to_value(), key, value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                for (key_value, key, value) in entries {
+                    self.key_path.push(Key::Get(key_value));
+                    self.serialize(key, map_ty.key_type())?;
+                    self.serialize(value, map_ty.value_type())?;
+                    self.key_path.pop();
+                }
+                Ok(())
+            }
+            (Type::Set(set_ty), ReflectRef::Set(set)) => {
+                write_varint(self.out, set.len() as u128);
+                let mut elements = set
+                    .iter()
+                    .map(|element| (element.to_value(), element))
+                    .collect::<Vec<_>>();
+                elements.sort_by(|a, b| a.0.cmp(&b.0));
+                for (element_value, element) in elements {
+                    self.key_path.push(Key::Get(element_value));
+                    self.serialize(element, set_ty.element_type())?;
+                    self.key_path.pop();
+                }
+                Ok(())
+            }
+            (Type::Opaque(opaque_ty), _) => {
+                Err(self.error(SerializeErrorKind::Opaque(opaque_ty.type_name().to_owned())))
+            }
+            (
+                Type::Scalar(_)
+                | Type::Struct(_)
+                | Type::TupleStruct(_)
+                | Type::Tuple(_)
+                | Type::Enum(_)
+                | Type::List(_)
+                | Type::Array(_)
+                | Type::Map(_)
+                | Type::Set(_),
+                _,
+            ) => Err(self.type_mismatch(ty, value)),
+        }
+    }
+
+    fn serialize_elements<'a>(
+        &mut self,
+        elements: impl Iterator<Item = &'a dyn Reflect>,
+        element_ty: Type<'_>,
+    ) -> Result<(), SerializeError> {
+        for (index, element) in elements.enumerate() {
+            self.key_path.push(Key::Get(index.into()));
+            self.serialize(element, element_ty)?;
+            self.key_path.pop();
+        }
+        Ok(())
+    }
+
+    fn serialize_scalar(
+        &mut self,
+        ty: ScalarType,
+        scalar: ScalarRef<'_>,
+        value: &dyn Reflect,
+    ) -> Result<(), SerializeError> {
+        let out = &mut *self.out;
+        match (ty.underlying(), scalar) {
+            (ScalarType::usize, ScalarRef::usize(n)) => write_varint(out, n as u128),
+            (ScalarType::u8, ScalarRef::u8(n)) => write_varint(out, n.into()),
+            (ScalarType::u16, ScalarRef::u16(n)) => write_varint(out, n.into()),
+            (ScalarType::u32, ScalarRef::u32(n)) => write_varint(out, n.into()),
+            (ScalarType::u64, ScalarRef::u64(n)) => write_varint(out, n.into()),
+            (ScalarType::u128, ScalarRef::u128(n)) => write_varint(out, n),
+            (ScalarType::i8, ScalarRef::i8(n)) => write_varint(out, zigzag(n.into())),
+            (ScalarType::i16, ScalarRef::i16(n)) => write_varint(out, zigzag(n.into())),
+            (ScalarType::i32, ScalarRef::i32(n)) => write_varint(out, zigzag(n.into())),
+            (ScalarType::i64, ScalarRef::i64(n)) => write_varint(out, zigzag(n.into())),
+            (ScalarType::i128, ScalarRef::i128(n)) => write_varint(out, zigzag(n)),
+            (ScalarType::bool, ScalarRef::bool(b)) => out.push(b.into()),
+            (ScalarType::char, ScalarRef::char(c)) => write_varint(out, u32::from(c).into()),
+            (ScalarType::f32, ScalarRef::f32(n)) => out.extend_from_slice(&n.to_le_bytes()),
+            (ScalarType::f64, ScalarRef::f64(n)) => out.extend_from_slice(&n.to_le_bytes()),
+            (ScalarType::String, ScalarRef::String(s)) => {
+                write_varint(out, s.len() as u128);
+                out.extend_from_slice(s.as_bytes());
+            }
+            _ => return Err(self.type_mismatch(Type::Scalar(ty), value)),
+        }
+        Ok(())
+    }
+
+    fn check_len(&self, expected: usize, actual: usize) -> Result<(), SerializeError> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(self.error(SerializeErrorKind::LengthMismatch { expected, actual }))
+        }
+    }
+
+    fn type_mismatch(&self, ty: Type<'_>, value: &dyn Reflect) -> SerializeError {
+        self.error(SerializeErrorKind::TypeMismatch {
+            expected: ty.type_name().to_owned(),
+            actual: value.type_name().to_owned(),
+        })
+    }
+
+    fn error(&self, kind: SerializeErrorKind) -> SerializeError {
+        SerializeError {
+            path: self.key_path.clone(),
+            kind,
+        }
+    }
+}
+
+struct Deserializer<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Deserializer<'_> {
+    fn deserialize(&mut self, ty: Type<'_>) -> Result<Value, DeserializeError> {
+        if self.depth == self.max_depth {
+            return Err(self.error(DeserializeErrorKind::TooDeep));
+        }
+        self.depth += 1;
+        let value = self.deserialize_nested(ty);
+        self.depth -= 1;
+        value
+    }
+
+    fn deserialize_nested(&mut self, ty: Type<'_>) -> Result<Value, DeserializeError> {
+        let value = match ty {
+            Type::Scalar(scalar_ty) => self.deserialize_scalar(scalar_ty)?,
+            Type::Struct(struct_ty) => {
+                let mut struct_ = StructValue::with_capacity(struct_ty.fields_len());
+                for field_ty in struct_ty.field_types() {
+                    let field = self.deserialize(field_ty.get_type())?;
+                    struct_.set_field(field_ty.name(), field);
+                }
+                struct_.into()
+            }
+            Type::TupleStruct(tuple_struct_ty) => {
+                let mut tuple_struct =
+                    TupleStructValue::with_capacity(tuple_struct_ty.fields_len());
+                for field_ty in tuple_struct_ty.field_types() {
+                    tuple_struct.push_field(self.deserialize(field_ty.get_type())?);
+                }
+                tuple_struct.into()
+            }
+            Type::Tuple(tuple_ty) => {
+                let mut tuple = TupleValue::with_capacity(tuple_ty.fields_len());
+                for field_ty in tuple_ty.field_types() {
+                    tuple.push_field(self.deserialize(field_ty.get_type())?);
+                }
+                tuple.into()
+            }
+            Type::Enum(enum_ty) => {
+                let offset = self.offset;
+                let index = self.read_varint()?;
+                let variant_ty = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| enum_ty.variants().nth(index))
+                    .ok_or_else(|| DeserializeError {
+                        offset,
+                        kind: DeserializeErrorKind::InvalidVariant {
+                            index,
+                            type_name: enum_ty.type_name().to_owned(),
+                        },
+                    })?;
+                self.deserialize_variant(variant_ty)?.into()
+            }
+            Type::List(list_ty) => {
+                let len = self.read_len()?;
+                Value::List(self.deserialize_elements(len, list_ty.element_type())?)
+            }
+            Type::Array(array_ty) => {
+                Value::List(self.deserialize_elements(array_ty.len(), array_ty.element_type())?)
+            }
+            Type::Map(map_ty) => {
+                let len = self.read_len()?;
+                let mut map = BTreeMap::new();
+                for _ in 0..len {
+                    let key = self.deserialize(map_ty.key_type())?;
+                    let value = self.deserialize(map_ty.value_type())?;
+                    map.insert(key, value);
+                }
+                Value::Map(map)
+            }
+            Type::Set(set_ty) => {
+                let len = self.read_len()?;
+                let mut set = BTreeSet::new();
+                for _ in 0..len {
+                    set.insert(self.deserialize(set_ty.element_type())?);
+                }
+                Value::Set(set)
+            }
+            Type::Opaque(opaque_ty) => {
+                return Err(self.error(DeserializeErrorKind::Opaque(
+                    opaque_ty.type_name().to_owned(),
+                )))
+            }
+        };
+        Ok(value)
+    }
+
+    fn deserialize_variant(
+        &mut self,
+        variant_ty: Variant<'_>,
+    ) -> Result<EnumValue, DeserializeError> {
+        let name = variant_ty.name();
+        let fields_len = variant_ty.fields_len();
+        let variant = match variant_ty {
+            Variant::Struct(_) => {
+                let mut variant = EnumValue::new_struct_variant_with_capacity(name, fields_len);
+                for field_ty in variant_ty.field_types() {
+                    let field = self.deserialize(field_ty.get_type())?;
+                    variant.set_struct_field(field_ty.name().unwrap(), field);
+                }
+                variant.finish()
+            }
+            Variant::Tuple(_) => {
+                let mut variant = EnumValue::new_tuple_variant_with_capacity(name, fields_len);
+                for field_ty in variant_ty.field_types() {
+                    variant.push_tuple_field(self.deserialize(field_ty.get_type())?);
+                }
+                variant.finish()
+            }
+            Variant::Unit(_) => EnumValue::new_unit_variant(name),
+        };
+        Ok(variant)
+    }
+
+    fn deserialize_elements(
+        &mut self,
+        len: usize,
+        element_ty: Type<'_>,
+    ) -> Result<Vec<Value>, DeserializeError> {
+        // every element takes at least one byte so don't trust lengths longer than the input
+        let mut elements = Vec::with_capacity(len.min(self.bytes.len() - self.offset));
+        for _ in 0..len {
+            elements.push(self.deserialize(element_ty)?);
+        }
+        Ok(elements)
+    }
+
+    fn deserialize_scalar(&mut self, ty: ScalarType) -> Result<Value, DeserializeError> {
+        let offset = self.offset;
+        let value = match ty.underlying() {
+            ScalarType::usize => Value::usize(self.read_uint("usize")?),
+            ScalarType::u8 => Value::u8(self.read_uint("u8")?),
+            ScalarType::u16 => Value::u16(self.read_uint("u16")?),
+            ScalarType::u32 => Value::u32(self.read_uint("u32")?),
+            ScalarType::u64 => Value::u64(self.read_uint("u64")?),
+            ScalarType::u128 => Value::u128(self.read_varint()?),
+            ScalarType::i8 => Value::i8(self.read_int("i8")?),
+            ScalarType::i16 => Value::i16(self.read_int("i16")?),
+            ScalarType::i32 => Value::i32(self.read_int("i32")?),
+            ScalarType::i64 => Value::i64(self.read_int("i64")?),
+            ScalarType::i128 => Value::i128(unzigzag(self.read_varint()?)),
+            ScalarType::bool => {
+                let offset = self.offset;
+                match self.read_bytes(1)?[0] {
+                    0 => Value::bool(false),
+                    1 => Value::bool(true),
+                    byte => {
+                        return Err(DeserializeError {
+                            offset,
+                            kind: DeserializeErrorKind::InvalidBool(byte),
+                        })
+                    }
+                }
+            }
+            ScalarType::char => {
+                let offset = self.offset;
+                let n = self.read_uint::<u32>("char")?;
+                let c = char::from_u32(n).ok_or(DeserializeError {
+                    offset,
+                    kind: DeserializeErrorKind::InvalidChar(n),
+                })?;
+                Value::char(c)
+            }
+            ScalarType::f32 => Value::f32(f32::from_le_bytes(self.read_array()?)),
+            ScalarType::f64 => Value::f64(f64::from_le_bytes(self.read_array()?)),
+            ScalarType::String => {
+                let len = self.read_len()?;
+                let offset = self.offset;
+                let bytes = self.read_bytes(len)?;
+                let string = core::str::from_utf8(bytes).map_err(|_| DeserializeError {
+                    offset,
+                    kind: DeserializeErrorKind::InvalidUtf8,
+                })?;
+                Value::String(string.to_owned())
+            }
+            _ => unreachable!("`underlying` returns the scalar `NonZero*` types contain"),
+        };
+        if !ty.allows(&value) {
+            return Err(DeserializeError {
+                offset,
+                kind: DeserializeErrorKind::Zero(ty.type_name()),
+            });
+        }
+        Ok(value)
+    }
+
+    fn read_len(&mut self) -> Result<usize, DeserializeError> {
+        self.read_uint("usize")
+    }
+
+    fn read_uint<T>(&mut self, type_name: &'static str) -> Result<T, DeserializeError>
+    where
+        T: TryFrom<u128>,
+    {
+        let offset = self.offset;
+        T::try_from(self.read_varint()?).map_err(|_| DeserializeError {
+            offset,
+            kind: DeserializeErrorKind::Overflow(type_name),
+        })
+    }
+
+    fn read_int<T>(&mut self, type_name: &'static str) -> Result<T, DeserializeError>
+    where
+        T: TryFrom<i128>,
+    {
+        let offset = self.offset;
+        T::try_from(unzigzag(self.read_varint()?)).map_err(|_| DeserializeError {
+            offset,
+            kind: DeserializeErrorKind::Overflow(type_name),
+        })
+    }
+
+    fn read_varint(&mut self) -> Result<u128, DeserializeError> {
+        let offset = self.offset;
+        let mut n = 0_u128;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_bytes(1)?[0];
+            let bits = u128::from(byte & 0x7f);
+            if shift >= 128 || (shift > 0 && bits >> (128 - shift) != 0) {
+                return Err(DeserializeError {
+                    offset,
+                    kind: DeserializeErrorKind::Overflow("u128"),
+                });
+            }
+            n |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&[u8], DeserializeError> {
+        let bytes = self
+            .bytes
+            .get(self.offset..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| self.error(DeserializeErrorKind::UnexpectedEnd))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn error(&self, kind: DeserializeErrorKind) -> DeserializeError {
+        DeserializeError {
+            offset: self.offset,
+            kind,
+        }
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut n: u128) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn zigzag(n: i128) -> u128 {
+    ((n << 1) ^ (n >> 127)) as u128
+}
+
+fn unzigzag(n: u128) -> i128 {
+    ((n >> 1) as i128) ^ -((n & 1) as i128)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::vec;
+    use core::num::NonZeroU8;
+    use core::time::Duration;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::key_path;
+    use crate::DescribeType;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Save {
+        name: String,
+        level: u8,
+        offset: i64,
+        position: [f32; 3],
+        inventory: Vec<Item>,
+        flags: BTreeSet<char>,
+        scores: HashMap<String, u128>,
+        pair: (bool, f64),
+        id: Id,
+        best: Option<i128>,
+        played: Duration,
+        lives: NonZeroU8,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        Sword { damage: u32 },
+        Potion(u16, u16),
+        Key,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Id(usize);
+
+    fn save() -> Save {
+        Save {
+            name: "Ferris".to_owned(),
+            level: 12,
+            offset: -300,
+            position: [1.0, 2.0, 3.0],
+            inventory: vec![Item::Sword { damage: 50 }, Item::Potion(1, 2), Item::Key],
+            flags: BTreeSet::from(['a', '🦀']),
+            scores: HashMap::from([("level 1".to_owned(), u128::MAX)]),
+            pair: (true, -0.5),
+            id: Id(1234),
+            best: Some(i128::MIN),
+            played: Duration::from_millis(1500),
+            lives: NonZeroU8::new(3).unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let save = save();
+        let ty = <Save as DescribeType>::type_descriptor();
+
+        let bytes = serialize(&save, ty.get_type()).unwrap();
+        let value = deserialize(&bytes, ty.get_type()).unwrap();
+        assert_eq!(Save::from_reflect(&value).unwrap(), save);
+
+        // the same bytes are written for values
+        assert_eq!(serialize(&value, ty.get_type()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn maps_and_sets_are_sorted() {
+        let ty = <HashMap<u32, u8> as DescribeType>::type_descriptor();
+        let map = (0..100_u32)
+            .map(|n| (n, n as u8))
+            .collect::<HashMap<_, _>>();
+        let reversed = (0..100_u32)
+            .rev()
+            .map(|n| (n, n as u8))
+            .collect::<HashMap<_, _>>();
+        let bytes = serialize(&map, ty.get_type()).unwrap();
+        assert_eq!(serialize(&reversed, ty.get_type()).unwrap(), bytes);
+        assert_eq!(serialize(&map.to_value(), ty.get_type()).unwrap(), bytes);
+
+        let ty = <HashSet<String> as DescribeType>::type_descriptor();
+        let set = HashSet::from(["b".to_owned(), "a".to_owned(), "c".to_owned()]);
+        assert_eq!(
+            serialize(&set, ty.get_type()).unwrap(),
+            [3, 1, b'a', 1, b'b', 1, b'c']
+        );
+    }
+
+    #[test]
+    fn is_compact() {
+        fn bytes<T>(value: T) -> Vec<u8>
+        where
+            T: Reflect + DescribeType,
+        {
+            serialize(&value, <T as DescribeType>::type_descriptor().get_type()).unwrap()
+        }
+
+        assert_eq!(bytes(0_u64), [0]);
+        assert_eq!(bytes(300_u32), [0xac, 0x02]);
+        assert_eq!(bytes(-1_i32), [1]);
+        assert_eq!(bytes(1_i32), [2]);
+        assert_eq!(bytes(true), [1]);
+        assert_eq!(bytes(1.0_f32), 1.0_f32.to_le_bytes());
+        assert_eq!(bytes("hi".to_owned()), [2, b'h', b'i']);
+        assert_eq!(bytes(Some(1_u8)), [1, 1]);
+        assert_eq!(bytes(Item::Potion(1, 2)), [1, 1, 2]);
+        assert_eq!(bytes([1_u8, 2]), [1, 2]);
+        assert_eq!(bytes(vec![1_u8, 2]), [2, 1, 2]);
+        assert_eq!(bytes(u128::MAX).len(), 19);
+        assert_eq!(bytes(Duration::from_nanos(300)), [0xac, 0x02]);
+        assert_eq!(bytes(NonZeroU8::new(1).unwrap()), [1]);
+    }
+
+    #[test]
+    fn looks_through_guards() {
+        #[derive(Reflect, Debug)]
+        #[reflect(crate_name(crate), opt_out(Clone))]
+        struct Shared {
+            inner: Mutex<Vec<u8>>,
+            name: Box<str>,
+        }
+
+        let shared = Shared {
+            inner: Mutex::new(vec![1, 2]),
+            name: "a".into(),
+        };
+        let ty = <Shared as DescribeType>::type_descriptor();
+        let bytes = serialize(&shared, ty.get_type()).unwrap();
+        assert_eq!(bytes, [2, 1, 2, 1, b'a']);
+    }
+
+    #[test]
+    fn serialize_errors() {
+        let ty = <Save as DescribeType>::type_descriptor();
+
+        let mut value = save().to_value();
+        if let Value::StructValue(struct_) = &mut value {
+            struct_.set_field("level", 1_u16);
+        }
+        let err = serialize(&value, ty.get_type()).unwrap_err();
+        assert_eq!(err.path(), &key_path!(.level));
+        assert_eq!(err.to_string(), "expected `u8`, found `u16` at `.level`");
+
+        let err = serialize(
+            &vec![1_u8, 2],
+            <[u8; 3] as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &SerializeErrorKind::LengthMismatch {
+                expected: 3,
+                actual: 2
+            }
+        );
+
+        let err = serialize(
+            &StructValue::new(),
+            <StructValue as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap_err();
+        assert!(matches!(err.kind(), SerializeErrorKind::Opaque(_)));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        #[reflect(crate_name(crate))]
+        enum Chain {
+            Link(Box<Chain>),
+            End,
+        }
+
+        let ty = <Chain as DescribeType>::type_descriptor();
+
+        // every zero is another `Link`
+        let err = deserialize(&vec![0; 1_000_000], ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::TooDeep);
+        assert_eq!(err.offset(), DEFAULT_MAX_DEPTH);
+
+        let mut bytes = vec![0; 9];
+        bytes.push(1);
+        let value = deserialize_with_max_depth(&bytes, ty.get_type(), 10).unwrap();
+        assert_eq!(serialize(&value, ty.get_type()).unwrap(), bytes);
+        let err = deserialize_with_max_depth(&bytes, ty.get_type(), 9).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn deserialize_errors() {
+        let ty = <Save as DescribeType>::type_descriptor();
+        let bytes = serialize(&save(), ty.get_type()).unwrap();
+
+        let err = deserialize(&bytes[..bytes.len() - 1], ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::UnexpectedEnd);
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        let err = deserialize(&trailing, ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::TrailingBytes);
+        assert_eq!(err.offset(), bytes.len());
+
+        let u8_ty = <u8 as DescribeType>::type_descriptor();
+        let err = deserialize(&[0xac, 0x02], u8_ty.get_type()).unwrap_err();
+        assert_eq!(err.to_string(), "number doesn't fit in `u8` at offset 0");
+
+        let item_ty = <Item as DescribeType>::type_descriptor();
+        let err = deserialize(&[3], item_ty.get_type()).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            DeserializeErrorKind::InvalidVariant { index: 3, .. }
+        ));
+
+        let bool_ty = <bool as DescribeType>::type_descriptor();
+        let err = deserialize(&[2], bool_ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::InvalidBool(2));
+
+        let string_ty = <String as DescribeType>::type_descriptor();
+        let err = deserialize(&[1, 0xff], string_ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::InvalidUtf8);
+        assert_eq!(err.offset(), 1);
+
+        // lengths longer than the input don't allocate up front
+        let list_ty = <Vec<u8> as DescribeType>::type_descriptor();
+        let err = deserialize(&[0xff, 0xff, 0xff, 0xff, 0x0f], list_ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::UnexpectedEnd);
+
+        let u128_ty = <u128 as DescribeType>::type_descriptor();
+        let err = deserialize(&[0xff; 20], u128_ty.get_type()).unwrap_err();
+        assert_eq!(err.kind(), &DeserializeErrorKind::Overflow("u128"));
+
+        let non_zero_ty = <NonZeroU8 as DescribeType>::type_descriptor();
+        let err = deserialize(&[0], non_zero_ty.get_type()).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &DeserializeErrorKind::Zero(core::any::type_name::<NonZeroU8>())
+        );
+    }
+}
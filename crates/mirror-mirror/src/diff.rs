@@ -0,0 +1,1113 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::guard::with_guards;
+use crate::key_path::value_to_usize;
+use crate::key_path::GetPath;
+use crate::key_path::Key;
+use crate::key_path::KeyPath;
+use crate::reflect_eq;
+use crate::Array;
+use crate::Enum;
+use crate::List;
+use crate::Map;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectRef;
+use crate::Set;
+use crate::Struct;
+use crate::Tuple;
+use crate::TupleStruct;
+use crate::Value;
+
+/// A single difference between two reflected values.
+///
+/// Each change is addressed by a [`KeyPath`] relative to the root of the values being compared.
+/// See [`reflect_diff`] for how changes are computed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    /// The value at `path` was replaced by `value`.
+    Set { path: KeyPath, value: Value },
+    /// `value` was inserted into a list, map, or set.
+    ///
+    /// The last key in `path` is the index or map key the value was inserted at. For sets it's the
+    /// inserted element itself.
+    Insert { path: KeyPath, value: Value },
+    /// The list element, map entry, or set element at `path` was removed.
+    Remove { path: KeyPath },
+    /// The enum at `path` was changed to another variant, `value` being the new value.
+    SwitchVariant { path: KeyPath, value: Value },
+}
+
+impl Change {
+    /// The key path of the value this change applies to.
+    pub fn path(&self) -> &KeyPath {
+        match self {
+            Change::Set { path, .. }
+            | Change::Insert { path, .. }
+            | Change::Remove { path }
+            | Change::SwitchVariant { path, .. } => path,
+        }
+    }
+
+    /// Apply the change to `target`.
+    ///
+    /// The key path is resolved with [`GetPath::at_mut`]. Inserts and removes resolve everything
+    /// but the last key, which must be a [`Key::Get`] addressing a list index, map key, or set
+    /// element.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), ApplyError> {
+        match self {
+            Change::Set { path, value } => {
+                let target = target.at_mut(path).ok_or(ApplyError::PathNotFound)?;
+                target
+                    .try_patch(value)
+                    .map_err(|err| ApplyError::Patch(err.prefixed(path)))
+            }
+            Change::Insert { path, value } => {
+                let (parent, key) = split_element_path(path)?;
+                let parent = target.at_mut(&parent).ok_or(ApplyError::PathNotFound)?;
+                match parent.reflect_mut() {
+                    ReflectMut::List(list) => {
+                        let index = value_to_usize(key).ok_or(ApplyError::InvalidPath)?;
+                        if index > list.len() {
+                            return Err(ApplyError::IndexOutOfBounds);
+                        }
+                        if !list.try_insert(index, value) {
+                            return Err(ApplyError::Mismatch);
+                        }
+                        Ok(())
+                    }
+                    ReflectMut::Map(map) => {
+                        map.insert(key, value);
+                        match map.get(key) {
+                            Some(inserted) if reflect_eq(inserted, value) != Some(false) => Ok(()),
+                            Some(_) | None => Err(ApplyError::Mismatch),
+                        }
+                    }
+                    ReflectMut::Set(set) => {
+                        set.insert(value);
+                        if set.contains(value) {
+                            Ok(())
+                        } else {
+                            Err(ApplyError::Mismatch)
+                        }
+                    }
+                    ReflectMut::Struct(_)
+                    | ReflectMut::TupleStruct(_)
+                    | ReflectMut::Tuple(_)
+                    | ReflectMut::Enum(_)
+                    | ReflectMut::Array(_)
+                    | ReflectMut::Scalar(_)
+                    | ReflectMut::Opaque(_) => Err(ApplyError::NotListOrMap),
+                }
+            }
+            Change::Remove { path } => {
+                let (parent, key) = split_element_path(path)?;
+                let parent = target.at_mut(&parent).ok_or(ApplyError::PathNotFound)?;
+                match parent.reflect_mut() {
+                    ReflectMut::List(list) => {
+                        let index = value_to_usize(key).ok_or(ApplyError::InvalidPath)?;
+                        list.try_remove(index)
+                            .ok_or(ApplyError::IndexOutOfBounds)
+                            .map(drop)
+                    }
+                    ReflectMut::Map(map) => {
+                        map.remove(key).ok_or(ApplyError::PathNotFound).map(drop)
+                    }
+                    ReflectMut::Set(set) => {
+                        set.remove(key).ok_or(ApplyError::PathNotFound).map(drop)
+                    }
+                    ReflectMut::Struct(_)
+                    | ReflectMut::TupleStruct(_)
+                    | ReflectMut::Tuple(_)
+                    | ReflectMut::Enum(_)
+                    | ReflectMut::Array(_)
+                    | ReflectMut::Scalar(_)
+                    | ReflectMut::Opaque(_) => Err(ApplyError::NotListOrMap),
+                }
+            }
+            Change::SwitchVariant { path, value } => {
+                let target = target.at_mut(path).ok_or(ApplyError::PathNotFound)?;
+                if target.reflect_ref().as_enum().is_none() {
+                    return Err(ApplyError::NotAnEnum);
+                }
+                target
+                    .try_patch(value)
+                    .map_err(|err| ApplyError::Patch(err.prefixed(path)))
+            }
+        }
+    }
+}
+
+fn split_element_path(path: &KeyPath) -> Result<(KeyPath, &Value), ApplyError> {
+    match path.path.split_last() {
+        Some((Key::Get(key), parent)) => Ok((parent.iter().cloned().collect(), key)),
+        Some((Key::Field(_) | Key::Variant(_), _)) | None => Err(ApplyError::InvalidPath),
+    }
+}
+
+/// A list of [`Change`]s that can be serialized and applied to reflected values.
+///
+/// Usually created from the changes returned by [`reflect_diff`]:
+///
+/// ```
+/// use mirror_mirror::{Reflect, reflect_diff, diff::ChangeSet};
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Foo {
+///     n: i32,
+///     list: Vec<i32>,
+/// }
+///
+/// let old = Foo { n: 1, list: vec![1, 2, 3] };
+/// let new = Foo { n: 2, list: vec![1] };
+///
+/// let change_set = ChangeSet::from(reflect_diff(&old, &new).unwrap());
+///
+/// let mut value = old.clone();
+/// let results = change_set.apply(&mut value);
+///
+/// assert!(results.iter().all(|result| result.is_ok()));
+/// assert_eq!(value, new);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeSet {
+    changes: Vec<Change>,
+}
+
+impl ChangeSet {
+    /// Create an empty change set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a change to the end of the set, builder style.
+    pub fn with_change(mut self, change: Change) -> Self {
+        self.push(change);
+        self
+    }
+
+    /// Add a change to the end of the set.
+    pub fn push(&mut self, change: Change) {
+        self.changes.push(change);
+    }
+
+    /// The number of changes in the set.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if the set contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Iterate over the changes in the order they're applied.
+    pub fn iter(&self) -> core::slice::Iter<'_, Change> {
+        self.changes.iter()
+    }
+
+    /// Apply the changes to `target`, in order.
+    ///
+    /// A change that fails to apply doesn't stop the following ones from being applied. The
+    /// returned `Vec` contains the result of each change, in the same order as the changes.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Vec<Result<(), ApplyError>> {
+        self.changes
+            .iter()
+            .map(|change| change.apply(target))
+            .collect()
+    }
+}
+
+impl From<Vec<Change>> for ChangeSet {
+    fn from(changes: Vec<Change>) -> Self {
+        Self { changes }
+    }
+}
+
+impl FromIterator<Change> for ChangeSet {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Change>,
+    {
+        Self {
+            changes: Vec::from_iter(iter),
+        }
+    }
+}
+
+impl Extend<Change> for ChangeSet {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = Change>,
+    {
+        self.changes.extend(iter);
+    }
+}
+
+impl IntoIterator for ChangeSet {
+    type Item = Change;
+    type IntoIter = alloc::vec::IntoIter<Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ChangeSet {
+    type Item = &'a Change;
+    type IntoIter = core::slice::Iter<'a, Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The reason a [`Change`] couldn't be applied.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApplyError {
+    /// The key path, or the path to the list, map, or set for inserts and removes, didn't resolve
+    /// to a value.
+    PathNotFound,
+    /// The key path of an insert or remove doesn't end in a list index, map key, or set element.
+    InvalidPath,
+    /// The value an insert or remove targets isn't a list, map, or set.
+    NotListOrMap,
+    /// The list index of an insert or remove is out of bounds.
+    IndexOutOfBounds,
+    /// The value a variant switch targets isn't an enum.
+    NotAnEnum,
+    /// The value of an insert couldn't be converted into the element type of the list, map, or
+    /// set.
+    Mismatch,
+    /// Patching the value at the key path failed.
+    Patch(PatchError),
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyError::PathNotFound => write!(f, "no value found at key path"),
+            ApplyError::InvalidPath => {
+                write!(
+                    f,
+                    "key path doesn't end in a list index, map key, or set element"
+                )
+            }
+            ApplyError::NotListOrMap => write!(f, "value is not a list, map, or set"),
+            ApplyError::IndexOutOfBounds => write!(f, "list index out of bounds"),
+            ApplyError::NotAnEnum => write!(f, "value is not an enum"),
+            ApplyError::Mismatch => write!(f, "value doesn't match the element type"),
+            ApplyError::Patch(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ApplyError {}
+
+/// Compute the structural differences between two reflected values.
+///
+/// The changes describe how to get from `old` to `new`:
+///
+/// - Struct, tuple, and tuple struct fields are compared recursively. If the values don't have
+///   the same fields the whole value is [`Change::Set`].
+/// - Enums with the same variant have their fields compared recursively, otherwise a
+///   [`Change::SwitchVariant`] is emitted. Key paths into variant fields include the variant, such
+///   as `.foo::Some.0`.
+/// - Lists have their common prefix compared element by element. Extra elements in `new` become
+///   [`Change::Insert`]s and extra elements in `old` become [`Change::Remove`]s, ordered from the
+///   back so they can be applied one after the other.
+/// - Map entries are matched by key. Keys only in `old` are removed and keys only in `new` are
+///   inserted.
+/// - Scalars that differ, and values of different kinds, are [`Change::Set`].
+/// - Opaque values are compared by their [`Reflect::to_value`] and are [`Change::Set`] as a whole
+///   if they differ.
+///
+/// Returns an empty `Vec` if the values are equal and `None` if a value doesn't reflect itself
+/// consistently, such as a list that doesn't return an element within its length.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::{Reflect, reflect_diff, diff::Change, key_path};
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     name: String,
+///     items: Vec<u32>,
+/// }
+///
+/// let old = Player { name: "Bob".to_owned(), items: vec![1] };
+/// let new = Player { name: "Alice".to_owned(), items: vec![1, 2] };
+///
+/// let changes = reflect_diff(&old, &new).unwrap();
+///
+/// assert_eq!(
+///     changes,
+///     [
+///         Change::Set {
+///             path: key_path!(.name),
+///             value: "Alice".to_owned().into(),
+///         },
+///         Change::Insert {
+///             path: key_path!(.items[1_usize]),
+///             value: 2_u32.into(),
+///         },
+///     ],
+/// );
+/// ```
+pub fn reflect_diff(old: &dyn Reflect, new: &dyn Reflect) -> Option<Vec<Change>> {
+    let mut changes = Vec::new();
+    diff(old, new, &KeyPath::default(), &mut changes)?;
+    Some(changes)
+}
+
+fn diff(
+    old: &dyn Reflect,
+    new: &dyn Reflect,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    with_guards(old, new, |old, new| diff_guarded(old, new, path, changes))
+}
+
+fn diff_guarded(
+    old: &dyn Reflect,
+    new: &dyn Reflect,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Scalar(a), ReflectRef::Scalar(b)) => {
+            if a != b {
+                changes.push(set(path, new));
+            }
+        }
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) => diff_struct(a, b, path, changes)?,
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) => {
+            diff_tuple_struct(a, b, path, changes)?
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) => diff_tuple(a, b, path, changes)?,
+        (ReflectRef::Enum(a), ReflectRef::Enum(b)) => diff_enum(a, b, path, changes)?,
+        (ReflectRef::Array(a), ReflectRef::Array(b)) => diff_array(a, b, path, changes)?,
+        (ReflectRef::List(a), ReflectRef::List(b)) => diff_list(a, b, path, changes)?,
+        (ReflectRef::Map(a), ReflectRef::Map(b)) => diff_map(a, b, path, changes)?,
+        (ReflectRef::Set(a), ReflectRef::Set(b)) => diff_set(a, b, path, changes),
+        // opaque values can't be looked into so they're replaced as a whole
+        (ReflectRef::Opaque(_), _) | (_, ReflectRef::Opaque(_)) => {
+            if old.to_value() != new.to_value() {
+                changes.push(set(path, new));
+            }
+        }
+        // values of different kinds
+        _ => changes.push(set(path, new)),
+    }
+
+    Some(())
+}
+
+fn set(path: &KeyPath, new: &dyn Reflect) -> Change {
+    Change::Set {
+        path: path.clone(),
+        value: new.to_value(),
+    }
+}
+
+fn diff_struct(
+    old: &dyn Struct,
+    new: &dyn Struct,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    let same_fields = old.fields_len() == new.fields_len()
+        && new.fields().all(|(name, _)| old.field(name).is_some());
+    if !same_fields {
+        changes.push(set(path, new.as_reflect()));
+        return Some(());
+    }
+
+    for (name, value_new) in new.fields() {
+        let value_old = old.field(name)?;
+        diff(value_old, value_new, &path.clone().field(name), changes)?;
+    }
+
+    Some(())
+}
+
+fn diff_tuple_struct(
+    old: &dyn TupleStruct,
+    new: &dyn TupleStruct,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    if old.fields_len() != new.fields_len() {
+        changes.push(set(path, new.as_reflect()));
+        return Some(());
+    }
+
+    for (index, (value_old, value_new)) in old.fields().zip(new.fields()).enumerate() {
+        diff(value_old, value_new, &path.clone().field(index), changes)?;
+    }
+
+    Some(())
+}
+
+fn diff_tuple(
+    old: &dyn Tuple,
+    new: &dyn Tuple,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    if old.fields_len() != new.fields_len() {
+        changes.push(set(path, new.as_reflect()));
+        return Some(());
+    }
+
+    for (index, (value_old, value_new)) in old.fields().zip(new.fields()).enumerate() {
+        diff(value_old, value_new, &path.clone().field(index), changes)?;
+    }
+
+    Some(())
+}
+
+fn diff_enum(
+    old: &dyn Enum,
+    new: &dyn Enum,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    if old.variant_name() != new.variant_name() || old.variant_kind() != new.variant_kind() {
+        changes.push(Change::SwitchVariant {
+            path: path.clone(),
+            value: new.to_value(),
+        });
+        return Some(());
+    }
+
+    let variant_path = path.clone().variant(new.variant_name());
+
+    match new.variant_kind() {
+        VariantKind::Struct => {
+            let same_fields = old.fields_len() == new.fields_len()
+                && new.fields().all(|field| match field {
+                    VariantField::Struct(name, _) => old.field(name).is_some(),
+                    VariantField::Tuple(_) => false,
+                });
+            if !same_fields {
+                changes.push(set(path, new.as_reflect()));
+                return Some(());
+            }
+
+            for field in new.fields() {
+                match field {
+                    VariantField::Struct(name, value_new) => {
+                        let value_old = old.field(name)?;
+                        diff(
+                            value_old,
+                            value_new,
+                            &variant_path.clone().field(name),
+                            changes,
+                        )?;
+                    }
+                    VariantField::Tuple(_) => return None,
+                }
+            }
+        }
+        VariantKind::Tuple => {
+            if old.fields_len() != new.fields_len() {
+                changes.push(set(path, new.as_reflect()));
+                return Some(());
+            }
+
+            for index in 0..new.fields_len() {
+                let value_old = old.field_at(index)?;
+                let value_new = new.field_at(index)?;
+                diff(
+                    value_old,
+                    value_new,
+                    &variant_path.clone().field(index),
+                    changes,
+                )?;
+            }
+        }
+        VariantKind::Unit => {}
+    }
+
+    Some(())
+}
+
+fn diff_array(
+    old: &dyn Array,
+    new: &dyn Array,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    if old.len() != new.len() {
+        changes.push(set(path, new.as_reflect()));
+        return Some(());
+    }
+
+    for (index, (value_old, value_new)) in old.iter().zip(new.iter()).enumerate() {
+        diff(value_old, value_new, &path.clone().get(index), changes)?;
+    }
+
+    Some(())
+}
+
+fn diff_list(
+    old: &dyn List,
+    new: &dyn List,
+    path: &KeyPath,
+    changes: &mut Vec<Change>,
+) -> Option<()> {
+    for (index, (value_old, value_new)) in old.iter().zip(new.iter()).enumerate() {
+        diff(value_old, value_new, &path.clone().get(index), changes)?;
+    }
+
+    for index in old.len()..new.len() {
+        changes.push(Change::Insert {
+            path: path.clone().get(index),
+            value: new.get(index)?.to_value(),
+        });
+    }
+
+    for index in (new.len()..old.len()).rev() {
+        changes.push(Change::Remove {
+            path: path.clone().get(index),
+        });
+    }
+
+    Some(())
+}
+
+fn diff_map(old: &dyn Map, new: &dyn Map, path: &KeyPath, changes: &mut Vec<Change>) -> Option<()> {
+    for (key, value_old) in old.iter() {
+        let key_path = path.clone().get(key.to_value());
+        match new.get(key) {
+            Some(value_new) => diff(value_old, value_new, &key_path, changes)?,
+            None => changes.push(Change::Remove { path: key_path }),
+        }
+    }
+
+    for (key, value_new) in new.iter() {
+        if old.get(key).is_none() {
+            changes.push(Change::Insert {
+                path: path.clone().get(key.to_value()),
+                value: value_new.to_value(),
+            });
+        }
+    }
+
+    Some(())
+}
+
+// set elements cannot be changed in place so changed elements show up as a remove and an insert
+fn diff_set(old: &dyn Set, new: &dyn Set, path: &KeyPath, changes: &mut Vec<Change>) {
+    for value_old in old.iter() {
+        if !new.contains(value_old) {
+            changes.push(Change::Remove {
+                path: path.clone().get(value_old.to_value()),
+            });
+        }
+    }
+
+    for value_new in new.iter() {
+        if !old.contains(value_new) {
+            changes.push(Change::Insert {
+                path: path.clone().get(value_new.to_value()),
+                value: value_new.to_value(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec;
+
+    use super::*;
+    use crate::enum_::EnumValue;
+    use crate::key_path;
+    use crate::struct_::StructValue;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        n: i32,
+        name: String,
+        bar: Bar,
+        list: Vec<i32>,
+        map: BTreeMap<String, i32>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        A { a: bool },
+        B(i32, i32),
+        C,
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            n: 1,
+            name: "foo".into(),
+            bar: Bar::A { a: true },
+            list: vec![1, 2, 3],
+            map: BTreeMap::from([("a".into(), 1), ("b".into(), 2)]),
+        }
+    }
+
+    #[test]
+    fn equal_values() {
+        assert!(reflect_diff(&foo(), &foo()).unwrap().is_empty());
+        assert!(reflect_diff(&foo(), &foo().to_value()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scalar() {
+        assert_eq!(
+            reflect_diff(&1, &2).unwrap(),
+            [Change::Set {
+                path: KeyPath::default(),
+                value: 2.into(),
+            }]
+        );
+        assert_eq!(
+            reflect_diff(&1, &2_u8).unwrap(),
+            [Change::Set {
+                path: KeyPath::default(),
+                value: 2_u8.into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn struct_fields() {
+        let mut new = foo();
+        new.n = 2;
+        new.name = "bar".into();
+
+        assert_eq!(
+            reflect_diff(&foo(), &new).unwrap(),
+            [
+                Change::Set {
+                    path: key_path!(.n),
+                    value: 2.into(),
+                },
+                Change::Set {
+                    path: key_path!(.name),
+                    value: "bar".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_with_different_fields() {
+        let old = StructValue::new().with_field("a", 1);
+        let new = StructValue::new().with_field("b", 1);
+
+        assert_eq!(
+            reflect_diff(&old, &new).unwrap(),
+            [Change::Set {
+                path: KeyPath::default(),
+                value: new.to_value(),
+            }]
+        );
+    }
+
+    #[test]
+    fn enum_same_variant() {
+        let mut new = foo();
+        new.bar = Bar::A { a: false };
+
+        assert_eq!(
+            reflect_diff(&foo(), &new).unwrap(),
+            [Change::Set {
+                path: key_path!(.bar::A.a),
+                value: false.into(),
+            }]
+        );
+
+        let old = Bar::B(1, 2);
+        let new = Bar::B(1, 3);
+        assert_eq!(
+            reflect_diff(&old, &new).unwrap(),
+            [Change::Set {
+                path: key_path!(::B.1),
+                value: 3.into(),
+            }]
+        );
+
+        assert!(reflect_diff(&Bar::C, &Bar::C).unwrap().is_empty());
+    }
+
+    #[test]
+    fn enum_switch_variant() {
+        let mut new = foo();
+        new.bar = Bar::B(1, 2);
+
+        let changes = reflect_diff(&foo(), &new).unwrap();
+        assert_eq!(
+            changes,
+            [Change::SwitchVariant {
+                path: key_path!(.bar),
+                value: Bar::B(1, 2).to_value(),
+            }]
+        );
+
+        let Change::SwitchVariant { value, .. } = &changes[0] else {
+            unreachable!()
+        };
+        assert_eq!(Bar::from_reflect(value).unwrap(), Bar::B(1, 2));
+
+        assert_eq!(
+            reflect_diff(&Bar::C, &EnumValue::new_unit_variant("D")).unwrap(),
+            [Change::SwitchVariant {
+                path: KeyPath::default(),
+                value: EnumValue::new_unit_variant("D").to_value(),
+            }]
+        );
+    }
+
+    #[test]
+    fn list_insert() {
+        let mut new = foo();
+        new.list[0] = 10;
+        new.list.push(4);
+        new.list.push(5);
+
+        assert_eq!(
+            reflect_diff(&foo(), &new).unwrap(),
+            [
+                Change::Set {
+                    path: key_path!(.list[0_usize]),
+                    value: 10.into(),
+                },
+                Change::Insert {
+                    path: key_path!(.list[3_usize]),
+                    value: 4.into(),
+                },
+                Change::Insert {
+                    path: key_path!(.list[4_usize]),
+                    value: 5.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn list_remove() {
+        let mut new = foo();
+        new.list.truncate(1);
+
+        assert_eq!(
+            reflect_diff(&foo(), &new).unwrap(),
+            [
+                Change::Remove {
+                    path: key_path!(.list[2_usize]),
+                },
+                Change::Remove {
+                    path: key_path!(.list[1_usize]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn array_of_different_length() {
+        let old = [1, 2];
+        let new = [1, 2, 3];
+        assert_eq!(
+            reflect_diff(&old, &new).unwrap(),
+            [Change::Set {
+                path: KeyPath::default(),
+                value: new.to_value(),
+            }]
+        );
+
+        assert_eq!(
+            reflect_diff(&[1, 2], &[1, 3]).unwrap(),
+            [Change::Set {
+                path: key_path!([1_usize]),
+                value: 3.into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn map() {
+        let mut new = foo();
+        new.map.remove("a");
+        new.map.insert("b".into(), 3);
+        new.map.insert("c".into(), 4);
+
+        assert_eq!(
+            reflect_diff(&foo(), &new).unwrap(),
+            [
+                Change::Remove {
+                    path: key_path!(.map["a"]),
+                },
+                Change::Set {
+                    path: key_path!(.map["b"]),
+                    value: 3.into(),
+                },
+                Change::Insert {
+                    path: key_path!(.map["c"]),
+                    value: 4.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn different_kinds() {
+        assert_eq!(
+            reflect_diff(&1, &vec![1]).unwrap(),
+            [Change::Set {
+                path: KeyPath::default(),
+                value: vec![1].to_value(),
+            }]
+        );
+    }
+
+    fn apply(old: &Foo, new: &Foo) -> Foo {
+        let change_set = ChangeSet::from(reflect_diff(old, new).unwrap());
+        let mut value = old.clone();
+        for result in change_set.apply(&mut value) {
+            result.unwrap();
+        }
+        value
+    }
+
+    #[test]
+    fn apply_diff() {
+        let mut new = foo();
+        new.n = 2;
+        new.bar = Bar::B(1, 2);
+        new.list = vec![3];
+        new.map.remove("a");
+        new.map.insert("c".into(), 3);
+        assert_eq!(apply(&foo(), &new), new);
+
+        let old = new;
+        let mut new = old.clone();
+        new.bar = Bar::A { a: false };
+        new.list = vec![4, 5, 6, 7];
+        assert_eq!(apply(&old, &new), new);
+    }
+
+    #[test]
+    fn apply_insert_in_the_middle() {
+        let mut value = vec![1, 3];
+        Change::Insert {
+            path: key_path!([1]),
+            value: 2.into(),
+        }
+        .apply(&mut value)
+        .unwrap();
+        assert_eq!(value, [1, 2, 3]);
+    }
+
+    #[test]
+    fn apply_errors() {
+        let mut value = foo();
+
+        let change_set = ChangeSet::new()
+            .with_change(Change::Set {
+                path: key_path!(.missing),
+                value: 1.into(),
+            })
+            .with_change(Change::Set {
+                path: key_path!(.n),
+                value: "not a number".into(),
+            })
+            .with_change(Change::Insert {
+                path: key_path!(.list),
+                value: 1.into(),
+            })
+            .with_change(Change::Insert {
+                path: key_path!(.n[0]),
+                value: 1.into(),
+            })
+            .with_change(Change::Insert {
+                path: key_path!(.list[10]),
+                value: 1.into(),
+            })
+            .with_change(Change::Insert {
+                path: key_path!(.list[0]),
+                value: "not a number".into(),
+            })
+            .with_change(Change::Remove {
+                path: key_path!(.map["missing"]),
+            })
+            .with_change(Change::SwitchVariant {
+                path: key_path!(.n),
+                value: Bar::C.to_value(),
+            })
+            .with_change(Change::SwitchVariant {
+                path: key_path!(.bar),
+                value: EnumValue::new_unit_variant("D").to_value(),
+            })
+            .with_change(Change::Set {
+                path: key_path!(.n),
+                value: 2.into(),
+            });
+
+        assert_eq!(
+            change_set.apply(&mut value),
+            [
+                Err(ApplyError::PathNotFound),
+                Err(ApplyError::Patch(
+                    PatchError::type_mismatch(&1, &String::new()).prefixed(&key_path!(.n))
+                )),
+                Err(ApplyError::InvalidPath),
+                Err(ApplyError::NotListOrMap),
+                Err(ApplyError::IndexOutOfBounds),
+                Err(ApplyError::Mismatch),
+                Err(ApplyError::PathNotFound),
+                Err(ApplyError::NotAnEnum),
+                Err(ApplyError::Patch(
+                    PatchError::type_mismatch(&Bar::C, &EnumValue::new_unit_variant("D"))
+                        .prefixed(&key_path!(.bar))
+                )),
+                Ok(()),
+            ]
+        );
+
+        let mut expected = foo();
+        expected.n = 2;
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn opaque() {
+        use core::any::Any;
+        use core::fmt;
+
+        use crate::type_info::graph::NodeId;
+        use crate::type_info::graph::OpaqueNode;
+        use crate::type_info::graph::TypeGraph;
+        use crate::DescribeType;
+        use crate::ReflectOwned;
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Id(i32);
+
+        impl DescribeType for Id {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                graph.get_or_build_node_with::<Self, _>(|graph| {
+                    OpaqueNode::new::<Self>(Default::default(), graph)
+                })
+            }
+        }
+
+        impl Reflect for Id {
+            trivial_reflect_methods!();
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Opaque(self)
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Opaque(self)
+            }
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                if let Some(id) = Self::from_reflect(value) {
+                    *self = id;
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                self.0.to_value()
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                Box::new(*self)
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{self:?}")
+            }
+        }
+
+        impl FromReflect for Id {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                match reflect.downcast_ref() {
+                    Some(id) => Some(*id),
+                    None => i32::from_reflect(reflect).map(Id),
+                }
+            }
+        }
+
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        #[reflect(crate_name(crate))]
+        struct Player {
+            id: Id,
+            score: i32,
+        }
+
+        let old = Player {
+            id: Id(1),
+            score: 1,
+        };
+        assert!(reflect_diff(&old, &old.clone()).unwrap().is_empty());
+
+        let new = Player {
+            id: Id(2),
+            score: 2,
+        };
+        let changes = reflect_diff(&old, &new).unwrap();
+        assert_eq!(
+            changes,
+            [
+                Change::Set {
+                    path: key_path!(.id),
+                    value: 2.into(),
+                },
+                Change::Set {
+                    path: key_path!(.score),
+                    value: 2.into(),
+                },
+            ]
+        );
+
+        let mut value = old;
+        for change in &changes {
+            change.apply(&mut value).unwrap();
+        }
+        assert_eq!(value, new);
+    }
+
+    #[cfg(feature = "speedy")]
+    #[test]
+    fn speedy_round_trip() {
+        use speedy::Readable;
+        use speedy::Writable;
+
+        let mut new = foo();
+        new.bar = Bar::C;
+        new.list.pop();
+        let change_set = ChangeSet::from(reflect_diff(&foo(), &new).unwrap());
+
+        let bytes = change_set.write_to_vec().unwrap();
+        assert_eq!(ChangeSet::read_from_buffer(&bytes).unwrap(), change_set);
+    }
+}
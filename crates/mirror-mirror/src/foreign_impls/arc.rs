@@ -0,0 +1,124 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt;
+
+use crate::reflect_debug;
+use crate::reflect_eq;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectGuard;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::TypeDescriptor;
+use crate::Value;
+
+// `Arc<T>` is reflected transparently, like `Box<T>`. Mutable access goes through
+// `Arc::make_mut` so the value is cloned if it's shared with other `Arc`s, which therefore don't
+// observe the changes. The clone happens when the mutable reference is created, whether or not
+// anything is written through it. Patching first checks if there's anything to change.
+//
+// `Rc<T>` isn't `Send` so it cannot implement `Reflect` and is out of scope. See the "Shared
+// ownership" section of the crate docs.
+
+impl<T> DescribeType for Arc<T>
+where
+    T: DescribeType,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        T::build(graph)
+    }
+}
+
+impl<T> Reflect for Arc<T>
+where
+    T: Reflect + DescribeType + Clone + Sync,
+{
+    fn type_descriptor(&self) -> Cow<'static, TypeDescriptor> {
+        <T as DescribeType>::type_descriptor()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        <T as Reflect>::as_any(self)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        <T as Reflect>::as_any_mut(Arc::make_mut(self))
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        <T as Reflect>::as_reflect(self)
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        <T as Reflect>::as_reflect_mut(Arc::make_mut(self))
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        let value = Arc::try_unwrap(*self).unwrap_or_else(|arc| T::clone(&arc));
+        <T as Reflect>::reflect_owned(Box::new(value))
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        <T as Reflect>::reflect_ref(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        <T as Reflect>::reflect_mut(Arc::make_mut(self))
+    }
+
+    fn reflect_guard(&self) -> ReflectGuard<'_> {
+        <T as Reflect>::reflect_guard(self)
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        if reflect_eq(self, value) != Some(true) {
+            <T as Reflect>::patch(Arc::make_mut(self), value)
+        }
+    }
+
+    fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+        if reflect_eq(self, value) == Some(true) {
+            return Ok(());
+        }
+        <T as Reflect>::try_patch(Arc::make_mut(self), value)
+    }
+
+    fn to_value(&self) -> Value {
+        <T as Reflect>::to_value(self)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        <T as Reflect>::clone_reflect(self)
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        reflect_debug(self, f)
+    }
+}
+
+impl<T> FromReflect for Arc<T>
+where
+    T: FromReflect + DescribeType + Clone + Sync,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        Some(Arc::new(T::from_reflect(reflect)?))
+    }
+}
+
+impl<T> From<Arc<T>> for Value
+where
+    T: Into<Value> + Clone,
+{
+    fn from(arc: Arc<T>) -> Self {
+        Arc::try_unwrap(arc)
+            .unwrap_or_else(|arc| T::clone(&arc))
+            .into()
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::any::Any;
+
+use crate::array::Array;
+use crate::iter::ValueIterMut;
+use crate::type_info::graph::ListNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::List;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+// `BinaryHeap`s are reflected as lists when read. Elements are accessed in ascending order, like
+// `BinaryHeap::into_sorted_vec`, rather than the internal order of the heap, so heaps with equal
+// elements produce equal `Value`s. That means indexing and iterating sorts the elements first.
+//
+// Elements cannot be mutated in place since that could break the heap invariant, and the heap
+// decides where elements end up, so `reflect_mut` returns `ReflectMut::Opaque` and heaps are
+// changed by patching the whole heap. Mutable key paths stop at the heap with
+// `KeyPathErrorKind::Immutable` and `try_visit_mut` visits a copy that's patched back.
+//
+// The `List` impl can still be used directly. `get_mut` and `iter_mut` don't return anything and
+// `try_insert` isn't supported, while pushing, popping, and removing elements keeps the heap
+// valid.
+
+impl<T> List for BinaryHeap<T>
+where
+    T: FromReflect + DescribeType + Ord,
+{
+    fn push(&mut self, value: &dyn Reflect) {
+        if let Some(value) = T::from_reflect(value) {
+            BinaryHeap::push(self, value);
+        }
+    }
+
+    /// Pops the greatest element, which is the last one.
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        let value = BinaryHeap::pop(self)?;
+        Some(Box::new(value))
+    }
+
+    fn try_remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut elements = core::mem::take(self).into_sorted_vec();
+        let value = elements.remove(index);
+        *self = BinaryHeap::from(elements);
+        Some(Box::new(value))
+    }
+}
+
+impl<T> Array for BinaryHeap<T>
+where
+    T: FromReflect + DescribeType + Ord,
+{
+    fn get(&self, index: usize) -> Option<&dyn Reflect> {
+        sorted(self).get(index).map(|value| value.as_reflect())
+    }
+
+    fn get_mut(&mut self, _index: usize) -> Option<&mut dyn Reflect> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinaryHeap::is_empty(self)
+    }
+
+    fn iter(&self) -> crate::array::Iter<'_> {
+        crate::array::Iter::boxed(sorted(self).into_iter().map(|value| value.as_reflect()))
+    }
+
+    fn iter_mut(&mut self) -> ValueIterMut<'_> {
+        Box::new(core::iter::empty())
+    }
+}
+
+impl<T> DescribeType for BinaryHeap<T>
+where
+    T: DescribeType,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(|graph| ListNode::new::<Self, T>(graph))
+    }
+}
+
+impl<T> Reflect for BinaryHeap<T>
+where
+    T: FromReflect + DescribeType + Ord,
+{
+    trivial_reflect_methods!();
+
+    // elements cannot be patched in place so the heap is replaced if every element of `value` can
+    // be converted
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(new_value) = Self::from_reflect(value) {
+            *self = new_value;
+        }
+    }
+
+    fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+        if let Some(new_value) = Self::from_reflect(value) {
+            *self = new_value;
+            Ok(())
+        } else {
+            Err(PatchError::type_mismatch(self, value))
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let data = sorted(self).into_iter().map(Reflect::to_value).collect();
+        Value::List(data)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        let value = self.to_value();
+        Box::new(Self::from_reflect(&value).unwrap())
+    }
+
+    fn debug(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(Array::iter(self)).finish()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::List(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::List(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Opaque(self)
+    }
+}
+
+impl<T> FromReflect for BinaryHeap<T>
+where
+    T: FromReflect + DescribeType + Ord,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let list = reflect.reflect_ref().as_list()?;
+        let mut out = Vec::with_capacity(list.len());
+        for value in list.iter() {
+            out.push(T::from_reflect(value)?);
+        }
+        Some(BinaryHeap::from(out))
+    }
+}
+
+impl<T> From<BinaryHeap<T>> for Value
+where
+    T: Reflect + Ord,
+{
+    fn from(heap: BinaryHeap<T>) -> Self {
+        let list = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|value| value.to_value())
+            .collect();
+        Value::List(list)
+    }
+}
+
+fn sorted<T>(heap: &BinaryHeap<T>) -> Vec<&T>
+where
+    T: Ord,
+{
+    let mut elements = heap.iter().collect::<Vec<_>>();
+    elements.sort();
+    elements
+}
@@ -8,7 +8,9 @@ use crate::type_info::graph::NodeId;
 use crate::type_info::graph::TypeGraph;
 use crate::DescribeType;
 use crate::FromReflect;
+use crate::PatchError;
 use crate::Reflect;
+use crate::ReflectGuard;
 use crate::ReflectMut;
 use crate::ReflectOwned;
 use crate::ReflectRef;
@@ -60,10 +62,18 @@ where
         <T as Reflect>::reflect_mut(self)
     }
 
+    fn reflect_guard(&self) -> ReflectGuard<'_> {
+        <T as Reflect>::reflect_guard(self)
+    }
+
     fn patch(&mut self, value: &dyn Reflect) {
         <T as Reflect>::patch(self, value)
     }
 
+    fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+        <T as Reflect>::try_patch(self, value)
+    }
+
     fn to_value(&self) -> Value {
         <T as Reflect>::to_value(self)
     }
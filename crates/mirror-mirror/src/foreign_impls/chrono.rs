@@ -0,0 +1,73 @@
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+
+use super::via_scalar::impl_reflect_via_scalar;
+use super::via_scalar::IntoOption;
+
+// `NaiveDate`s are reflected as the number of days since January 1 of year 1, where that day is 1.
+impl_reflect_via_scalar! {
+    NaiveDate,
+    i32,
+    |date: &NaiveDate| date.num_days_from_ce(),
+    NaiveDate::from_num_days_from_ce_opt,
+}
+
+// Date times are reflected as the number of nanoseconds since the Unix epoch. `NaiveDateTime`s are
+// assumed to be in UTC. Leap seconds aren't kept.
+impl_reflect_via_scalar! {
+    NaiveDateTime,
+    i128,
+    |date_time: &NaiveDateTime| utc_to_nanos(&date_time.and_utc()),
+    |nanos| utc_from_nanos(nanos).map(|date_time| date_time.naive_utc()),
+}
+
+impl_reflect_via_scalar! {
+    DateTime<Utc>,
+    i128,
+    utc_to_nanos,
+    utc_from_nanos,
+}
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+fn utc_to_nanos(date_time: &DateTime<Utc>) -> i128 {
+    let nanos = date_time.timestamp_subsec_nanos() as i128 % NANOS_PER_SEC;
+    date_time.timestamp() as i128 * NANOS_PER_SEC + nanos
+}
+
+fn utc_from_nanos(nanos: i128) -> Option<DateTime<Utc>> {
+    let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+    let nanos = nanos.rem_euclid(NANOS_PER_SEC) as u32;
+    DateTime::from_timestamp(secs, nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromReflect;
+    use crate::Reflect;
+
+    #[test]
+    fn works() {
+        let date = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
+        assert_eq!(date.to_value(), 1.to_value());
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(NaiveDate::from_reflect(&date.to_value()).unwrap(), date);
+
+        let date_time = DateTime::from_timestamp(-1, 500_000_000).unwrap();
+        assert_eq!(date_time.to_value(), (-500_000_000_i128).to_value());
+        assert_eq!(
+            DateTime::<Utc>::from_reflect(&date_time.to_value()).unwrap(),
+            date_time
+        );
+
+        let naive = date.and_hms_nano_opt(12, 30, 0, 1).unwrap();
+        assert_eq!(
+            NaiveDateTime::from_reflect(&naive.to_value()).unwrap(),
+            naive
+        );
+    }
+}
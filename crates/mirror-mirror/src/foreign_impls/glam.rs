@@ -1,34 +1,572 @@
-use glam::{Mat3, Vec2, Vec3};
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt;
+
+use glam::Affine2;
+use glam::Affine3A;
+use glam::BVec2;
+use glam::BVec3;
+use glam::BVec4;
+use glam::DAffine2;
+use glam::DAffine3;
+use glam::DMat2;
+use glam::DMat3;
+use glam::DMat4;
+use glam::DQuat;
+use glam::DVec2;
+use glam::DVec3;
+use glam::DVec4;
+use glam::IVec2;
+use glam::IVec3;
+use glam::IVec4;
+use glam::Mat2;
+use glam::Mat3;
+use glam::Mat3A;
+use glam::Mat4;
+use glam::Quat;
+use glam::UVec2;
+use glam::UVec3;
+use glam::UVec4;
+use glam::Vec2;
+use glam::Vec3;
+use glam::Vec3A;
+use glam::Vec4;
 use mirror_mirror_macros::__private_derive_reflect_foreign;
 
+use crate::iter::PairIterMut;
+use crate::struct_;
+use crate::struct_::Struct;
+use crate::struct_::StructValue;
+use crate::type_info::graph::NamedFieldNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::StructNode;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+// Types that are plain structs regardless of architecture and cargo features.
+macro_rules! derive_reflect_for_structs {
+    ($($ty:ident { $($field:ident),* }: $field_ty:ty;)*) => {
+        $(
+            __private_derive_reflect_foreign! {
+                #[reflect(crate_name(crate))]
+                pub struct $ty {
+                    $(pub $field: $field_ty,)*
+                }
+            }
+        )*
+    };
+}
+
+derive_reflect_for_structs! {
+    Vec2 { x, y }: f32;
+    Vec3 { x, y, z }: f32;
+    DVec2 { x, y }: f64;
+    DVec3 { x, y, z }: f64;
+    DVec4 { x, y, z, w }: f64;
+    IVec2 { x, y }: i32;
+    IVec3 { x, y, z }: i32;
+    IVec4 { x, y, z, w }: i32;
+    UVec2 { x, y }: u32;
+    UVec3 { x, y, z }: u32;
+    UVec4 { x, y, z, w }: u32;
+    BVec2 { x, y }: bool;
+    BVec3 { x, y, z }: bool;
+    BVec4 { x, y, z, w }: bool;
+    DQuat { x, y, z, w }: f64;
+    Mat3 { x_axis, y_axis, z_axis }: Vec3;
+    Mat3A { x_axis, y_axis, z_axis }: Vec3A;
+    Mat4 { x_axis, y_axis, z_axis, w_axis }: Vec4;
+    DMat2 { x_axis, y_axis }: DVec2;
+    DMat3 { x_axis, y_axis, z_axis }: DVec3;
+    DMat4 { x_axis, y_axis, z_axis, w_axis }: DVec4;
+}
+
 __private_derive_reflect_foreign! {
     #[reflect(crate_name(crate))]
-    pub struct Vec2 {
-        pub x: f32,
-        pub y: f32,
+    pub struct Affine2 {
+        pub matrix2: Mat2,
+        pub translation: Vec2,
     }
 }
 
 __private_derive_reflect_foreign! {
     #[reflect(crate_name(crate))]
-    pub struct Vec3 {
-        pub x: f32,
-        pub y: f32,
-        pub z: f32,
+    pub struct Affine3A {
+        pub matrix3: Mat3A,
+        pub translation: Vec3A,
     }
 }
 
-// `Vec4`, `Quat`, and `Mat2` are left out because glam uses bad hacks which changes the struct
-// definitions for different architectures (simd vs no simd) and cargo features. So we'd have
-// to use the same hacks in mirror-mirror which I'd like to avoid.
-
-// `Mat4` is left out because it contains `Vec4` which we don't support.
+__private_derive_reflect_foreign! {
+    #[reflect(crate_name(crate))]
+    pub struct DAffine2 {
+        pub matrix2: DMat2,
+        pub translation: DVec2,
+    }
+}
 
 __private_derive_reflect_foreign! {
     #[reflect(crate_name(crate))]
-    pub struct Mat3 {
-        pub x_axis: Vec3,
-        pub y_axis: Vec3,
-        pub z_axis: Vec3,
+    pub struct DAffine3 {
+        pub matrix3: DMat3,
+        pub translation: DVec3,
+    }
+}
+
+// `Vec3A` and `Vec4` are stored in a SIMD register on some targets and plain structs on others, so
+// their fields are accessed through `AsRef` and `AsMut` for arrays which glam provides either way.
+macro_rules! impl_reflect_for_simd_vector {
+    ($($ty:ident { $($field:ident),* }: [$scalar:ty; $len:literal];)*) => {
+        $(
+            impl DescribeType for $ty {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    graph.get_or_build_node_with::<Self, _>(|graph| {
+                        let fields = &[$(
+                            NamedFieldNode::new::<$scalar>(
+                                stringify!($field),
+                                Default::default(),
+                                &[],
+                                graph,
+                            )
+                        ),*];
+                        StructNode::new::<Self>(fields, Default::default(), &[])
+                    })
+                }
+            }
+
+            impl Reflect for $ty {
+                trivial_reflect_methods!();
+
+                fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                    ReflectOwned::Struct(self)
+                }
+
+                fn reflect_ref(&self) -> ReflectRef<'_> {
+                    ReflectRef::Struct(self)
+                }
+
+                fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                    ReflectMut::Struct(self)
+                }
+
+                fn patch(&mut self, value: &dyn Reflect) {
+                    if let Some(value) = value.reflect_ref().as_struct() {
+                        for (name, field) in self.fields_mut() {
+                            if let Some(new_value) = value.field(name) {
+                                field.patch(new_value);
+                            }
+                        }
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    let [$($field),*] = *<Self as AsRef<[$scalar; $len]>>::as_ref(self);
+                    StructValue::with_capacity($len)
+                        $(.with_field(stringify!($field), $field))*
+                        .into()
+                }
+
+                fn clone_reflect(&self) -> Box<dyn Reflect> {
+                    Box::new(*self)
+                }
+
+                fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    if f.alternate() {
+                        write!(f, "{:#?}", self)
+                    } else {
+                        write!(f, "{:?}", self)
+                    }
+                }
+            }
+
+            impl FromReflect for $ty {
+                fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                    if let Some(value) = reflect.downcast_ref::<Self>() {
+                        return Some(*value);
+                    }
+                    let struct_ = reflect.reflect_ref().as_struct()?;
+                    Some(Self::new($(
+                        <$scalar>::from_reflect(struct_.field(stringify!($field))?)?
+                    ),*))
+                }
+            }
+
+            impl Struct for $ty {
+                fn field(&self, name: &str) -> Option<&dyn Reflect> {
+                    let [$($field),*] = <Self as AsRef<[$scalar; $len]>>::as_ref(self);
+                    match name {
+                        $(stringify!($field) => Some($field),)*
+                        _ => None,
+                    }
+                }
+
+                fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+                    let [$($field),*] = <Self as AsMut<[$scalar; $len]>>::as_mut(self);
+                    match name {
+                        $(stringify!($field) => Some($field),)*
+                        _ => None,
+                    }
+                }
+
+                fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+                    let array = <Self as AsRef<[$scalar; $len]>>::as_ref(self);
+                    Some(array.get(index)?)
+                }
+
+                fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+                    let array = <Self as AsMut<[$scalar; $len]>>::as_mut(self);
+                    Some(array.get_mut(index)?)
+                }
+
+                fn name_at(&self, index: usize) -> Option<&str> {
+                    [$(stringify!($field)),*].get(index).copied()
+                }
+
+                fn fields(&self) -> struct_::Iter<'_> {
+                    struct_::Iter::new(self)
+                }
+
+                fn fields_mut(&mut self) -> PairIterMut<'_> {
+                    let [$($field),*] = <Self as AsMut<[$scalar; $len]>>::as_mut(self);
+                    let iter = [$((stringify!($field), $field as &mut dyn Reflect)),*];
+                    Box::new(iter.into_iter())
+                }
+
+                fn fields_len(&self) -> usize {
+                    $len
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    value.to_value()
+                }
+            }
+        )*
+    };
+}
+
+impl_reflect_for_simd_vector! {
+    Vec3A { x, y, z }: [f32; 3];
+    Vec4 { x, y, z, w }: [f32; 4];
+}
+
+// `Quat` and `Mat2` are stored in a SIMD register on some targets, where their fields are reached
+// through `Deref` and `DerefMut`. Each field can be borrowed on its own but not several at once,
+// so `Struct::fields_mut` doesn't return any fields. Everything in this crate that mutates fields,
+// such as `try_patch`, `GetPath::at_mut`, `try_visit_mut`, and `GetPath::query_mut`, goes through
+// `Struct::field_mut` one field at a time instead.
+macro_rules! impl_reflect_for_simd_struct {
+    ($($ty:ident { $($field:ident),* }: $field_ty:ty => $new:expr;)*) => {
+        $(
+            impl DescribeType for $ty {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    graph.get_or_build_node_with::<Self, _>(|graph| {
+                        let fields = &[$(
+                            NamedFieldNode::new::<$field_ty>(
+                                stringify!($field),
+                                Default::default(),
+                                &[],
+                                graph,
+                            )
+                        ),*];
+                        StructNode::new::<Self>(fields, Default::default(), &[])
+                    })
+                }
+            }
+
+            impl Reflect for $ty {
+                trivial_reflect_methods!();
+
+                fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                    ReflectOwned::Struct(self)
+                }
+
+                fn reflect_ref(&self) -> ReflectRef<'_> {
+                    ReflectRef::Struct(self)
+                }
+
+                fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                    ReflectMut::Struct(self)
+                }
+
+                fn patch(&mut self, value: &dyn Reflect) {
+                    if let Some(value) = value.reflect_ref().as_struct() {
+                        $(
+                            if let Some(new_value) = value.field(stringify!($field)) {
+                                self.$field.patch(new_value);
+                            }
+                        )*
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    StructValue::new()
+                        $(.with_field(stringify!($field), self.$field))*
+                        .into()
+                }
+
+                fn clone_reflect(&self) -> Box<dyn Reflect> {
+                    Box::new(*self)
+                }
+
+                fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    if f.alternate() {
+                        write!(f, "{:#?}", self)
+                    } else {
+                        write!(f, "{:?}", self)
+                    }
+                }
+            }
+
+            impl FromReflect for $ty {
+                fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                    if let Some(value) = reflect.downcast_ref::<Self>() {
+                        return Some(*value);
+                    }
+                    let struct_ = reflect.reflect_ref().as_struct()?;
+                    Some($new($(
+                        <$field_ty>::from_reflect(struct_.field(stringify!($field))?)?
+                    ),*))
+                }
+            }
+
+            impl Struct for $ty {
+                fn field(&self, name: &str) -> Option<&dyn Reflect> {
+                    match name {
+                        $(stringify!($field) => Some(&self.$field),)*
+                        _ => None,
+                    }
+                }
+
+                fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+                    match name {
+                        $(stringify!($field) => Some(&mut self.$field),)*
+                        _ => None,
+                    }
+                }
+
+                fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+                    self.field(self.name_at(index)?)
+                }
+
+                fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+                    let name = [$(stringify!($field)),*].get(index).copied()?;
+                    self.field_mut(name)
+                }
+
+                fn name_at(&self, index: usize) -> Option<&str> {
+                    [$(stringify!($field)),*].get(index).copied()
+                }
+
+                fn fields(&self) -> struct_::Iter<'_> {
+                    struct_::Iter::new(self)
+                }
+
+                fn fields_mut(&mut self) -> PairIterMut<'_> {
+                    Box::new(core::iter::empty())
+                }
+
+                fn fields_len(&self) -> usize {
+                    [$(stringify!($field)),*].len()
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    value.to_value()
+                }
+            }
+        )*
+    };
+}
+
+impl_reflect_for_simd_struct! {
+    Quat { x, y, z, w }: f32 => Quat::from_xyzw;
+    Mat2 { x_axis, y_axis }: Vec2 => Mat2::from_cols;
+}
+
+// `BVec3A` and `BVec4A` are left out since they don't exist when glam's `scalar-math` feature is
+// enabled, so we can't name them without knowing which features glam was built with.
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::key_path;
+    use crate::key_path::GetPath;
+    use crate::key_path::GetTypePath;
+    use crate::key_path::KeyPath;
+    use crate::key_path::KeyPathErrorKind;
+    use crate::key_path::Query;
+    use crate::reflect_diff;
+    use crate::reflect_eq;
+    use crate::try_visit::try_visit_mut;
+    use crate::try_visit::TryVisitMut;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Transform {
+        translation: Vec3A,
+        rotation: Quat,
+        scale: Vec3,
+    }
+
+    fn transform() -> Transform {
+        Transform {
+            translation: Vec3A::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_xyzw(0.0, 0.0, 0.0, 1.0),
+            scale: Vec3::ONE,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        fn round_trip<T>(value: T)
+        where
+            T: Reflect + FromReflect + PartialEq + fmt::Debug,
+        {
+            assert_eq!(T::from_reflect(&value.to_value()).unwrap(), value);
+            assert_eq!(T::from_reflect(&value).unwrap(), value);
+        }
+
+        round_trip(Vec3A::new(1.0, 2.0, 3.0));
+        round_trip(Vec4::new(1.0, 2.0, 3.0, 4.0));
+        round_trip(DVec3::new(1.0, 2.0, 3.0));
+        round_trip(IVec2::new(-1, 2));
+        round_trip(UVec3::new(1, 2, 3));
+        round_trip(BVec3::new(true, false, true));
+        round_trip(Quat::from_rotation_y(1.0));
+        round_trip(Mat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
+        round_trip(Mat4::from_cols_array(&[
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        ]));
+        round_trip(Affine3A::from_translation(Vec3::new(1.0, 2.0, 3.0)));
+        round_trip(DAffine2::from_angle(1.0));
+        round_trip(transform());
+    }
+
+    #[test]
+    fn struct_shaped() {
+        let value = Vec4::new(1.0, 2.0, 3.0, 4.0).to_value();
+        assert_eq!(value.get_at::<f32>(&key_path!(.w)).unwrap(), &4.0);
+
+        let value = Quat::from_xyzw(1.0, 2.0, 3.0, 4.0).to_value();
+        assert_eq!(value.get_at::<f32>(&key_path!(.z)).unwrap(), &3.0);
+
+        let ty = <Affine3A as DescribeType>::type_descriptor();
+        assert_eq!(
+            ty.type_at(&key_path!(.matrix3.x_axis.y))
+                .unwrap()
+                .type_name(),
+            "f32"
+        );
+
+        let ty = <Transform as DescribeType>::type_descriptor();
+        assert_eq!(
+            ty.type_at(&key_path!(.rotation.w)).unwrap().type_name(),
+            "f32"
+        );
+    }
+
+    #[test]
+    fn mutable_fields() {
+        let mut mat = Mat4::IDENTITY;
+        *mat.get_at_mut::<f32>(&key_path!(.w_axis.x)).unwrap() = 5.0;
+        assert_eq!(mat.w_axis.x, 5.0);
+
+        let mut affine = Affine3A::IDENTITY;
+        *affine
+            .get_at_mut::<f32>(&key_path!(.translation.z))
+            .unwrap() = 2.0;
+        assert_eq!(affine.translation.z, 2.0);
+
+        let mut vec = Vec3A::ZERO;
+        let fields = vec.fields_mut().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(fields, ["x", "y", "z"]);
+        vec.patch(&StructValue::new().with_field("y", 1.0_f32));
+        assert_eq!(vec, Vec3A::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn quat_and_mat2_fields() {
+        let mut transform = transform();
+
+        assert_eq!(
+            transform.get_at::<f32>(&key_path!(.rotation.w)).unwrap(),
+            &1.0
+        );
+        let rotation = transform.rotation.reflect_ref().as_struct().unwrap();
+        let fields = rotation.fields().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(fields, ["x", "y", "z", "w"]);
+
+        *transform
+            .get_at_mut::<f32>(&key_path!(.rotation.x))
+            .unwrap() = 1.0;
+        assert_eq!(transform.rotation, Quat::from_xyzw(1.0, 0.0, 0.0, 1.0));
+
+        let err = transform.try_at_mut(&key_path!(.rotation.v)).unwrap_err();
+        assert_eq!(err.kind(), &KeyPathErrorKind::NoSuchField);
+        assert_eq!(err.resolved(), &key_path!(.rotation));
+
+        let query = ".rotation.*".parse::<Query>().unwrap();
+        transform.query_mut(&query, |_, value| {
+            *value.downcast_mut::<f32>().unwrap() += 1.0;
+        });
+        assert_eq!(transform.rotation, Quat::from_xyzw(2.0, 1.0, 1.0, 2.0));
+
+        assert!(transform
+            .rotation
+            .try_patch(&StructValue::new().with_field("x", "one"))
+            .is_err());
+        assert_eq!(transform.rotation, Quat::from_xyzw(2.0, 1.0, 1.0, 2.0));
+
+        let mut mat = Mat2::IDENTITY;
+        *mat.get_at_mut::<f32>(&key_path!(.y_axis.x)).unwrap() = 3.0;
+        assert_eq!(mat, Mat2::from_cols_array(&[1.0, 0.0, 3.0, 1.0]));
+        mat.patch(&StructValue::new().with_field("x_axis", Vec2::new(2.0, 2.0)));
+        assert_eq!(mat, Mat2::from_cols_array(&[2.0, 2.0, 3.0, 1.0]));
+    }
+
+    #[test]
+    fn eq_diff_and_visit() {
+        struct Double;
+
+        impl TryVisitMut for Double {
+            type Error = Infallible;
+
+            fn try_visit_f32(
+                &mut self,
+                value: &mut f32,
+                _key_path: &KeyPath,
+            ) -> Result<(), Self::Error> {
+                *value *= 2.0;
+                Ok(())
+            }
+        }
+
+        let a = transform();
+        let mut b = transform();
+        assert!(reflect_eq(&a, &b).unwrap());
+
+        b.rotation.w = 0.5;
+        assert!(!reflect_eq(&a, &b).unwrap());
+        let changes = reflect_diff(&a, &b).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path(), &key_path!(.rotation.w));
+
+        let ty = <Transform as DescribeType>::type_descriptor();
+        try_visit_mut(&mut Double, &mut b, ty.get_type()).unwrap();
+        assert_eq!(b.translation, Vec3A::new(2.0, 4.0, 6.0));
+        assert_eq!(b.rotation, Quat::from_xyzw(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(b.scale, Vec3::splat(2.0));
     }
 }
@@ -0,0 +1,139 @@
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::hash::Hash;
+use std::collections::HashSet;
+
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::SetNode;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Set;
+use crate::Value;
+
+impl<T, S> Set for HashSet<T, S>
+where
+    T: FromReflect + DescribeType + Hash + Eq,
+    S: BuildHasher + Default + Send + 'static,
+{
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect> {
+        let value = T::from_reflect(value)?;
+        let value = HashSet::get(self, &value)?;
+        Some(value.as_reflect())
+    }
+
+    fn contains(&self, value: &dyn Reflect) -> bool {
+        Set::get(self, value).is_some()
+    }
+
+    fn insert(&mut self, value: &dyn Reflect) -> bool {
+        match T::from_reflect(value) {
+            Some(value) => HashSet::insert(self, value),
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let value = T::from_reflect(value)?;
+        let previous = HashSet::take(self, &value)?;
+        Some(Box::new(previous))
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+
+    fn iter(&self) -> crate::set::Iter<'_> {
+        let iter = HashSet::iter(self).map(|value| value.as_reflect());
+        Box::new(iter)
+    }
+}
+
+impl<T, S> DescribeType for HashSet<T, S>
+where
+    T: DescribeType,
+    S: 'static,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(|graph| SetNode::new::<Self, T>(graph))
+    }
+}
+
+impl<T, S> Reflect for HashSet<T, S>
+where
+    T: FromReflect + DescribeType + Hash + Eq,
+    S: BuildHasher + Default + Send + 'static,
+{
+    trivial_reflect_methods!();
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Set(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Set(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Set(self)
+    }
+
+    // elements cannot be patched in place so the set is replaced by the elements of `value` that
+    // can be converted
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(set) = value.reflect_ref().as_set() {
+            *self = set.iter().filter_map(T::from_reflect).collect();
+        }
+    }
+
+    // `Value::Set` is a `BTreeSet` so the elements are ordered regardless of the iteration order
+    // of the `HashSet`
+    fn to_value(&self) -> Value {
+        let data = HashSet::iter(self).map(Reflect::to_value).collect();
+        Value::Set(data)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        let value = self.to_value();
+        Box::new(Self::from_reflect(&value).unwrap())
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(Set::iter(self)).finish()
+    }
+}
+
+impl<T, S> FromReflect for HashSet<T, S>
+where
+    T: FromReflect + DescribeType + Hash + Eq,
+    S: BuildHasher + Default + Send + 'static,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let set = reflect.reflect_ref().as_set()?;
+        let mut out = HashSet::with_capacity_and_hasher(set.len(), S::default());
+        for value in set.iter() {
+            out.insert(T::from_reflect(value)?);
+        }
+        Some(out)
+    }
+}
+
+impl<T, S> From<HashSet<T, S>> for Value
+where
+    T: Reflect,
+{
+    fn from(set: HashSet<T, S>) -> Self {
+        let set = set.into_iter().map(|value| value.to_value()).collect();
+        Value::Set(set)
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::iter::PairIterMut;
+use crate::type_info::graph::MapNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Map;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+// `IndexMap`s are reflected as maps that iterate in insertion order. Removing an entry keeps the
+// order of the others.
+
+impl<K, V, S> Map for IndexMap<K, V, S>
+where
+    K: FromReflect + DescribeType + Hash + Eq,
+    V: FromReflect + DescribeType,
+    S: BuildHasher + Default + Send + 'static,
+{
+    fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+        let key = K::from_reflect(key)?;
+        let value = IndexMap::get(self, &key)?;
+        Some(value.as_reflect())
+    }
+
+    fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+        let key = K::from_reflect(key)?;
+        let value = IndexMap::get_mut(self, &key)?;
+        Some(value.as_reflect_mut())
+    }
+
+    fn insert(&mut self, key: &dyn Reflect, value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let key = K::from_reflect(key)?;
+        let value = V::from_reflect(value)?;
+        let previous = IndexMap::insert(self, key, value)?;
+        Some(Box::new(previous))
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let key = K::from_reflect(key)?;
+        // keep the order of the other entries
+        let previous = IndexMap::shift_remove(self, &key)?;
+        Some(Box::new(previous))
+    }
+
+    fn len(&self) -> usize {
+        IndexMap::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        IndexMap::is_empty(self)
+    }
+
+    fn iter(&self) -> crate::map::Iter<'_> {
+        let iter = IndexMap::iter(self).map(|(key, value)| (key.as_reflect(), value.as_reflect()));
+        Box::new(iter)
+    }
+
+    fn iter_mut(&mut self) -> PairIterMut<'_, dyn Reflect> {
+        let iter =
+            IndexMap::iter_mut(self).map(|(key, value)| (key.as_reflect(), value.as_reflect_mut()));
+        Box::new(iter)
+    }
+}
+
+impl<K, V, S> DescribeType for IndexMap<K, V, S>
+where
+    K: DescribeType,
+    V: DescribeType,
+    S: 'static,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(|graph| MapNode::new::<Self, K, V>(graph))
+    }
+}
+
+impl<K, V, S> Reflect for IndexMap<K, V, S>
+where
+    K: FromReflect + DescribeType + Hash + Eq,
+    V: FromReflect + DescribeType,
+    S: BuildHasher + Default + Send + 'static,
+{
+    trivial_reflect_methods!();
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Map(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Map(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Map(self)
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(map) = value.reflect_ref().as_map() {
+            for (key, new_value) in map.iter() {
+                if let Some(value) = Map::get_mut(self, key) {
+                    value.patch(new_value);
+                }
+            }
+        }
+    }
+
+    // `Value::Map` is a `BTreeMap` so the entries are ordered by key rather than by insertion
+    fn to_value(&self) -> Value {
+        let data = IndexMap::iter(self)
+            .map(|(key, value)| (key.to_value(), value.to_value()))
+            .collect();
+        Value::Map(data)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        let value = self.to_value();
+        Box::new(Self::from_reflect(&value).unwrap())
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(Map::iter(self)).finish()
+    }
+}
+
+impl<K, V, S> FromReflect for IndexMap<K, V, S>
+where
+    K: FromReflect + DescribeType + Hash + Eq,
+    V: FromReflect + DescribeType,
+    S: BuildHasher + Default + Send + 'static,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let map = reflect.as_reflect().as_map()?;
+        let mut out = IndexMap::with_capacity_and_hasher(map.len(), S::default());
+        for (key, value) in map.iter() {
+            out.insert(K::from_reflect(key)?, V::from_reflect(value)?);
+        }
+        Some(out)
+    }
+}
+
+impl<K, V, S> From<IndexMap<K, V, S>> for Value
+where
+    K: Reflect,
+    V: Reflect,
+{
+    fn from(map: IndexMap<K, V, S>) -> Self {
+        let map = map
+            .into_iter()
+            .map(|(key, value)| (key.to_value(), value.to_value()))
+            .collect();
+        Value::Map(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::hash::BuildHasherDefault;
+
+    use super::*;
+
+    type Map<K, V> = IndexMap<K, V, BuildHasherDefault<ahash::AHasher>>;
+
+    #[test]
+    fn iterates_in_insertion_order() {
+        let mut map = Map::default();
+        map.insert("b".to_owned(), 1);
+        map.insert("a".to_owned(), 2);
+        map.insert("c".to_owned(), 3);
+
+        let reflect = map.as_map_mut().unwrap();
+        reflect.remove(&"b".to_owned());
+        reflect.insert(&"b".to_owned(), &4);
+
+        let keys = reflect
+            .iter()
+            .map(|(key, _)| key.downcast_ref::<String>().unwrap().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["a", "c", "b"]);
+
+        let new_map = Map::<String, i32>::from_reflect(&map.to_value()).unwrap();
+        assert_eq!(new_map, map);
+    }
+}
@@ -0,0 +1,197 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use core::any::Any;
+use core::cell::Cell;
+use core::cell::RefCell;
+use core::fmt;
+
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectGuard;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::TypeDescriptor;
+use crate::Value;
+
+// Interior mutability types are described as the type they contain. Mutable access goes through
+// `get_mut` which doesn't need to lock anything, so `reflect_mut` is transparent. Shared access
+// has to go through `Reflect::reflect_guard` since references into the value cannot outlive the
+// lock, so `reflect_ref` returns `ReflectRef::Opaque`. `as_any`, `as_any_mut`, `as_reflect`, and
+// `as_reflect_mut` all return the wrapper so downcasting finds the same type either way.
+//
+// `try_guard` doesn't wait for the lock since the lock might be held by the caller, such as when
+// comparing a value that's already locked, which would deadlock or panic. If the value is locked
+// `reflect_guard` returns a guard for the wrapper itself, which is opaque, so lookups fail with
+// `KeyPathErrorKind::Locked` and `FromReflect` returns `None`. `to_value` and `clone_reflect` have
+// no way to fail so they wait for the lock with `guard`, the same as `Clone` for `Mutex` contents
+// would, and panic for a mutably borrowed `RefCell` the same as `RefCell::clone`.
+macro_rules! impl_interior_mut {
+    (
+        $ty:ident<T> $(where T: $bound:ident)?;
+        get_mut($get_mut_this:ident) => $get_mut:expr;
+        guard($guard_this:ident) => $guard:expr;
+        try_guard($try_guard_this:ident) => $try_guard:expr;
+        into_inner($into_inner_this:ident) => $into_inner:expr;
+    ) => {
+        impl<T> DescribeType for $ty<T>
+        where
+            T: DescribeType,
+        {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                T::build(graph)
+            }
+        }
+
+        impl<T> Reflect for $ty<T>
+        where
+            T: FromReflect + DescribeType $(+ $bound)?,
+        {
+            fn type_descriptor(&self) -> Cow<'static, TypeDescriptor> {
+                <T as DescribeType>::type_descriptor()
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                let $into_inner_this = *self;
+                <T as Reflect>::reflect_owned(Box::new($into_inner))
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                let $get_mut_this = self;
+                <T as Reflect>::reflect_mut($get_mut)
+            }
+
+            fn reflect_guard(&self) -> ReflectGuard<'_> {
+                let $try_guard_this = self;
+                match $try_guard {
+                    Some(guard) => guard,
+                    None => ReflectGuard::new(self),
+                }
+            }
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                let value = value.reflect_guard();
+                let $get_mut_this = self;
+                <T as Reflect>::patch($get_mut, &*value)
+            }
+
+            fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+                let value = value.reflect_guard();
+                let $get_mut_this = self;
+                <T as Reflect>::try_patch($get_mut, &*value)
+            }
+
+            fn to_value(&self) -> Value {
+                let $guard_this = self;
+                $guard.to_value()
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                let $guard_this = self;
+                let value = T::from_reflect(&*$guard).unwrap();
+                Box::new($ty::new(value))
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let $try_guard_this = self;
+                match $try_guard {
+                    Some(guard) => guard.debug(f),
+                    None => write!(f, "<locked>"),
+                }
+            }
+        }
+
+        impl<T> FromReflect for $ty<T>
+        where
+            T: FromReflect + DescribeType $(+ $bound)?,
+        {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                let value = T::from_reflect(&*reflect.reflect_guard())?;
+                Some($ty::new(value))
+            }
+        }
+
+        impl<T> From<$ty<T>> for Value
+        where
+            T: Into<Value>,
+        {
+            fn from($into_inner_this: $ty<T>) -> Self {
+                $into_inner.into()
+            }
+        }
+    };
+}
+
+impl_interior_mut! {
+    Cell<T> where T: Copy;
+    get_mut(this) => Cell::get_mut(this);
+    guard(this) => ReflectGuard::from_guard(Box::new(this.get()));
+    try_guard(this) => Some(ReflectGuard::from_guard(Box::new(this.get())));
+    into_inner(this) => Cell::into_inner(this);
+}
+
+impl_interior_mut! {
+    RefCell<T>;
+    get_mut(this) => RefCell::get_mut(this);
+    guard(this) => ReflectGuard::from_guard(this.borrow());
+    try_guard(this) => this.try_borrow().ok().map(ReflectGuard::from_guard);
+    into_inner(this) => RefCell::into_inner(this);
+}
+
+#[cfg(feature = "std")]
+mod sync {
+    use std::sync::Mutex;
+    use std::sync::PoisonError;
+    use std::sync::RwLock;
+    use std::sync::TryLockError;
+
+    use super::*;
+
+    impl_interior_mut! {
+        Mutex<T>;
+        get_mut(this) => this.get_mut().unwrap_or_else(PoisonError::into_inner);
+        guard(this) => ReflectGuard::from_guard(this.lock().unwrap_or_else(PoisonError::into_inner));
+        try_guard(this) => match this.try_lock() {
+            Ok(guard) => Some(ReflectGuard::from_guard(guard)),
+            Err(TryLockError::Poisoned(err)) => Some(ReflectGuard::from_guard(err.into_inner())),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        into_inner(this) => this.into_inner().unwrap_or_else(PoisonError::into_inner);
+    }
+
+    impl_interior_mut! {
+        RwLock<T>;
+        get_mut(this) => this.get_mut().unwrap_or_else(PoisonError::into_inner);
+        guard(this) => ReflectGuard::from_guard(this.read().unwrap_or_else(PoisonError::into_inner));
+        try_guard(this) => match this.try_read() {
+            Ok(guard) => Some(ReflectGuard::from_guard(guard)),
+            Err(TryLockError::Poisoned(err)) => Some(ReflectGuard::from_guard(err.into_inner())),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        into_inner(this) => this.into_inner().unwrap_or_else(PoisonError::into_inner);
+    }
+}
@@ -0,0 +1,168 @@
+use alloc::boxed::Box;
+use alloc::collections::LinkedList;
+use core::any::Any;
+
+use crate::array::Array;
+use crate::iter::ValueIterMut;
+use crate::type_info::graph::ListNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::List;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+// accessing elements by index walks the list so `get` and `get_mut` are O(n). `iter` and
+// `iter_mut` don't go through indices
+
+impl<T> List for LinkedList<T>
+where
+    T: FromReflect + DescribeType,
+{
+    fn push(&mut self, value: &dyn Reflect) {
+        if let Some(value) = T::from_reflect(value) {
+            LinkedList::push_back(self, value);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        let value = LinkedList::pop_back(self)?;
+        Some(Box::new(value))
+    }
+
+    fn try_remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut tail = LinkedList::split_off(self, index);
+        let value = tail.pop_front()?;
+        LinkedList::append(self, &mut tail);
+        Some(Box::new(value))
+    }
+
+    fn try_insert(&mut self, index: usize, value: &dyn Reflect) -> bool {
+        if index > self.len() {
+            return false;
+        }
+        if let Some(value) = T::from_reflect(value) {
+            let mut tail = LinkedList::split_off(self, index);
+            LinkedList::push_back(self, value);
+            LinkedList::append(self, &mut tail);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<T> Array for LinkedList<T>
+where
+    T: FromReflect + DescribeType,
+{
+    fn get(&self, index: usize) -> Option<&dyn Reflect> {
+        LinkedList::iter(self)
+            .nth(index)
+            .map(|value| value.as_reflect())
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        LinkedList::iter_mut(self)
+            .nth(index)
+            .map(|value| value.as_reflect_mut())
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        LinkedList::is_empty(self)
+    }
+
+    fn iter(&self) -> crate::array::Iter<'_> {
+        crate::array::Iter::boxed(LinkedList::iter(self).map(|value| value.as_reflect()))
+    }
+
+    fn iter_mut(&mut self) -> ValueIterMut<'_> {
+        let iter = LinkedList::iter_mut(self).map(|value| value.as_reflect_mut());
+        Box::new(iter)
+    }
+}
+
+impl<T> DescribeType for LinkedList<T>
+where
+    T: DescribeType,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(|graph| ListNode::new::<Self, T>(graph))
+    }
+}
+
+impl<T> Reflect for LinkedList<T>
+where
+    T: FromReflect + DescribeType,
+{
+    trivial_reflect_methods!();
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(list) = value.reflect_ref().as_list() {
+            for (value, new_value) in LinkedList::iter_mut(self).zip(list.iter()) {
+                value.patch(new_value);
+            }
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let data = LinkedList::iter(self).map(Reflect::to_value).collect();
+        Value::List(data)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        let value = self.to_value();
+        Box::new(Self::from_reflect(&value).unwrap())
+    }
+
+    fn debug(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(Array::iter(self)).finish()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::List(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::List(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::List(self)
+    }
+}
+
+impl<T> FromReflect for LinkedList<T>
+where
+    T: FromReflect + DescribeType,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let list = reflect.reflect_ref().as_list()?;
+        let mut out = LinkedList::new();
+        for value in list.iter() {
+            out.push_back(T::from_reflect(value)?);
+        }
+        Some(out)
+    }
+}
+
+impl<T> From<LinkedList<T>> for Value
+where
+    T: Reflect,
+{
+    fn from(list: LinkedList<T>) -> Self {
+        let list = list.into_iter().map(|value| value.to_value()).collect();
+        Value::List(list)
+    }
+}
@@ -8,16 +8,41 @@ use core::ops::RangeToInclusive;
 use crate::__private::*;
 use mirror_mirror_macros::__private_derive_reflect_foreign;
 
+mod arc;
 mod array;
+mod binary_heap;
 mod boxed;
 mod btree_map;
+mod btree_set;
+mod interior_mut;
+mod linked_list;
+mod reverse;
+mod string;
 mod vec;
+mod vec_deque;
 mod via_scalar;
 
+#[cfg(feature = "std")]
+mod hash_map;
+#[cfg(feature = "std")]
+mod hash_set;
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "chrono")]
+mod chrono;
 #[cfg(feature = "glam")]
 mod glam;
+#[cfg(feature = "indexmap")]
+mod indexmap;
 #[cfg(feature = "macaw")]
 mod macaw;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "uuid")]
+mod uuid;
 
 __private_derive_reflect_foreign! {
     #[reflect(opt_out(Clone, Debug), crate_name(crate))]
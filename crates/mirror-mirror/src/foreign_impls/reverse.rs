@@ -0,0 +1,110 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use core::any::Any;
+use core::cmp::Reverse;
+use core::fmt;
+
+use crate::reflect_debug;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectGuard;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::TypeDescriptor;
+use crate::Value;
+
+// `Reverse<T>` is reflected transparently as `T`. It only changes how values are ordered which
+// doesn't affect reflection.
+
+impl<T> DescribeType for Reverse<T>
+where
+    T: DescribeType,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        T::build(graph)
+    }
+}
+
+impl<T> Reflect for Reverse<T>
+where
+    T: Reflect + DescribeType,
+{
+    fn type_descriptor(&self) -> Cow<'static, TypeDescriptor> {
+        <T as DescribeType>::type_descriptor()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        <T as Reflect>::as_any(&self.0)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        <T as Reflect>::as_any_mut(&mut self.0)
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        <T as Reflect>::as_reflect(&self.0)
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        <T as Reflect>::as_reflect_mut(&mut self.0)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        <T as Reflect>::reflect_owned(Box::new(self.0))
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        <T as Reflect>::reflect_ref(&self.0)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        <T as Reflect>::reflect_mut(&mut self.0)
+    }
+
+    fn reflect_guard(&self) -> ReflectGuard<'_> {
+        <T as Reflect>::reflect_guard(&self.0)
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        <T as Reflect>::patch(&mut self.0, value)
+    }
+
+    fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+        <T as Reflect>::try_patch(&mut self.0, value)
+    }
+
+    fn to_value(&self) -> Value {
+        <T as Reflect>::to_value(&self.0)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        <T as Reflect>::clone_reflect(&self.0)
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        reflect_debug(self, f)
+    }
+}
+
+impl<T> FromReflect for Reverse<T>
+where
+    T: FromReflect + DescribeType,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        Some(Reverse(T::from_reflect(reflect)?))
+    }
+}
+
+impl<T> From<Reverse<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(reverse: Reverse<T>) -> Self {
+        reverse.0.into()
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::boxed::Box;
+use core::any::Any;
+
+use smallvec::Array as SmallVecArray;
+use smallvec::SmallVec;
+
+use crate::array::Array;
+use crate::iter::ValueIterMut;
+use crate::type_info::graph::ListNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::List;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+impl<A> List for SmallVec<A>
+where
+    A: SmallVecArray + 'static,
+    A::Item: FromReflect + DescribeType,
+{
+    fn push(&mut self, value: &dyn Reflect) {
+        if let Some(value) = A::Item::from_reflect(value) {
+            SmallVec::push(self, value);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        let value = SmallVec::pop(self)?;
+        Some(Box::new(value))
+    }
+
+    fn try_remove(&mut self, index: usize) -> Option<Box<dyn Reflect>> {
+        if index < self.len() {
+            let value = SmallVec::remove(self, index);
+            Some(Box::new(value))
+        } else {
+            None
+        }
+    }
+
+    fn try_insert(&mut self, index: usize, value: &dyn Reflect) -> bool {
+        if index > self.len() {
+            return false;
+        }
+        if let Some(value) = A::Item::from_reflect(value) {
+            SmallVec::insert(self, index, value);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<A> Array for SmallVec<A>
+where
+    A: SmallVecArray + 'static,
+    A::Item: FromReflect + DescribeType,
+{
+    fn get(&self, index: usize) -> Option<&dyn Reflect> {
+        self.as_slice().get(index).map(|value| value.as_reflect())
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        self.as_mut_slice()
+            .get_mut(index)
+            .map(|value| value.as_reflect_mut())
+    }
+
+    fn len(&self) -> usize {
+        SmallVec::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SmallVec::is_empty(self)
+    }
+
+    fn iter(&self) -> crate::array::Iter<'_> {
+        crate::array::Iter::new(self)
+    }
+
+    fn iter_mut(&mut self) -> ValueIterMut<'_> {
+        let iter = self
+            .as_mut_slice()
+            .iter_mut()
+            .map(|value| value.as_reflect_mut());
+        Box::new(iter)
+    }
+}
+
+impl<A> DescribeType for SmallVec<A>
+where
+    A: SmallVecArray + 'static,
+    A::Item: DescribeType,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(ListNode::new::<Self, A::Item>)
+    }
+}
+
+impl<A> Reflect for SmallVec<A>
+where
+    A: SmallVecArray + 'static,
+    A::Item: FromReflect + DescribeType,
+{
+    trivial_reflect_methods!();
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(list) = value.reflect_ref().as_list() {
+            for (idx, new_value) in list.iter().enumerate() {
+                if let Some(value) = self.get_mut(idx) {
+                    value.patch(new_value);
+                }
+            }
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let data = self.iter().map(Reflect::to_value).collect();
+        Value::List(data)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        let value = self.to_value();
+        Box::new(Self::from_reflect(&value).unwrap())
+    }
+
+    fn debug(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::List(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::List(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::List(self)
+    }
+}
+
+impl<A> FromReflect for SmallVec<A>
+where
+    A: SmallVecArray + 'static,
+    A::Item: FromReflect + DescribeType,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let list = reflect.reflect_ref().as_list()?;
+        let mut out = SmallVec::new();
+        for value in list.iter() {
+            out.push(A::Item::from_reflect(value)?);
+        }
+        Some(out)
+    }
+}
+
+impl<A> From<SmallVec<A>> for Value
+where
+    A: SmallVecArray,
+    A::Item: Reflect,
+{
+    fn from(list: SmallVec<A>) -> Self {
+        let list = list.into_iter().map(|value| value.to_value()).collect();
+        Value::List(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_path;
+    use crate::key_path::GetPath;
+
+    #[test]
+    fn works() {
+        let mut list = SmallVec::<[i32; 2]>::from_slice(&[1, 2]);
+        list.as_list_mut().unwrap().push(&3);
+        assert!(list.spilled());
+        assert_eq!(list.get_at::<i32>(&key_path!([2])).unwrap(), &3);
+
+        let value = list.to_value();
+        assert_eq!(value, alloc::vec::Vec::from([1, 2, 3]).to_value());
+        assert_eq!(SmallVec::<[i32; 2]>::from_reflect(&value).unwrap(), list);
+
+        list.patch(&alloc::vec::Vec::from([10]));
+        assert_eq!(list.as_slice(), [10, 2, 3]);
+    }
+}
@@ -0,0 +1,194 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt;
+
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::PatchError;
+use crate::Reflect;
+use crate::ReflectGuard;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::ScalarOwned;
+use crate::ScalarRef;
+use crate::StrRef;
+use crate::TypeDescriptor;
+use crate::Value;
+
+// String-like types are described as `String` and converted to and from `String` when reflected.
+// `reflect_ref` returns a `ScalarRef::String` that borrows the `str`. There is no `String` to
+// borrow mutably so `reflect_mut` returns `Opaque` and they're changed by patching.
+//
+// Paths and OS strings that aren't valid UTF-8 have no `str` to borrow either, so `reflect_ref`
+// returns `Opaque` for those and their guard owns a lossily converted `String`, which
+// `reflect_eq`, `reflect_diff`, the serializers, and the visitors look through.
+//
+// `Arc<str>` doesn't overlap with the `Arc<T>` impl since that requires `T: Sized`.
+macro_rules! impl_reflect_via_string {
+    (
+        $ty:ty;
+        as_str($as_str_this:ident) => $as_str:expr;
+        from_string($from_string_this:ident) => $from_string:expr;
+    ) => {
+        impl DescribeType for $ty {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                <String as DescribeType>::build(graph)
+            }
+        }
+
+        impl Reflect for $ty {
+            fn type_descriptor(&self) -> Cow<'static, TypeDescriptor> {
+                <String as DescribeType>::type_descriptor()
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Scalar(ScalarOwned::String(into_string(&*self)))
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                match self.as_str() {
+                    Cow::Borrowed(string) => {
+                        ReflectRef::Scalar(ScalarRef::String(StrRef::new(string, self)))
+                    }
+                    Cow::Owned(_) => ReflectRef::Opaque(self),
+                }
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Opaque(self)
+            }
+
+            fn reflect_guard(&self) -> ReflectGuard<'_> {
+                match self.as_str() {
+                    Cow::Borrowed(_) => ReflectGuard::new(self),
+                    Cow::Owned(string) => ReflectGuard::from_guard(Box::new(string)),
+                }
+            }
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                if let Some(new) = Self::from_reflect(value) {
+                    *self = new;
+                }
+            }
+
+            fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+                if let Some(new) = Self::from_reflect(value) {
+                    *self = new;
+                    Ok(())
+                } else {
+                    Err(PatchError::type_mismatch(self, value))
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(into_string(self))
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                Box::new(self.clone())
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if f.alternate() {
+                    write!(f, "{:#?}", self)
+                } else {
+                    write!(f, "{:?}", self)
+                }
+            }
+        }
+
+        impl FromReflect for $ty {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let Some(value) = reflect.downcast_ref::<Self>() {
+                    Some(value.clone())
+                } else {
+                    let $from_string_this = String::from_reflect(reflect)?;
+                    Some($from_string)
+                }
+            }
+        }
+
+        impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                value.to_value()
+            }
+        }
+
+        impl AsStr for $ty {
+            fn as_str(&self) -> Cow<'_, str> {
+                let $as_str_this = self;
+                Cow::from($as_str)
+            }
+        }
+    };
+}
+
+trait AsStr {
+    fn as_str(&self) -> Cow<'_, str>;
+}
+
+fn into_string(value: &impl AsStr) -> String {
+    value.as_str().into_owned()
+}
+
+impl_reflect_via_string! {
+    Box<str>;
+    as_str(this) => &**this;
+    from_string(string) => string.into_boxed_str();
+}
+
+impl_reflect_via_string! {
+    Cow<'static, str>;
+    as_str(this) => &**this;
+    from_string(string) => Cow::Owned(string);
+}
+
+impl_reflect_via_string! {
+    Arc<str>;
+    as_str(this) => &**this;
+    from_string(string) => Arc::from(string);
+}
+
+// Paths and OS strings aren't necessarily UTF-8. Such values are converted lossily, replacing
+// invalid sequences with `U+FFFD REPLACEMENT CHARACTER`, so they won't round-trip.
+#[cfg(feature = "std")]
+mod os_str {
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    impl_reflect_via_string! {
+        PathBuf;
+        as_str(this) => this.to_string_lossy();
+        from_string(string) => PathBuf::from(string);
+    }
+
+    impl_reflect_via_string! {
+        OsString;
+        as_str(this) => this.to_string_lossy();
+        from_string(string) => OsString::from(string);
+    }
+}
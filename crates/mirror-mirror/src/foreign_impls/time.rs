@@ -0,0 +1,68 @@
+use time::Date;
+use time::OffsetDateTime;
+use time::PrimitiveDateTime;
+
+use super::via_scalar::impl_reflect_via_scalar;
+use super::via_scalar::IntoOption;
+
+// `Date`s are reflected as their Julian day number.
+impl_reflect_via_scalar! {
+    Date,
+    i32,
+    |date: &Date| date.to_julian_day(),
+    |day| Date::from_julian_day(day).ok(),
+}
+
+// Date times are reflected as the number of nanoseconds since the Unix epoch. `PrimitiveDateTime`s
+// are assumed to be in UTC. `OffsetDateTime`s are converted to UTC so their offset isn't kept,
+// although the point in time they represent is.
+impl_reflect_via_scalar! {
+    PrimitiveDateTime,
+    i128,
+    |date_time: &PrimitiveDateTime| date_time.assume_utc().unix_timestamp_nanos(),
+    |nanos| {
+        let date_time = OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+        Some(PrimitiveDateTime::new(date_time.date(), date_time.time()))
+    },
+}
+
+impl_reflect_via_scalar! {
+    OffsetDateTime,
+    i128,
+    |date_time: &OffsetDateTime| date_time.unix_timestamp_nanos(),
+    |nanos| OffsetDateTime::from_unix_timestamp_nanos(nanos).ok(),
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+    use time::Time;
+    use time::UtcOffset;
+
+    use super::*;
+    use crate::FromReflect;
+    use crate::Reflect;
+
+    #[test]
+    fn works() {
+        let date = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+        assert_eq!(date.to_value(), 2_460_370.to_value());
+        assert_eq!(Date::from_reflect(&date.to_value()).unwrap(), date);
+
+        let date_time = PrimitiveDateTime::new(
+            Date::from_calendar_date(1969, Month::December, 31).unwrap(),
+            Time::from_hms_milli(23, 59, 59, 500).unwrap(),
+        );
+        assert_eq!(date_time.to_value(), (-500_000_000_i128).to_value());
+        assert_eq!(
+            PrimitiveDateTime::from_reflect(&date_time.to_value()).unwrap(),
+            date_time
+        );
+
+        let offset = PrimitiveDateTime::new(date, Time::from_hms_nano(12, 0, 0, 1).unwrap())
+            .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        let round_tripped = OffsetDateTime::from_reflect(&offset.to_value()).unwrap();
+        assert_eq!(round_tripped, offset);
+        assert!(round_tripped.offset().is_utc());
+    }
+}
@@ -0,0 +1,51 @@
+use alloc::string::String;
+
+use uuid::Uuid;
+
+use super::via_scalar::impl_reflect_via_scalar;
+use super::via_scalar::IntoOption;
+use crate::FromReflect;
+use crate::Reflect;
+
+// `Uuid`s are reflected as `u128`s. `FromReflect` also accepts strings such as
+// `"67e55044-10b1-426f-9247-bb680e5fe0c8"`.
+impl_reflect_via_scalar! {
+    Uuid,
+    u128,
+    |uuid: &Uuid| uuid.as_u128(),
+    Uuid::from_u128,
+    or_else: uuid_from_str,
+}
+
+fn uuid_from_str(reflect: &dyn Reflect) -> Option<Uuid> {
+    let string = String::from_reflect(reflect)?;
+    Uuid::parse_str(&string).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_info::ScalarType;
+    use crate::DescribeType;
+
+    #[test]
+    fn works() {
+        let uuid = Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+        assert_eq!(
+            uuid.to_value(),
+            0x67e5504410b1426f9247bb680e5fe0c8_u128.to_value()
+        );
+        assert_eq!(Uuid::from_reflect(&uuid.to_value()).unwrap(), uuid);
+
+        let string = "67e55044-10b1-426f-9247-bb680e5fe0c8".to_owned();
+        assert_eq!(Uuid::from_reflect(&string).unwrap(), uuid);
+        assert!(Uuid::from_reflect(&"not a uuid".to_owned()).is_none());
+
+        assert_eq!(
+            <Uuid as DescribeType>::type_descriptor()
+                .get_type()
+                .as_scalar(),
+            Some(ScalarType::u128)
+        );
+    }
+}
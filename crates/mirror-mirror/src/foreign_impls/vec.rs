@@ -39,6 +39,18 @@ where
             None
         }
     }
+
+    fn try_insert(&mut self, index: usize, value: &dyn Reflect) -> bool {
+        if index > self.len() {
+            return false;
+        }
+        if let Some(value) = T::from_reflect(value) {
+            Vec::insert(self, index, value);
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl<T> Array for Vec<T>
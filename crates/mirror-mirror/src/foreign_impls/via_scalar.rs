@@ -9,21 +9,24 @@ use core::num::NonZeroU32;
 use core::num::NonZeroU64;
 use core::num::NonZeroU8;
 use core::num::NonZeroUsize;
+use core::num::Wrapping;
+use core::sync::atomic::Ordering;
 use core::time::Duration;
 
+use crate::FromReflect;
+use crate::Reflect;
+
+// Types that are reflected via a scalar are described as that scalar, so serializers and schemas
+// treat them the same as the scalar. `reflect_mut` returns `Opaque` since there is no scalar to
+// borrow mutably, so they're changed by patching.
+//
+// The `@without_describe` form leaves out `DescribeType`, for types with scalar nodes of their
+// own such as `NonZero*`.
 macro_rules! impl_reflect_via_scalar {
-    ($ty:ty, $via_ty:ty, $get_fn:expr, $new_fn:expr $(,)?) => {
+    (@without_describe $ty:ty, $via_ty:ty, $get_fn:expr, $new_fn:expr $(, or_else: $or_else:expr)? $(,)?) => {
         const _: () = {
             use $crate::__private::*;
 
-            impl DescribeType for $ty {
-                fn build(graph: &mut TypeGraph) -> NodeId {
-                    graph.get_or_build_node_with::<Self, _>(|graph| {
-                        OpaqueNode::new::<Self>(Default::default(), graph)
-                    })
-                }
-            }
-
             impl Reflect for $ty {
                 trivial_reflect_methods!();
 
@@ -47,13 +50,22 @@ macro_rules! impl_reflect_via_scalar {
                     }
                 }
 
+                fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+                    if let Some(n) = Self::from_reflect(value) {
+                        *self = n;
+                        Ok(())
+                    } else {
+                        Err(PatchError::type_mismatch(self, value))
+                    }
+                }
+
                 #[allow(clippy::redundant_closure_call)]
                 fn to_value(&self) -> Value {
                     $get_fn(self).to_value()
                 }
 
                 fn clone_reflect(&self) -> Box<dyn Reflect> {
-                    Box::new(*self)
+                    Box::new(Self::from_reflect(self).unwrap())
                 }
 
                 fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -66,12 +78,16 @@ macro_rules! impl_reflect_via_scalar {
             }
 
             impl FromReflect for $ty {
+                // goes through `$via_ty` even for `Self` since not all types are `Clone`, such as
+                // atomics
+                #[allow(clippy::redundant_closure_call)]
                 fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
                     if let Some(n) = reflect.downcast_ref::<Self>() {
-                        Some(*n)
+                        $new_fn($get_fn(n)).into_option()
                     } else {
                         <$via_ty>::from_reflect(reflect)
                             .and_then(|value| $new_fn(value).into_option())
+                            $(.or_else(|| $or_else(reflect)))?
                     }
                 }
             }
@@ -83,23 +99,163 @@ macro_rules! impl_reflect_via_scalar {
             }
         };
     };
+
+    ($ty:ty, $via_ty:ty, $($rest:tt)*) => {
+        const _: () = {
+            use $crate::__private::*;
+
+            impl DescribeType for $ty {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    <$via_ty as DescribeType>::build(graph)
+                }
+            }
+        };
+
+        impl_reflect_via_scalar! { @without_describe $ty, $via_ty, $($rest)* }
+    };
+}
+
+// used by the optional integrations, such as `uuid`
+#[allow(unused_imports)]
+pub(super) use impl_reflect_via_scalar;
+
+impl_reflect_via_scalar! { @without_describe NonZeroUsize, usize, |n: &NonZeroUsize| n.get(), Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroU8,    u8,    |n: &NonZeroU8| n.get(),    Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroU16,   u16,   |n: &NonZeroU16| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroU32,   u32,   |n: &NonZeroU32| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroU64,   u64,   |n: &NonZeroU64| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroU128,  u128,  |n: &NonZeroU128| n.get(),  Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroI8,    i8,    |n: &NonZeroI8| n.get(),    Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroI16,   i16,   |n: &NonZeroI16| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroI32,   i32,   |n: &NonZeroI32| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroI64,   i64,   |n: &NonZeroI64| n.get(),   Self::new }
+impl_reflect_via_scalar! { @without_describe NonZeroI128,  i128,  |n: &NonZeroI128| n.get(),  Self::new }
+
+macro_rules! impl_reflect_for_wrapping {
+    ($($ty:ident)*) => {
+        $(
+            impl_reflect_via_scalar! { Wrapping<$ty>, $ty, |n: &Wrapping<$ty>| n.0, Wrapping }
+        )*
+    };
+}
+
+impl_reflect_for_wrapping! {
+    usize u8 u16 u32 u64 u128
+    i8 i16 i32 i64 i128
+}
+
+// `Saturating<T>` requires Rust 1.74, which `build.rs` checks for. Clippy compares it against the
+// crate's MSRV regardless.
+#[cfg(has_saturating)]
+#[allow(unknown_lints, clippy::incompatible_msrv)]
+mod saturating {
+    use core::num::Saturating;
+
+    use super::IntoOption;
+
+    macro_rules! impl_reflect_for_saturating {
+        ($($ty:ident)*) => {
+            $(
+                impl_reflect_via_scalar! { Saturating<$ty>, $ty, |n: &Saturating<$ty>| n.0, Saturating }
+            )*
+        };
+    }
+
+    impl_reflect_for_saturating! {
+        usize u8 u16 u32 u64 u128
+        i8 i16 i32 i64 i128
+    }
+}
+
+// Atomics are read with `Ordering::SeqCst`. Writes go through `&mut` references which don't need
+// an ordering.
+macro_rules! impl_reflect_for_atomic {
+    ($($width:literal => $($atomic:ident($ty:ident))*;)*) => {
+        $(
+            $(
+                #[cfg(target_has_atomic = $width)]
+                impl_reflect_via_scalar! {
+                    core::sync::atomic::$atomic,
+                    $ty,
+                    |n: &core::sync::atomic::$atomic| n.load(Ordering::SeqCst),
+                    core::sync::atomic::$atomic::new,
+                }
+            )*
+        )*
+    };
+}
+
+impl_reflect_for_atomic! {
+    "8" => AtomicBool(bool) AtomicU8(u8) AtomicI8(i8);
+    "16" => AtomicU16(u16) AtomicI16(i16);
+    "32" => AtomicU32(u32) AtomicI32(i32);
+    "64" => AtomicU64(u64) AtomicI64(i64);
+    "ptr" => AtomicUsize(usize);
+}
+
+// `Duration`s are reflected as whole nanoseconds which is lossless. They used to be reflected as
+// `f32` seconds so such values are still accepted by `FromReflect`.
+impl_reflect_via_scalar! {
+    Duration,
+    u128,
+    |d: &Duration| d.as_nanos(),
+    duration_from_nanos,
+    or_else: duration_from_secs_f32,
+}
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+fn duration_from_nanos(nanos: u128) -> Option<Duration> {
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+    let nanos = (nanos % NANOS_PER_SEC) as u32;
+    Some(Duration::new(secs, nanos))
+}
+
+fn duration_from_secs_f32(reflect: &dyn Reflect) -> Option<Duration> {
+    let secs = f32::from_reflect(reflect)?;
+    // `Duration::from_secs_f32` panics on negative, non-finite, and overflowing values
+    if (0.0..u64::MAX as f32).contains(&secs) {
+        Some(Duration::from_secs_f32(secs))
+    } else {
+        None
+    }
+}
+
+// `SystemTime`s are reflected as the signed number of nanoseconds since the Unix epoch.
+//
+// `Instant` isn't reflected since it's only meaningful within the process that created it.
+#[cfg(feature = "std")]
+mod system_time {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    impl_reflect_via_scalar! {
+        SystemTime,
+        i128,
+        system_time_to_nanos,
+        system_time_from_nanos,
+    }
+
+    fn system_time_to_nanos(time: &SystemTime) -> i128 {
+        // `as_nanos` is at most `u64::MAX * 10^9` which fits in an `i128`
+        match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => after.as_nanos() as i128,
+            Err(err) => -(err.duration().as_nanos() as i128),
+        }
+    }
+
+    fn system_time_from_nanos(nanos: i128) -> Option<SystemTime> {
+        let duration = duration_from_nanos(nanos.unsigned_abs())?;
+        if nanos >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(duration)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(duration)
+        }
+    }
 }
 
-impl_reflect_via_scalar! { NonZeroUsize, usize, |n: &NonZeroUsize| n.get(), Self::new }
-impl_reflect_via_scalar! { NonZeroU8,    u8,    |n: &NonZeroU8| n.get(),    Self::new }
-impl_reflect_via_scalar! { NonZeroU16,   u16,   |n: &NonZeroU16| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroU32,   u32,   |n: &NonZeroU32| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroU64,   u64,   |n: &NonZeroU64| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroU128,  u128,  |n: &NonZeroU128| n.get(),  Self::new }
-impl_reflect_via_scalar! { NonZeroI8,    i8,    |n: &NonZeroI8| n.get(),    Self::new }
-impl_reflect_via_scalar! { NonZeroI16,   i16,   |n: &NonZeroI16| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroI32,   i32,   |n: &NonZeroI32| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroI64,   i64,   |n: &NonZeroI64| n.get(),   Self::new }
-impl_reflect_via_scalar! { NonZeroI128,  i128,  |n: &NonZeroI128| n.get(),  Self::new }
-
-impl_reflect_via_scalar! { Duration, f32, |d: &Duration| d.as_secs_f32(), Self::from_secs_f32 }
-
-trait IntoOption<T> {
+pub(super) trait IntoOption<T> {
     fn into_option(self) -> Option<T>;
 }
 
@@ -118,22 +274,151 @@ impl<T> IntoOption<T> for T {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::type_info::ScalarType;
     use crate::DescribeType;
+    use crate::ScalarRef;
 
     #[test]
-    fn keeps_type_name() {
+    fn described_as_scalar() {
         assert_eq!(
             <NonZeroI8 as DescribeType>::type_descriptor()
                 .get_type()
-                .type_name(),
-            "core::num::nonzero::NonZero<i8>"
+                .as_scalar(),
+            Some(ScalarType::NonZeroI8)
         );
 
         assert_eq!(
             <Duration as DescribeType>::type_descriptor()
                 .get_type()
-                .type_name(),
-            "core::time::Duration"
+                .as_scalar(),
+            Some(ScalarType::u128)
+        );
+    }
+
+    #[test]
+    fn non_zero_has_no_default() {
+        #[derive(Reflect, Clone, Debug)]
+        #[reflect(crate_name(crate))]
+        struct Foo {
+            n: NonZeroU8,
+        }
+
+        let ty = <NonZeroU8 as DescribeType>::type_descriptor();
+        assert_eq!(ty.type_name(), core::any::type_name::<NonZeroU8>());
+        assert!(!ty.has_default_value());
+        assert!(ty.default_value().is_none());
+        assert_eq!(
+            ty.get_type().as_scalar().unwrap().underlying(),
+            ScalarType::u8
+        );
+
+        let ty = <Foo as DescribeType>::type_descriptor();
+        assert!(ty.default_value().is_none());
+    }
+
+    #[test]
+    fn duration_is_lossless() {
+        let duration = Duration::new(1_000_000, 123_456_789);
+        assert_eq!(duration.to_value(), 1_000_000_123_456_789_u128.to_value());
+        assert_eq!(
+            Duration::from_reflect(&duration.to_value()).unwrap(),
+            duration
+        );
+
+        let max = Duration::MAX.to_value();
+        assert_eq!(Duration::from_reflect(&max).unwrap(), Duration::MAX);
+        assert!(Duration::from_reflect(&u128::MAX.to_value()).is_none());
+    }
+
+    #[test]
+    fn duration_from_f32_seconds() {
+        assert_eq!(
+            Duration::from_reflect(&1.5_f32.to_value()).unwrap(),
+            Duration::from_millis(1500)
+        );
+
+        let mut duration = Duration::ZERO;
+        duration.patch(&2.0_f32);
+        assert_eq!(duration, Duration::from_secs(2));
+
+        assert!(Duration::from_reflect(&(-1.0_f32).to_value()).is_none());
+        assert!(Duration::from_reflect(&f32::NAN.to_value()).is_none());
+        assert!(Duration::from_reflect(&f32::INFINITY.to_value()).is_none());
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut n = Wrapping(u8::MAX);
+        assert_eq!(n.to_value(), u8::MAX.to_value());
+
+        n.patch(&1_u8);
+        assert_eq!(n, Wrapping(1));
+        assert_eq!(Wrapping::<u8>::from_reflect(&2_u8).unwrap(), Wrapping(2));
+        assert!(Wrapping::<u8>::from_reflect(&2_i32).is_none());
+    }
+
+    #[cfg(has_saturating)]
+    #[allow(unknown_lints, clippy::incompatible_msrv)]
+    #[test]
+    fn saturating() {
+        use core::num::Saturating;
+
+        let mut n = Saturating(i8::MIN);
+        assert_eq!(n.to_value(), i8::MIN.to_value());
+
+        n.patch(&1_i8);
+        assert_eq!(n, Saturating(1));
+        assert_eq!(
+            Saturating::<i8>::from_reflect(&2_i8).unwrap(),
+            Saturating(2)
+        );
+        assert!(Saturating::<i8>::from_reflect(&2_u8).is_none());
+        assert_eq!(
+            <Saturating<i8> as DescribeType>::type_descriptor()
+                .get_type()
+                .as_scalar(),
+            Some(ScalarType::i8)
+        );
+    }
+
+    #[test]
+    fn atomics() {
+        use core::sync::atomic::AtomicBool;
+        use core::sync::atomic::AtomicU64;
+
+        let mut n = AtomicU64::new(1);
+        assert_eq!(n.to_value(), 1_u64.to_value());
+        assert_eq!(n.as_scalar().unwrap(), ScalarRef::u64(1));
+
+        n.patch(&2_u64);
+        assert_eq!(n.load(Ordering::SeqCst), 2);
+
+        let cloned = n.clone_reflect();
+        n.store(3, Ordering::SeqCst);
+        assert_eq!(cloned.to_value(), 2_u64.to_value());
+
+        let flag = AtomicBool::from_reflect(&true.to_value()).unwrap();
+        assert!(flag.load(Ordering::SeqCst));
+        assert_eq!(format!("{:?}", flag.as_reflect()), "true");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_time() {
+        use std::time::SystemTime;
+
+        let after = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+        assert_eq!(after.to_value(), 1_700_000_000_000_000_005_i128.to_value());
+        assert_eq!(SystemTime::from_reflect(&after.to_value()).unwrap(), after);
+
+        let before = SystemTime::UNIX_EPOCH - Duration::new(10, 1);
+        assert_eq!(before.to_value(), (-10_000_000_001_i128).to_value());
+        assert_eq!(
+            SystemTime::from_reflect(&before.to_value()).unwrap(),
+            before
         );
+
+        let now = SystemTime::now();
+        assert_eq!(SystemTime::from_reflect(&now.to_value()).unwrap(), now);
     }
 }
@@ -52,6 +52,7 @@ impl<'a> GetField<'a, &str, private::Value> for &'a Value {
             | ReflectRef::Tuple(_)
             | ReflectRef::List(_)
             | ReflectRef::Array(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Opaque(_)
             | ReflectRef::Scalar(_) => None,
         }
@@ -71,6 +72,7 @@ impl<'a> GetFieldMut<'a, &str, private::Value> for &'a mut Value {
             | ReflectMut::Tuple(_)
             | ReflectMut::List(_)
             | ReflectMut::Array(_)
+            | ReflectMut::Set(_)
             | ReflectMut::Opaque(_)
             | ReflectMut::Scalar(_) => None,
         }
@@ -93,7 +95,10 @@ where
                 ReflectRef::Array(inner) => inner.get_field(key),
                 ReflectRef::List(inner) => inner.get_field(key),
                 ReflectRef::Map(inner) => inner.get_field(key),
-                ReflectRef::Struct(_) | ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => None,
+                ReflectRef::Struct(_)
+                | ReflectRef::Set(_)
+                | ReflectRef::Scalar(_)
+                | ReflectRef::Opaque(_) => None,
             }
         } else if let Some(key) = key.as_any().downcast_ref::<String>() {
             match self.reflect_ref() {
@@ -104,6 +109,7 @@ where
                 | ReflectRef::Enum(_)
                 | ReflectRef::List(_)
                 | ReflectRef::Array(_)
+                | ReflectRef::Set(_)
                 | ReflectRef::Opaque(_)
                 | ReflectRef::Scalar(_) => None,
             }
@@ -116,6 +122,7 @@ where
                 | ReflectRef::Array(_)
                 | ReflectRef::List(_)
                 | ReflectRef::Struct(_)
+                | ReflectRef::Set(_)
                 | ReflectRef::Opaque(_)
                 | ReflectRef::Scalar(_) => None,
             }
@@ -139,7 +146,10 @@ where
                 ReflectMut::List(inner) => inner.get_field_mut(key),
                 ReflectMut::Array(inner) => inner.get_field_mut(key),
                 ReflectMut::Map(inner) => inner.get_field_mut(key),
-                ReflectMut::Struct(_) | ReflectMut::Scalar(_) | ReflectMut::Opaque(_) => None,
+                ReflectMut::Struct(_)
+                | ReflectMut::Set(_)
+                | ReflectMut::Scalar(_)
+                | ReflectMut::Opaque(_) => None,
             }
         } else if let Some(key) = key.as_any().downcast_ref::<String>() {
             match self.reflect_mut() {
@@ -150,6 +160,7 @@ where
                 | ReflectMut::Enum(_)
                 | ReflectMut::List(_)
                 | ReflectMut::Array(_)
+                | ReflectMut::Set(_)
                 | ReflectMut::Opaque(_)
                 | ReflectMut::Scalar(_) => None,
             }
@@ -162,6 +173,7 @@ where
                 | ReflectMut::List(_)
                 | ReflectMut::Array(_)
                 | ReflectMut::Struct(_)
+                | ReflectMut::Set(_)
                 | ReflectMut::Opaque(_)
                 | ReflectMut::Scalar(_) => None,
             }
@@ -367,8 +379,7 @@ where
 mod private {
     #![allow(missing_debug_implementations)]
 
-    /// Types used to disambiguate otherwise overlapping trait impls
-
+    // Types used to disambiguate otherwise overlapping trait impls
     pub struct Struct;
     pub struct TupleStruct;
     pub struct Enum;
@@ -0,0 +1,151 @@
+use alloc::boxed::Box;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::Reflect;
+
+/// Shared access to a reflected value that might be behind a lock.
+///
+/// Returned by [`Reflect::reflect_guard`]. Dereferences to the value and keeps it borrowed or
+/// locked until the guard is dropped.
+///
+/// `RefCell<T>`, `Mutex<T>`, and the other interior mutability types are described as `T` in the
+/// type graph and are transparent when accessed mutably, since `&mut` access doesn't need a lock.
+/// Shared access through [`Reflect::reflect_ref`] cannot reach through the lock however, as the
+/// returned references would outlive it, so they're reflected as opaque values which must be
+/// locked with [`Reflect::reflect_guard`] to look inside. [`GetPath::with_at`] and
+/// [`GetPath::query_with`] lock the values along a key path as they go.
+///
+/// `reflect_guard` doesn't wait for the lock, since it might be held by the caller. If the value
+/// is already locked, or mutably borrowed in the case of `RefCell`, the guard contains the
+/// wrapper itself which remains opaque. [`Reflect::to_value`] and [`Reflect::clone_reflect`] can't
+/// fail so they do wait for the lock, and panic for a mutably borrowed `RefCell`.
+///
+/// Paths and OS strings that aren't valid UTF-8 are reflected the same way, except their guards
+/// own a copy of the value converted to a `String`.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Mutex;
+/// use mirror_mirror::{Reflect, key_path, key_path::GetPath};
+///
+/// #[derive(Reflect, Debug)]
+/// #[reflect(opt_out(Clone))]
+/// struct Player {
+///     stats: Mutex<Stats>,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Stats {
+///     health: f32,
+/// }
+///
+/// let mut player = Player {
+///     stats: Mutex::new(Stats { health: 1.0 }),
+/// };
+///
+/// // shared lookups stop at the lock
+/// assert!(player.get_at::<f32>(&key_path!(.stats.health)).is_none());
+///
+/// // so lock it and look up the rest of the path in the guard
+/// let stats = player.at(&key_path!(.stats)).unwrap().reflect_guard();
+/// assert_eq!(stats.get_at::<f32>(&key_path!(.health)), Some(&1.0));
+/// drop(stats);
+///
+/// // or have the locks along the path taken for you
+/// let health = player.with_at(&key_path!(.stats.health), |health| health.to_value());
+/// assert_eq!(health, Some(1.0_f32.to_value()));
+///
+/// // mutable lookups go straight through
+/// *player.get_at_mut::<f32>(&key_path!(.stats.health)).unwrap() = 0.5;
+/// assert_eq!(player.stats.lock().unwrap().health, 0.5);
+/// ```
+///
+/// [`GetPath::with_at`]: crate::key_path::GetPath::with_at
+/// [`GetPath::query_with`]: crate::key_path::GetPath::query_with
+pub struct ReflectGuard<'a>(Inner<'a>);
+
+enum Inner<'a> {
+    Borrowed(&'a dyn Reflect),
+    Guarded(Box<dyn Deref<Target = dyn Reflect> + 'a>),
+}
+
+impl<'a> ReflectGuard<'a> {
+    /// Create a guard for a value that isn't behind a lock.
+    pub fn new(value: &'a dyn Reflect) -> Self {
+        Self(Inner::Borrowed(value))
+    }
+
+    /// Create a guard from a lock guard, such as a [`Ref`] or [`MutexGuard`], that dereferences
+    /// to a reflected value.
+    ///
+    /// [`Ref`]: core::cell::Ref
+    /// [`MutexGuard`]: std::sync::MutexGuard
+    pub fn from_guard<G, T>(guard: G) -> Self
+    where
+        G: Deref<Target = T> + 'a,
+        T: Reflect,
+    {
+        Self(Inner::Guarded(Box::new(Mapped(guard))))
+    }
+}
+
+impl<'a> Deref for ReflectGuard<'a> {
+    type Target = dyn Reflect;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            Inner::Borrowed(value) => *value,
+            Inner::Guarded(guard) => &**guard,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ReflectGuard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).debug(f)
+    }
+}
+
+/// Call `f` with guards for `a` and `b`.
+///
+/// If `a` and `b` are the same value it's only locked once, since a value that's already locked
+/// cannot be locked again.
+pub(crate) fn with_guards<R>(
+    a: &dyn Reflect,
+    b: &dyn Reflect,
+    f: impl FnOnce(&dyn Reflect, &dyn Reflect) -> R,
+) -> R {
+    let a_guard = a.reflect_guard();
+    if is_same_value(a, b) {
+        f(&*a_guard, &*a_guard)
+    } else {
+        let b_guard = b.reflect_guard();
+        f(&*a_guard, &*b_guard)
+    }
+}
+
+/// Returns `true` if `a` and `b` are references to the same value.
+///
+/// Comparing addresses isn't enough since a struct and its first field can have the same address.
+fn is_same_value(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    core::ptr::eq(
+        a as *const dyn Reflect as *const (),
+        b as *const dyn Reflect as *const (),
+    ) && Reflect::type_id(a) == Reflect::type_id(b)
+}
+
+struct Mapped<G>(G);
+
+impl<G, T> Deref for Mapped<G>
+where
+    G: Deref<Target = T>,
+    T: Reflect,
+{
+    type Target = dyn Reflect;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_reflect()
+    }
+}
@@ -0,0 +1,170 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::fmt;
+
+use super::Key;
+use super::KeyPath;
+use crate::type_info::Type;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::Value;
+
+/// The error returned by [`GetPath::try_at`](super::GetPath::try_at) and
+/// [`GetPath::try_at_mut`](super::GetPath::try_at_mut).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyPathError {
+    resolved: KeyPath,
+    key: Key,
+    type_name: String,
+    kind: KeyPathErrorKind,
+}
+
+impl KeyPathError {
+    pub(super) fn new(
+        key_path: &KeyPath,
+        index: usize,
+        value: &dyn Reflect,
+        mut kind: KeyPathErrorKind,
+    ) -> Self {
+        let key = key_path.path[index].clone();
+
+        // maps and sets don't tell us whether a lookup failed because the key was missing or
+        // because it had the wrong type, so check the key type for those with scalar keys
+        if let (
+            KeyPathErrorKind::NoSuchKey,
+            Key::Get(key),
+            ReflectRef::Map(_) | ReflectRef::Set(_),
+        ) = (&kind, &key, value.reflect_ref())
+        {
+            if !is_valid_map_key(value, key) {
+                kind = KeyPathErrorKind::KeyNotConvertible;
+            }
+        }
+
+        Self {
+            resolved: key_path.path[..index].iter().cloned().collect(),
+            key,
+            type_name: value.type_name().to_owned(),
+            kind,
+        }
+    }
+
+    /// The longest prefix of the key path that could be resolved.
+    pub fn resolved(&self) -> &KeyPath {
+        &self.resolved
+    }
+
+    /// The key that couldn't be resolved.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// The type name of the value at [`resolved`](Self::resolved).
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Why the key couldn't be resolved.
+    pub fn kind(&self) -> &KeyPathErrorKind {
+        &self.kind
+    }
+}
+
+fn is_valid_map_key(map: &dyn Reflect, key: &Value) -> bool {
+    let type_descriptor = map.type_descriptor();
+    let key_type = match type_descriptor.get_type() {
+        Type::Map(map_type) => map_type.key_type(),
+        Type::Set(set_type) => set_type.element_type(),
+        _ => return true,
+    };
+    match key_type.as_scalar() {
+        Some(scalar_type) => key.as_reflect().type_descriptor().as_scalar() == Some(scalar_type),
+        None => true,
+    }
+}
+
+impl fmt::Display for KeyPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            resolved,
+            key,
+            type_name,
+            kind,
+        } = self;
+
+        if resolved.is_empty() {
+            write!(f, "cannot access `{key}`: ")?;
+        } else {
+            write!(f, "cannot access `{key}` at `{resolved}`: ")?;
+        }
+
+        match kind {
+            KeyPathErrorKind::NoSuchField => write!(f, "no such field in `{type_name}`"),
+            KeyPathErrorKind::IndexOutOfBounds { len } => {
+                write!(f, "index out of bounds for `{type_name}` of length {len}")
+            }
+            KeyPathErrorKind::NoSuchKey => write!(f, "no such key in `{type_name}`"),
+            KeyPathErrorKind::KeyNotConvertible => {
+                write!(f, "key has the wrong type for `{type_name}`")
+            }
+            KeyPathErrorKind::VariantMismatch { actual } => {
+                write!(f, "`{type_name}` is the variant `{actual}`")
+            }
+            KeyPathErrorKind::Opaque => write!(f, "`{type_name}` is opaque"),
+            KeyPathErrorKind::Immutable => {
+                write!(f, "elements of `{type_name}` cannot be mutated")
+            }
+            KeyPathErrorKind::Locked => {
+                write!(
+                    f,
+                    "`{type_name}` is locked, use `GetPath::with_at` to look inside"
+                )
+            }
+            KeyPathErrorKind::UnsupportedKey => match key {
+                Key::Field(_) => write!(f, "`{type_name}` doesn't have fields"),
+                Key::Get(_) => write!(f, "`{type_name}` isn't a list, array, map, or set"),
+                Key::Variant(_) => write!(f, "`{type_name}` isn't an enum"),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyPathError {}
+
+/// Why a [`Key`] couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyPathErrorKind {
+    /// The struct, tuple, or enum variant doesn't have the field.
+    NoSuchField,
+    /// The index is out of bounds for the list or array.
+    IndexOutOfBounds {
+        /// The length of the list or array.
+        len: usize,
+    },
+    /// The map doesn't contain the key.
+    NoSuchKey,
+    /// The key cannot be converted into the key type of the map, or into an index for lists and
+    /// arrays.
+    KeyNotConvertible,
+    /// The enum is a different variant.
+    VariantMismatch {
+        /// The name of the current variant.
+        actual: String,
+    },
+    /// The value is opaque and cannot be looked into.
+    Opaque,
+    /// The value is an element of a set, or a field of a type such as `glam::Quat` that can only
+    /// be patched as a whole, which cannot be mutated in place.
+    Immutable,
+    /// The value is behind a `RefCell`, `Mutex`, or other lock which shared lookups cannot reach
+    /// through. Use [`GetPath::with_at`] or [`GetPath::query_with`] which lock each value along
+    /// the way, or use a mutable lookup.
+    ///
+    /// [`GetPath::with_at`]: super::GetPath::with_at
+    /// [`GetPath::query_with`]: super::GetPath::query_with
+    Locked,
+    /// The value doesn't support this kind of key, such as accessing fields on a list.
+    UnsupportedKey,
+}
@@ -0,0 +1,872 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::str::FromStr;
+
+use crate::enum_::VariantKind;
+use crate::type_info::Type;
+use crate::type_info::TypeAtPath;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectRef;
+use crate::Value;
+
+mod error;
+mod parse;
+mod query;
+
+pub use self::error::KeyPathError;
+pub use self::error::KeyPathErrorKind;
+
+pub use self::parse::parse;
+pub(crate) use self::parse::parse_literal;
+pub(crate) use self::parse::Cursor;
+pub use self::parse::ParseError;
+pub use self::parse::ParseErrorKind;
+pub use self::query::parse_query;
+pub use self::query::Matches;
+pub use self::query::Query;
+pub use self::query::QueryKey;
+
+/// Look up values in reflected values with [`KeyPath`]s.
+///
+/// Implemented for every [`Reflect`] type. Other types only have to implement
+/// [`at`](GetPath::at) and [`at_mut`](GetPath::at_mut). The other methods default to resolving the
+/// empty key path with those and going from there, and panic if the empty key path doesn't
+/// resolve.
+pub trait GetPath {
+    fn at(&self, key_path: &KeyPath) -> Option<&dyn Reflect>;
+
+    fn get_at<T>(&self, key_path: &KeyPath) -> Option<&T>
+    where
+        T: Reflect,
+    {
+        self.at(key_path)?.downcast_ref()
+    }
+
+    fn at_mut(&mut self, key_path: &KeyPath) -> Option<&mut dyn Reflect>;
+
+    fn get_at_mut<T>(&mut self, key_path: &KeyPath) -> Option<&mut T>
+    where
+        T: Reflect,
+    {
+        self.at_mut(key_path)?.downcast_mut()
+    }
+
+    /// Like [`at`](GetPath::at) but returns an error explaining why the key path couldn't be
+    /// resolved.
+    ///
+    /// ```
+    /// use mirror_mirror::{key_path, key_path::{GetPath, KeyPathErrorKind}, Reflect};
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Player {
+    ///     items: Vec<Item>,
+    /// }
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// let player = Player { items: Vec::new() };
+    ///
+    /// let err = player.try_at(&key_path!(.items[0].name)).unwrap_err();
+    /// assert_eq!(err.resolved(), &key_path!(.items));
+    /// assert_eq!(err.kind(), &KeyPathErrorKind::IndexOutOfBounds { len: 0 });
+    /// ```
+    fn try_at(&self, key_path: &KeyPath) -> Result<&dyn Reflect, KeyPathError> {
+        root(self).try_at(key_path)
+    }
+
+    /// Like [`at_mut`](GetPath::at_mut) but returns an error explaining why the key path couldn't
+    /// be resolved.
+    fn try_at_mut(&mut self, key_path: &KeyPath) -> Result<&mut dyn Reflect, KeyPathError> {
+        root_mut(self).try_at_mut(key_path)
+    }
+
+    /// Find all the values that match a [`Query`] such as `.items[*].name`.
+    fn query<'a>(&'a self, query: &'a Query) -> Matches<'a> {
+        query.matches(root(self))
+    }
+
+    /// Call `f` with every value that matches a [`Query`] such as `.**.health`.
+    ///
+    /// Since a query can match both a value and values nested inside it, the matches are visited
+    /// with a callback rather than returned as an iterator. Matches are visited in pre-order so
+    /// `f` is called for a value before the values nested inside it, and changes made by `f` are
+    /// visible when the nested values are matched.
+    fn query_mut<F>(&mut self, query: &Query, f: F)
+    where
+        F: FnMut(&KeyPath, &mut dyn Reflect),
+    {
+        query.for_each_mut(root_mut(self), f)
+    }
+
+    /// Call `f` with the value at `key_path`, reaching through `RefCell`s, `Mutex`es, and other
+    /// locks along the way.
+    ///
+    /// Each lock is held with [`Reflect::reflect_guard`] until `f` returns. If the value at
+    /// `key_path` is itself behind a lock then `f` is called with the value inside. Returns `None`
+    /// if the key path doesn't resolve, including when a lock along the way is already held.
+    fn with_at<F, T>(&self, key_path: &KeyPath, f: F) -> Option<T>
+    where
+        F: FnOnce(&dyn Reflect) -> T,
+    {
+        root(self).with_at(key_path, f)
+    }
+
+    /// Call `f` with every value that matches a [`Query`], reaching through locks the same way as
+    /// [`with_at`](GetPath::with_at).
+    ///
+    /// Matches are visited in pre-order, like [`query_mut`](GetPath::query_mut).
+    fn query_with<F>(&self, query: &Query, f: F)
+    where
+        F: FnMut(&KeyPath, &dyn Reflect),
+    {
+        query.for_each_with(root(self), f)
+    }
+}
+
+/// The value at the empty key path, used by the default methods of [`GetPath`].
+fn root<P>(value: &P) -> &dyn Reflect
+where
+    P: GetPath + ?Sized,
+{
+    value
+        .at(&KeyPath::default())
+        .expect("`GetPath::at` doesn't resolve the empty key path")
+}
+
+fn root_mut<P>(value: &mut P) -> &mut dyn Reflect
+where
+    P: GetPath + ?Sized,
+{
+    value
+        .at_mut(&KeyPath::default())
+        .expect("`GetPath::at_mut` doesn't resolve the empty key path")
+}
+
+pub trait GetTypePath<'a> {
+    fn type_at(self, key_path: &KeyPath) -> Option<TypeAtPath<'a>>;
+}
+
+impl<R> GetPath for R
+where
+    R: Reflect + ?Sized,
+{
+    fn at(&self, key_path: &KeyPath) -> Option<&dyn Reflect> {
+        key_path.iter().try_fold(self.as_reflect(), value_at_key)
+    }
+
+    fn at_mut(&mut self, key_path: &KeyPath) -> Option<&mut dyn Reflect> {
+        key_path
+            .iter()
+            .try_fold(self.as_reflect_mut(), |value, key| {
+                value_at_key_mut(value, key)
+            })
+    }
+
+    fn try_at(&self, key_path: &KeyPath) -> Result<&dyn Reflect, KeyPathError> {
+        let mut value = self.as_reflect();
+        for (index, key) in key_path.iter().enumerate() {
+            value = try_value_at_key(value, key)
+                .map_err(|kind| KeyPathError::new(key_path, index, value, kind))?;
+        }
+        Ok(value)
+    }
+
+    fn try_at_mut(&mut self, key_path: &KeyPath) -> Result<&mut dyn Reflect, KeyPathError> {
+        // the key path is resolved twice on success since returning the reference from one
+        // branch and inspecting `self` in the other isn't accepted by the borrow checker
+        if self.at_mut(key_path).is_none() {
+            // the error is found using a shared reference since a value cannot be inspected
+            // after a failed lookup has consumed the mutable reference
+            return Err(mutable_lookup_error(self.as_reflect(), key_path, 0));
+        }
+        Ok(self.at_mut(key_path).unwrap())
+    }
+
+    fn query<'a>(&'a self, query: &'a Query) -> Matches<'a> {
+        query.matches(self.as_reflect())
+    }
+
+    fn query_mut<F>(&mut self, query: &Query, f: F)
+    where
+        F: FnMut(&KeyPath, &mut dyn Reflect),
+    {
+        query.for_each_mut(self.as_reflect_mut(), f)
+    }
+
+    fn with_at<F, T>(&self, key_path: &KeyPath, f: F) -> Option<T>
+    where
+        F: FnOnce(&dyn Reflect) -> T,
+    {
+        with_value_at(self.as_reflect(), &key_path.path, f)
+    }
+
+    fn query_with<F>(&self, query: &Query, f: F)
+    where
+        F: FnMut(&KeyPath, &dyn Reflect),
+    {
+        query.for_each_with(self.as_reflect(), f)
+    }
+}
+
+fn with_value_at<F, T>(value: &dyn Reflect, keys: &[Key], f: F) -> Option<T>
+where
+    F: FnOnce(&dyn Reflect) -> T,
+{
+    let guard = value.reflect_guard();
+    match keys.split_first() {
+        Some((key, rest)) => with_value_at(value_at_key(&*guard, key)?, rest, f),
+        None => Some(f(&*guard)),
+    }
+}
+
+/// Find why `at_mut` failed to resolve `key_path`, starting at the key at `index`.
+///
+/// Mutable lookups reach through `RefCell`s and other locks so they're locked here to look inside.
+fn mutable_lookup_error(value: &dyn Reflect, key_path: &KeyPath, index: usize) -> KeyPathError {
+    find_mutable_lookup_error(value, key_path, index)
+        .expect("`mutable_lookup_error` called with a key path that resolves")
+}
+
+fn find_mutable_lookup_error(
+    value: &dyn Reflect,
+    key_path: &KeyPath,
+    index: usize,
+) -> Option<KeyPathError> {
+    let key = key_path.path.get(index)?;
+    let guard = value.reflect_guard();
+    let guarded = &*guard;
+    match (try_value_at_key(guarded, key), guarded.reflect_ref()) {
+        // elements of sets can be accessed with shared references but not mutated
+        (Ok(_), ReflectRef::Set(_)) => Some(KeyPathError::new(
+            key_path,
+            index,
+            guarded,
+            KeyPathErrorKind::Immutable,
+        )),
+        // if the rest of the key path resolves then `value` is a type such as `glam::Quat` whose
+        // fields can be read through its guard but not borrowed mutably
+        (Ok(child), _) => find_mutable_lookup_error(child, key_path, index + 1).or_else(|| {
+            Some(KeyPathError::new(
+                key_path,
+                index,
+                value,
+                KeyPathErrorKind::Immutable,
+            ))
+        }),
+        (Err(kind), _) => Some(KeyPathError::new(key_path, index, guarded, kind)),
+    }
+}
+
+/// Get the value at a single key.
+pub(crate) fn value_at_key<'a>(value: &'a dyn Reflect, key: &Key) -> Option<&'a dyn Reflect> {
+    try_value_at_key(value, key).ok()
+}
+
+/// Get the value at a single key, or the reason it doesn't exist.
+fn try_value_at_key<'a>(
+    value: &'a dyn Reflect,
+    key: &Key,
+) -> Result<&'a dyn Reflect, KeyPathErrorKind> {
+    let child = match key {
+        // .foo
+        Key::Field(NamedOrNumbered::Named(key)) => match value.reflect_ref() {
+            ReflectRef::Struct(inner) => inner.field(key),
+            ReflectRef::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Struct => inner.field(key),
+                VariantKind::Tuple | VariantKind::Unit => None,
+            },
+            ReflectRef::TupleStruct(_) | ReflectRef::Tuple(_) => None,
+            ReflectRef::Array(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
+            | ReflectRef::Scalar(_) => return Err(KeyPathErrorKind::UnsupportedKey),
+            ReflectRef::Opaque(_) => return Err(opaque(value)),
+        }
+        .ok_or(KeyPathErrorKind::NoSuchField)?,
+        // .0
+        Key::Field(NamedOrNumbered::Numbered(index)) => match value.reflect_ref() {
+            ReflectRef::TupleStruct(inner) => inner.field_at(*index),
+            ReflectRef::Tuple(inner) => inner.field_at(*index),
+            ReflectRef::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Tuple => inner.field_at(*index),
+                VariantKind::Struct | VariantKind::Unit => None,
+            },
+            ReflectRef::Struct(_) => None,
+            ReflectRef::Map(_)
+            | ReflectRef::Set(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Scalar(_) => return Err(KeyPathErrorKind::UnsupportedKey),
+            ReflectRef::Opaque(_) => return Err(opaque(value)),
+        }
+        .ok_or(KeyPathErrorKind::NoSuchField)?,
+        // ["foo"] or [0]
+        Key::Get(key) => match value.reflect_ref() {
+            ReflectRef::Map(inner) => inner.get(key).ok_or(KeyPathErrorKind::NoSuchKey)?,
+            ReflectRef::Set(inner) => inner.get(key).ok_or(KeyPathErrorKind::NoSuchKey)?,
+            ReflectRef::Array(inner) => {
+                let index = value_to_usize(key).ok_or(KeyPathErrorKind::KeyNotConvertible)?;
+                inner
+                    .get(index)
+                    .ok_or(KeyPathErrorKind::IndexOutOfBounds { len: inner.len() })?
+            }
+            ReflectRef::List(inner) => {
+                let index = value_to_usize(key).ok_or(KeyPathErrorKind::KeyNotConvertible)?;
+                inner
+                    .get(index)
+                    .ok_or(KeyPathErrorKind::IndexOutOfBounds { len: inner.len() })?
+            }
+            ReflectRef::Struct(_)
+            | ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::Enum(_)
+            | ReflectRef::Scalar(_) => return Err(KeyPathErrorKind::UnsupportedKey),
+            ReflectRef::Opaque(_) => return Err(opaque(value)),
+        },
+        // ::Some
+        Key::Variant(variant) => match value.reflect_ref() {
+            ReflectRef::Enum(enum_) => {
+                if enum_.variant_name() == variant {
+                    enum_.as_reflect()
+                } else {
+                    return Err(KeyPathErrorKind::VariantMismatch {
+                        actual: enum_.variant_name().to_owned(),
+                    });
+                }
+            }
+            ReflectRef::Struct(_)
+            | ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
+            | ReflectRef::Scalar(_) => return Err(KeyPathErrorKind::UnsupportedKey),
+            ReflectRef::Opaque(_) => return Err(opaque(value)),
+        },
+    };
+
+    Ok(child)
+}
+
+// values behind locks are opaque when accessed through shared references but are described as the
+// type they contain. String-like types such as `Box<str>` are opaque as well but described as
+// `String` which doesn't have anything to look inside.
+fn opaque(value: &dyn Reflect) -> KeyPathErrorKind {
+    match value.type_descriptor().get_type() {
+        Type::Opaque(_) => KeyPathErrorKind::Opaque,
+        Type::Scalar(_) => KeyPathErrorKind::UnsupportedKey,
+        _ => KeyPathErrorKind::Locked,
+    }
+}
+
+/// Get the value at a single key, mutably.
+pub(crate) fn value_at_key_mut<'a>(
+    value: &'a mut dyn Reflect,
+    key: &Key,
+) -> Option<&'a mut dyn Reflect> {
+    let child = match key {
+        // .foo
+        Key::Field(NamedOrNumbered::Named(key)) => match value.reflect_mut() {
+            ReflectMut::Struct(inner) => inner.field_mut(key)?,
+            ReflectMut::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Struct => inner.field_mut(key)?,
+                VariantKind::Tuple | VariantKind::Unit => return None,
+            },
+            ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Map(_)
+            | ReflectMut::Set(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // .0
+        Key::Field(NamedOrNumbered::Numbered(index)) => match value.reflect_mut() {
+            ReflectMut::TupleStruct(inner) => inner.field_at_mut(*index)?,
+            ReflectMut::Tuple(inner) => inner.field_at_mut(*index)?,
+            ReflectMut::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Tuple => inner.field_at_mut(*index)?,
+                VariantKind::Struct | VariantKind::Unit => return None,
+            },
+            ReflectMut::Map(_)
+            | ReflectMut::Set(_)
+            | ReflectMut::Struct(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // ["foo"] or [0]
+        Key::Get(key) => match value.reflect_mut() {
+            ReflectMut::Array(inner) => inner.get_mut(value_to_usize(key)?)?,
+            ReflectMut::List(inner) => inner.get_mut(value_to_usize(key)?)?,
+            ReflectMut::Map(inner) => inner.get_mut(key)?,
+            // elements of sets cannot be mutated in place
+            ReflectMut::Set(_) => return None,
+            ReflectMut::Struct(_)
+            | ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::Enum(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // ::Some
+        Key::Variant(variant) => match value.reflect_mut() {
+            ReflectMut::Enum(enum_) => {
+                if enum_.variant_name() == variant {
+                    enum_.as_reflect_mut()
+                } else {
+                    return None;
+                }
+            }
+            ReflectMut::Struct(_)
+            | ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::Map(_)
+            | ReflectMut::Set(_)
+            | ReflectMut::Opaque(_)
+            | ReflectMut::Scalar(_) => return None,
+        },
+    };
+    Some(child)
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyPath {
+    pub(crate) path: Vec<Key>,
+}
+
+impl KeyPath {
+    pub fn field(mut self, field: impl IntoKeyOrIndex) -> Self {
+        self.push_field(field);
+        self
+    }
+
+    pub fn push_field(&mut self, field: impl IntoKeyOrIndex) {
+        self.push(Key::Field(field.into_key_or_index()));
+    }
+
+    pub fn get(mut self, field: impl Into<Value>) -> Self {
+        self.push_get(field);
+        self
+    }
+
+    pub fn push_get(&mut self, field: impl Into<Value>) {
+        self.push(Key::Get(field.into()))
+    }
+
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.push_variant(variant);
+        self
+    }
+
+    pub fn push_variant(&mut self, variant: impl Into<String>) {
+        self.push(Key::Variant(variant.into()));
+    }
+
+    pub fn push(&mut self, key: Key) {
+        self.path.push(key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn pop(&mut self) {
+        self.path.pop();
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.into_iter()
+    }
+
+    pub fn breadcrumbs(&self) -> Breadcrumbs<'_> {
+        Breadcrumbs {
+            key_path: self,
+            index: 1,
+        }
+    }
+}
+
+impl From<Key> for KeyPath {
+    fn from(key: Key) -> Self {
+        let mut key_path = KeyPath::default();
+        key_path.push(key);
+        key_path
+    }
+}
+
+impl Extend<Key> for KeyPath {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = Key>,
+    {
+        self.path.extend(iter);
+    }
+}
+
+impl IntoIterator for KeyPath {
+    type Item = Key;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.path.into_iter())
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter(alloc::vec::IntoIter<Key>);
+
+impl Iterator for IntoIter {
+    type Item = Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FusedIterator for IntoIter {}
+
+impl<'a> IntoIterator for &'a KeyPath {
+    type Item = &'a Key;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self.path.iter())
+    }
+}
+
+#[derive(Debug)]
+pub struct Iter<'a>(alloc::slice::Iter<'a, Key>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+mod private {
+    use super::*;
+
+    pub trait Sealed {}
+    impl Sealed for &str {}
+    impl Sealed for &String {}
+    impl Sealed for String {}
+    impl Sealed for usize {}
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Key {
+    Field(NamedOrNumbered),
+    Get(Value),
+    Variant(String),
+}
+
+impl Key {
+    /// Create a `Key` that'll access a named field of a struct.
+    pub fn named_field(name: impl Into<String>) -> Self {
+        Self::Field(NamedOrNumbered::Named(name.into()))
+    }
+
+    /// Create a `Key` that'll access a numbered field of a tuple struct or tuple.
+    pub fn numbered_field(index: usize) -> Self {
+        Self::Field(NamedOrNumbered::Numbered(index))
+    }
+
+    /// Create a `Key` that'll access an element in a list, array, or map.
+    pub fn get(value: impl Into<Value>) -> Self {
+        Self::Get(value.into())
+    }
+
+    /// Create a `Key` that'll access an enum variant.
+    pub fn variant(name: impl Into<String>) -> Self {
+        Self::Variant(name.into())
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Field(key) => write!(f, "{key}"),
+            Key::Get(value) => {
+                f.write_str("[")?;
+                fmt_literal(value, f)?;
+                f.write_str("]")
+            }
+            Key::Variant(variant) => write!(f, "::{variant}"),
+        }
+    }
+}
+
+/// Write `value` as a Rust literal that [`parse`] understands.
+///
+/// Numbers other than `i32` and `f64` get a type suffix so they round trip. Values that aren't
+/// scalars are written using their `Debug` output and cannot be parsed.
+fn fmt_literal(value: &Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match value {
+        Value::usize(n) => write!(f, "{n}_usize"),
+        Value::u8(n) => write!(f, "{n}_u8"),
+        Value::u16(n) => write!(f, "{n}_u16"),
+        Value::u32(n) => write!(f, "{n}_u32"),
+        Value::u64(n) => write!(f, "{n}_u64"),
+        Value::u128(n) => write!(f, "{n}_u128"),
+        Value::i8(n) => write!(f, "{n}_i8"),
+        Value::i16(n) => write!(f, "{n}_i16"),
+        Value::i32(n) => write!(f, "{n}"),
+        Value::i64(n) => write!(f, "{n}_i64"),
+        Value::i128(n) => write!(f, "{n}_i128"),
+        Value::f32(n) => write!(f, "{n:?}_f32"),
+        Value::f64(n) => write!(f, "{n:?}"),
+        Value::bool(b) => write!(f, "{b}"),
+        Value::char(c) => write!(f, "{c:?}"),
+        Value::String(s) => write!(f, "{s:?}"),
+        Value::StructValue(_)
+        | Value::EnumValue(_)
+        | Value::TupleStructValue(_)
+        | Value::TupleValue(_)
+        | Value::List(_)
+        | Value::Map(_)
+        | Value::Set(_) => write!(f, "{:?}", value.as_reflect()),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NamedOrNumbered {
+    Named(String),
+    Numbered(usize),
+}
+
+impl fmt::Display for NamedOrNumbered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamedOrNumbered::Named(field) => write!(f, ".{field}"),
+            NamedOrNumbered::Numbered(index) => write!(f, ".{index}"),
+        }
+    }
+}
+
+pub trait IntoKeyOrIndex: private::Sealed {
+    fn into_key_or_index(self) -> NamedOrNumbered;
+}
+
+impl IntoKeyOrIndex for &str {
+    fn into_key_or_index(self) -> NamedOrNumbered {
+        NamedOrNumbered::Named(self.to_owned())
+    }
+}
+
+impl IntoKeyOrIndex for &String {
+    fn into_key_or_index(self) -> NamedOrNumbered {
+        NamedOrNumbered::Named(self.to_owned())
+    }
+}
+
+impl IntoKeyOrIndex for String {
+    fn into_key_or_index(self) -> NamedOrNumbered {
+        NamedOrNumbered::Named(self)
+    }
+}
+
+impl IntoKeyOrIndex for usize {
+    fn into_key_or_index(self) -> NamedOrNumbered {
+        NamedOrNumbered::Numbered(self)
+    }
+}
+
+pub fn field(field: impl IntoKeyOrIndex) -> KeyPath {
+    KeyPath::default().field(field)
+}
+
+pub fn get(field: impl Into<Value>) -> KeyPath {
+    KeyPath::default().get(field)
+}
+
+pub fn variant(variant: impl Into<String>) -> KeyPath {
+    KeyPath::default().variant(variant)
+}
+
+/// Convenience macro for creating [`KeyPath`]s.
+///
+/// Expands to calls to methods on [`KeyPath`]. Named fields are written as `.foo`, numbered
+/// fields as `.0`, variants as `::Some`, and lookups in lists, arrays, and maps as `[expr]` where
+/// `expr` can be any expression that converts into a [`Value`].
+///
+/// ```
+/// use mirror_mirror::{key_path, key_path::KeyPath};
+///
+/// let index = 3;
+///
+/// assert_eq!(
+///     key_path!(.foo[index]::Some.0.1),
+///     KeyPath::default().field("foo").get(index).variant("Some").field(0).field(1),
+/// );
+/// ```
+///
+/// # Typed key paths
+///
+/// Prefixing the path with a type, as in `key_path!(MyType, .foo)`, checks that the path exists
+/// in the type's [`TypeDescriptor`](crate::type_info::TypeDescriptor). The check happens when the
+/// expression is evaluated so a typo is caught the first time the code runs, for example in a
+/// test.
+///
+/// ```
+/// use mirror_mirror::{key_path, Reflect};
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Item {
+///     name: Option<String>,
+/// }
+///
+/// let path = key_path!(Player, .items[0].name::Some.0);
+/// ```
+///
+/// ```should_panic
+/// # use mirror_mirror::{key_path, Reflect};
+/// # #[derive(Reflect, Clone, Debug)]
+/// # struct Player {
+/// #     items: Vec<Item>,
+/// # }
+/// # #[derive(Reflect, Clone, Debug)]
+/// # struct Item {
+/// #     name: Option<String>,
+/// # }
+/// // panics since `Item` doesn't have a field called `nmae`
+/// let path = key_path!(Player, .items[0].nmae);
+/// ```
+///
+/// # Panics
+///
+/// Typed key paths panic if the path doesn't exist in the type.
+#[macro_export]
+macro_rules! key_path {
+    ( $($tt:tt)* ) => {
+        $crate::__private_key_path!($crate; $($tt)*)
+    };
+}
+
+impl FromStr for KeyPath {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.path {
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn value_to_usize(value: &Value) -> Option<usize> {
+    match value {
+        Value::usize(n) => Some(*n),
+        Value::u8(n) => Some(*n as usize),
+        Value::u16(n) => Some(*n as usize),
+        Value::u32(n) => Some(*n as usize),
+        Value::u64(n) => Some(*n as usize),
+        Value::u128(n) => Some(*n as usize),
+        Value::i8(n) => Some(*n as usize),
+        Value::i16(n) => Some(*n as usize),
+        Value::i32(n) => Some(*n as usize),
+        Value::i64(n) => Some(*n as usize),
+        Value::i128(n) => Some(*n as usize),
+        Value::bool(_)
+        | Value::char(_)
+        | Value::f32(_)
+        | Value::f64(_)
+        | Value::String(_)
+        | Value::StructValue(_)
+        | Value::EnumValue(_)
+        | Value::TupleStructValue(_)
+        | Value::TupleValue(_)
+        | Value::List(_)
+        | Value::Map(_)
+        | Value::Set(_) => None,
+    }
+}
+
+impl FromIterator<Key> for KeyPath {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Key>,
+    {
+        Self {
+            path: Vec::from_iter(iter),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Breadcrumbs<'a> {
+    key_path: &'a KeyPath,
+    index: usize,
+}
+
+impl<'a> Iterator for Breadcrumbs<'a> {
+    type Item = &'a [Key];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let keys = self.key_path.path.get(0..self.index)?;
+        self.index += 1;
+        Some(keys)
+    }
+}
@@ -0,0 +1,414 @@
+use alloc::string::String;
+use core::fmt;
+
+use super::Key;
+use super::KeyPath;
+use crate::Value;
+
+/// Parse a [`KeyPath`] from the format produced by its `Display` implementation.
+///
+/// Supports every kind of [`Key`]:
+///
+/// - Named fields such as `.foo`.
+/// - Numbered fields such as `.0`.
+/// - Variants such as `::Some`.
+/// - Lookups in lists, arrays, and maps such as `[1]`, `[2_usize]`, `["foo"]`, or `['a']`.
+///   Integers without a suffix are `i32` and floats without a suffix are `f64`, like in Rust.
+///
+/// Lookups with keys other than scalars, such as structs, aren't supported.
+///
+/// `KeyPath` also implements [`FromStr`](core::str::FromStr) which calls this function.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::key_path::{self, KeyPath};
+///
+/// let path = key_path::parse(r#".foo[1]["bar"]::Some.0"#).unwrap();
+///
+/// assert_eq!(
+///     path,
+///     KeyPath::default().field("foo").get(1).get("bar").variant("Some").field(0),
+/// );
+///
+/// let err = key_path::parse(".foo[1").unwrap_err();
+/// assert_eq!(err.column(), 7);
+/// assert_eq!(err.to_string(), "expected `]` at column 7");
+/// ```
+pub fn parse(input: &str) -> Result<KeyPath, ParseError> {
+    let mut cursor = Cursor::new(input);
+    let mut path = KeyPath::default();
+    while !cursor.is_at_end() {
+        path.push(parse_key(&mut cursor)?);
+    }
+    Ok(path)
+}
+
+pub(super) fn parse_key(cursor: &mut Cursor<'_>) -> Result<Key, ParseError> {
+    if cursor.eat("::") {
+        let name = cursor
+            .ident()
+            .ok_or_else(|| cursor.error(ParseErrorKind::ExpectedVariant))?;
+        Ok(Key::variant(name))
+    } else if cursor.eat(".") {
+        if let Some(index) = cursor.digits() {
+            let index = index
+                .parse()
+                .map_err(|_| cursor.error(ParseErrorKind::InvalidIndex))?;
+            Ok(Key::numbered_field(index))
+        } else {
+            let name = cursor
+                .ident()
+                .ok_or_else(|| cursor.error(ParseErrorKind::ExpectedField))?;
+            Ok(Key::named_field(name))
+        }
+    } else if cursor.eat("[") {
+        cursor.skip_whitespace();
+        let value = parse_literal(cursor)?;
+        cursor.skip_whitespace();
+        cursor.expect("]", ParseErrorKind::ExpectedCloseBracket)?;
+        Ok(Key::Get(value))
+    } else {
+        Err(cursor.error(ParseErrorKind::ExpectedKey))
+    }
+}
+
+/// Parse a scalar literal such as `1`, `1.5_f32`, `true`, `'a'`, or `"foo"`.
+pub(crate) fn parse_literal(cursor: &mut Cursor<'_>) -> Result<Value, ParseError> {
+    match cursor.peek() {
+        Some('"') => parse_string(cursor).map(Value::String),
+        Some('\'') => parse_char(cursor).map(Value::char),
+        Some(c) if c.is_ascii_digit() || c == '-' || c == 'N' || c == 'i' => parse_number(cursor),
+        Some(_) => {
+            let start = cursor.position();
+            match cursor.ident() {
+                Some("true") => Ok(Value::bool(true)),
+                Some("false") => Ok(Value::bool(false)),
+                Some(_) | None => Err(cursor.error_at(start, ParseErrorKind::ExpectedLiteral)),
+            }
+        }
+        None => Err(cursor.error(ParseErrorKind::ExpectedLiteral)),
+    }
+}
+
+fn parse_string(cursor: &mut Cursor<'_>) -> Result<String, ParseError> {
+    cursor.expect("\"", ParseErrorKind::ExpectedLiteral)?;
+    let mut string = String::new();
+    loop {
+        match cursor.peek() {
+            Some('"') => {
+                cursor.bump();
+                return Ok(string);
+            }
+            Some('\\') => string.push(parse_escape(cursor)?),
+            Some(c) => {
+                cursor.bump();
+                string.push(c);
+            }
+            None => return Err(cursor.error(ParseErrorKind::UnterminatedString)),
+        }
+    }
+}
+
+fn parse_char(cursor: &mut Cursor<'_>) -> Result<char, ParseError> {
+    let start = cursor.position();
+    cursor.expect("'", ParseErrorKind::ExpectedLiteral)?;
+    let c = match cursor.peek() {
+        Some('\\') => parse_escape(cursor)?,
+        Some('\'') | None => return Err(cursor.error_at(start, ParseErrorKind::InvalidChar)),
+        Some(c) => {
+            cursor.bump();
+            c
+        }
+    };
+    cursor.expect("'", ParseErrorKind::InvalidChar)?;
+    Ok(c)
+}
+
+fn parse_escape(cursor: &mut Cursor<'_>) -> Result<char, ParseError> {
+    let start = cursor.position();
+    cursor.expect("\\", ParseErrorKind::InvalidEscape)?;
+    let escaped = match cursor.bump() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('0') => '\0',
+        Some('\\') => '\\',
+        Some('\'') => '\'',
+        Some('"') => '"',
+        Some('u') => {
+            let digits = cursor
+                .eat("{")
+                .then(|| cursor.take_while(|c| c.is_ascii_hexdigit()))
+                .filter(|_| cursor.eat("}"));
+            digits
+                .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| cursor.error_at(start, ParseErrorKind::InvalidEscape))?
+        }
+        Some(_) | None => return Err(cursor.error_at(start, ParseErrorKind::InvalidEscape)),
+    };
+    Ok(escaped)
+}
+
+fn parse_number(cursor: &mut Cursor<'_>) -> Result<Value, ParseError> {
+    let start = cursor.position();
+
+    let mut number = String::new();
+    if cursor.eat("-") {
+        number.push('-');
+    }
+
+    let mut is_float = false;
+    if cursor.eat("NaN") {
+        number.push_str("NaN");
+        is_float = true;
+    } else if cursor.eat("inf") {
+        number.push_str("inf");
+        is_float = true;
+    } else {
+        if !push_digits(cursor, &mut number) {
+            return Err(cursor.error_at(start, ParseErrorKind::ExpectedLiteral));
+        }
+        // `1.foo` isn't a float but `1.0` is
+        if cursor.peek() == Some('.') && cursor.peek_nth(1).map_or(false, |c| c.is_ascii_digit()) {
+            cursor.bump();
+            number.push('.');
+            push_digits(cursor, &mut number);
+            is_float = true;
+        }
+        if matches!(cursor.peek(), Some('e' | 'E')) {
+            cursor.bump();
+            number.push('e');
+            if let Some(sign @ ('-' | '+')) = cursor.peek() {
+                cursor.bump();
+                number.push(sign);
+            }
+            if !push_digits(cursor, &mut number) {
+                return Err(cursor.error_at(start, ParseErrorKind::InvalidNumber));
+            }
+            is_float = true;
+        }
+    }
+
+    let suffix_start = cursor.position();
+    cursor.eat("_");
+    let suffix = cursor.ident().unwrap_or_default();
+
+    macro_rules! parse_as {
+        ($($ty:ident)*) => {
+            match suffix {
+                $(
+                    stringify!($ty) => number
+                        .parse::<$ty>()
+                        .map(Value::$ty)
+                        .map_err(|_| cursor.error_at(start, ParseErrorKind::InvalidNumber)),
+                )*
+                "" if is_float => number
+                    .parse::<f64>()
+                    .map(Value::f64)
+                    .map_err(|_| cursor.error_at(start, ParseErrorKind::InvalidNumber)),
+                "" => number
+                    .parse::<i32>()
+                    .map(Value::i32)
+                    .map_err(|_| cursor.error_at(start, ParseErrorKind::InvalidNumber)),
+                _ => Err(cursor.error_at(suffix_start, ParseErrorKind::InvalidSuffix)),
+            }
+        };
+    }
+
+    if is_float && !matches!(suffix, "" | "f32" | "f64") {
+        return Err(cursor.error_at(suffix_start, ParseErrorKind::InvalidSuffix));
+    }
+
+    parse_as! {
+        usize u8 u16 u32 u64 u128
+        i8 i16 i32 i64 i128
+        f32 f64
+    }
+}
+
+/// Push digits, skipping `_` separators, and return whether there were any.
+fn push_digits(cursor: &mut Cursor<'_>, number: &mut String) -> bool {
+    let mut any = false;
+    while let Some(c) = cursor.peek() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            any = true;
+        } else if c == '_' && any && cursor.peek_nth(1).map_or(false, |c| c.is_ascii_digit()) {
+            // separator, as opposed to the start of a suffix like `_u8`
+        } else {
+            break;
+        }
+        cursor.bump();
+    }
+    any
+}
+
+/// A position in the input being parsed.
+#[derive(Debug, Clone)]
+pub(crate) struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub(crate) fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consume `token` if the input continues with it.
+    pub(crate) fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, token: &str, kind: ParseErrorKind) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(kind))
+        }
+    }
+
+    pub(crate) fn take_while(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consume an identifier such as `foo` or `_bar1`.
+    pub(crate) fn ident(&mut self) -> Option<&'a str> {
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                Some(self.take_while(|c| c.is_alphanumeric() || c == '_'))
+            }
+            Some(_) | None => None,
+        }
+    }
+
+    fn digits(&mut self) -> Option<&'a str> {
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        (!digits.is_empty()).then_some(digits)
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.position, kind)
+    }
+
+    pub(crate) fn error_at(&self, position: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            column: self.input[..position].chars().count() + 1,
+            kind,
+        }
+    }
+}
+
+/// The error returned by [`parse`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// The column, counted in characters and starting at 1, where the error occurred.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.kind, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The kind of a [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// Expected the start of a key, `.`, `[`, or `::`.
+    ExpectedKey,
+    /// Expected a field name or index after `.`.
+    ExpectedField,
+    /// Expected a variant name after `::`.
+    ExpectedVariant,
+    /// Expected a `]` after a literal.
+    ExpectedCloseBracket,
+    /// Expected a literal such as `1`, `"foo"`, or `true`.
+    ExpectedLiteral,
+    /// A numbered field that doesn't fit in a `usize`.
+    InvalidIndex,
+    /// A number that doesn't fit its type or is otherwise malformed.
+    InvalidNumber,
+    /// An unknown suffix on a number, such as `1_foo`.
+    InvalidSuffix,
+    /// A string that isn't closed with `"`.
+    UnterminatedString,
+    /// A char literal that doesn't contain exactly one character.
+    InvalidChar,
+    /// An unknown escape sequence in a string or char literal.
+    InvalidEscape,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::ExpectedKey => write!(f, "expected `.`, `[`, or `::`"),
+            ParseErrorKind::ExpectedField => write!(f, "expected field name or index"),
+            ParseErrorKind::ExpectedVariant => write!(f, "expected variant name"),
+            ParseErrorKind::ExpectedCloseBracket => write!(f, "expected `]`"),
+            ParseErrorKind::ExpectedLiteral => write!(f, "expected literal"),
+            ParseErrorKind::InvalidIndex => write!(f, "invalid field index"),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::InvalidSuffix => write!(f, "invalid number suffix"),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ParseErrorKind::InvalidChar => write!(f, "invalid char literal"),
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence"),
+        }
+    }
+}
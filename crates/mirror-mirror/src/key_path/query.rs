@@ -0,0 +1,605 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use super::parse::parse_key;
+use super::parse::Cursor;
+use super::value_at_key;
+use super::value_at_key_mut;
+use super::IntoKeyOrIndex;
+use super::Key;
+use super::KeyPath;
+use super::ParseError;
+use super::ParseErrorKind;
+use crate::enum_::VariantField;
+use crate::enum_::VariantFieldMut;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectRef;
+use crate::Value;
+
+/// A [`KeyPath`] that can contain wildcards and therefore match many values.
+///
+/// In addition to the keys supported by `KeyPath` a query can contain:
+///
+/// - `.*` which matches every field of a struct, tuple struct, tuple, or enum variant.
+/// - `[*]` which matches every element of a list or array, and every value in a map.
+/// - `.**` which matches the value itself and every value nested inside it, at any depth.
+///
+/// The paths of matched enum fields always include the variant, such as `.foo::Some.0`, even if
+/// the query doesn't.
+///
+/// Queries are usually parsed from strings, but can also be built using the methods on
+/// `Query`.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::{key_path, key_path::{GetPath, Query}, Reflect};
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Scene {
+///     entities: Vec<Entity>,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Entity {
+///     name: String,
+///     health: f32,
+///     children: Vec<Entity>,
+/// }
+///
+/// let mut scene = Scene {
+///     entities: Vec::from([
+///         Entity {
+///             name: "player".to_owned(),
+///             health: 10.0,
+///             children: Vec::from([Entity {
+///                 name: "sword".to_owned(),
+///                 health: 3.0,
+///                 children: Vec::new(),
+///             }]),
+///         },
+///     ]),
+/// };
+///
+/// // find every `name` of the top level entities
+/// let query = ".entities[*].name".parse::<Query>().unwrap();
+/// let names = scene
+///     .query(&query)
+///     .map(|(path, value)| (path, value.downcast_ref::<String>().unwrap().as_str()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(names, [(key_path!(.entities[0_usize].name), "player")]);
+///
+/// // set every `health` anywhere in the scene
+/// let query = ".**.health".parse::<Query>().unwrap();
+/// scene.query_mut(&query, |_path, value| value.patch(&100.0_f32));
+///
+/// assert_eq!(scene.entities[0].health, 100.0);
+/// assert_eq!(scene.entities[0].children[0].health, 100.0);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Query {
+    keys: Vec<QueryKey>,
+}
+
+impl Query {
+    pub fn field(mut self, field: impl IntoKeyOrIndex) -> Self {
+        self.push(QueryKey::Key(Key::Field(field.into_key_or_index())));
+        self
+    }
+
+    pub fn get(mut self, field: impl Into<Value>) -> Self {
+        self.push(QueryKey::Key(Key::Get(field.into())));
+        self
+    }
+
+    pub fn variant(mut self, variant: impl Into<alloc::string::String>) -> Self {
+        self.push(QueryKey::Key(Key::Variant(variant.into())));
+        self
+    }
+
+    /// Match every field. Same as `.*`.
+    pub fn any_field(mut self) -> Self {
+        self.push(QueryKey::AnyField);
+        self
+    }
+
+    /// Match every element of a list or array, or every value in a map. Same as `[*]`.
+    pub fn any_element(mut self) -> Self {
+        self.push(QueryKey::AnyElement);
+        self
+    }
+
+    /// Match the value and everything nested inside it. Same as `.**`.
+    pub fn descendants(mut self) -> Self {
+        self.push(QueryKey::Descendants);
+        self
+    }
+
+    pub fn push(&mut self, key: QueryKey) {
+        // `.**.**` matches the same as `.**` but would yield each match several times
+        if key == QueryKey::Descendants && self.keys.last() == Some(&QueryKey::Descendants) {
+            return;
+        }
+        self.keys.push(key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, QueryKey> {
+        self.keys.iter()
+    }
+
+    /// Find all the values in `value` that match the query.
+    ///
+    /// Same as [`GetPath::query`](super::GetPath::query).
+    pub fn matches<'a>(&'a self, value: &'a dyn Reflect) -> Matches<'a> {
+        Matches {
+            query: self,
+            stack: Vec::from([(KeyPath::default(), value, 0)]),
+        }
+    }
+
+    /// Call `f` with every value in `value` that matches the query.
+    ///
+    /// Same as [`GetPath::query_mut`](super::GetPath::query_mut).
+    pub fn for_each_mut<F>(&self, value: &mut dyn Reflect, mut f: F)
+    where
+        F: FnMut(&KeyPath, &mut dyn Reflect),
+    {
+        go_mut(&self.keys, value, &mut KeyPath::default(), &mut f);
+    }
+
+    /// Call `f` with every value in `value` that matches the query, reaching through locks.
+    ///
+    /// Same as [`GetPath::query_with`](super::GetPath::query_with).
+    pub fn for_each_with<F>(&self, value: &dyn Reflect, mut f: F)
+    where
+        F: FnMut(&KeyPath, &dyn Reflect),
+    {
+        go_with(&self.keys, value, &mut KeyPath::default(), &mut f);
+    }
+}
+
+impl From<KeyPath> for Query {
+    fn from(key_path: KeyPath) -> Self {
+        key_path.into_iter().map(QueryKey::Key).collect()
+    }
+}
+
+impl FromIterator<QueryKey> for Query {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = QueryKey>,
+    {
+        let mut query = Query::default();
+        for key in iter {
+            query.push(key);
+        }
+        query
+    }
+}
+
+impl<'a> IntoIterator for &'a Query {
+    type Item = &'a QueryKey;
+    type IntoIter = core::slice::Iter<'a, QueryKey>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.iter()
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_query(s)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.keys {
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single key in a [`Query`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QueryKey {
+    /// Match exactly this key.
+    Key(Key),
+    /// `.*`
+    AnyField,
+    /// `[*]`
+    AnyElement,
+    /// `.**`
+    Descendants,
+}
+
+impl fmt::Display for QueryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryKey::Key(key) => write!(f, "{key}"),
+            QueryKey::AnyField => write!(f, ".*"),
+            QueryKey::AnyElement => write!(f, "[*]"),
+            QueryKey::Descendants => write!(f, ".**"),
+        }
+    }
+}
+
+/// Parse a [`Query`] such as `.items[*].name` or `.**.health`.
+///
+/// See [`parse`](super::parse) for the syntax of the keys that aren't wildcards.
+pub fn parse_query(input: &str) -> Result<Query, ParseError> {
+    let mut cursor = Cursor::new(input);
+    let mut query = Query::default();
+    while !cursor.is_at_end() {
+        if cursor.eat(".**") {
+            query.push(QueryKey::Descendants);
+        } else if cursor.eat(".*") {
+            query.push(QueryKey::AnyField);
+        } else if let Some(after_wildcard) = any_element(&cursor) {
+            cursor = after_wildcard;
+            cursor.skip_whitespace();
+            cursor.expect("]", ParseErrorKind::ExpectedCloseBracket)?;
+            query.push(QueryKey::AnyElement);
+        } else {
+            query.push(QueryKey::Key(parse_key(&mut cursor)?));
+        }
+    }
+    Ok(query)
+}
+
+/// Returns the cursor after `[*` if the input continues with that.
+fn any_element<'a>(cursor: &Cursor<'a>) -> Option<Cursor<'a>> {
+    let mut cursor = cursor.clone();
+    if !cursor.eat("[") {
+        return None;
+    }
+    cursor.skip_whitespace();
+    cursor.eat("*").then_some(cursor)
+}
+
+/// Iterator returned by [`Query::matches`] and [`GetPath::query`](super::GetPath::query).
+///
+/// Values are yielded in pre-order, so a value comes before the values nested inside it.
+#[derive(Debug)]
+pub struct Matches<'a> {
+    query: &'a Query,
+    // values left to visit, the top of the stack is visited first
+    stack: Vec<(KeyPath, &'a dyn Reflect, usize)>,
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = (KeyPath, &'a dyn Reflect);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, value, index)) = self.stack.pop() {
+            let Some(query_key) = self.query.keys.get(index) else {
+                return Some((path, value));
+            };
+
+            match query_key {
+                QueryKey::Key(key) => {
+                    if let Some(child) = value_at_key(value, key) {
+                        let mut path = path;
+                        if let Key::Field(_) = key {
+                            path.extend(variant_key(value, &path));
+                        }
+                        path.push(key.clone());
+                        self.stack.push((path, child, index + 1));
+                    }
+                }
+                QueryKey::AnyField | QueryKey::AnyElement => {
+                    let children = children(value, query_key == &QueryKey::AnyField);
+                    self.push_children(&path, value, children, index + 1);
+                }
+                QueryKey::Descendants => {
+                    let mut children_ = children(value, true);
+                    children_.extend(children(value, false));
+                    self.push_children(&path, value, children_, index);
+                    // pushed last so the value itself is visited before its children
+                    self.stack.push((path, value, index + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Matches<'a> {
+    fn push_children(
+        &mut self,
+        path: &KeyPath,
+        parent: &dyn Reflect,
+        children: Vec<(Key, &'a dyn Reflect)>,
+        index: usize,
+    ) {
+        let mut path = path.clone();
+        path.extend(variant_key(parent, &path));
+        self.stack
+            .extend(children.into_iter().rev().map(|(key, child)| {
+                let mut path = path.clone();
+                path.push(key);
+                (path, child, index)
+            }));
+    }
+}
+
+impl core::iter::FusedIterator for Matches<'_> {}
+
+/// The key of the variant `value` is in, if it's an enum and `path` doesn't already end with it.
+///
+/// Paths to the fields of an enum go through its variant, as in `.foo::Some.0`, the same as the
+/// paths in [`Change`](crate::diff::Change)s.
+fn variant_key(value: &dyn Reflect, path: &KeyPath) -> Option<Key> {
+    let enum_ = value.reflect_ref().as_enum()?;
+    if let Some(Key::Variant(_)) = path.path.last() {
+        return None;
+    }
+    Some(Key::Variant(enum_.variant_name().into()))
+}
+
+/// The fields of `value` if `fields` is true, otherwise its elements.
+fn children(value: &dyn Reflect, fields: bool) -> Vec<(Key, &dyn Reflect)> {
+    match value.reflect_ref() {
+        ReflectRef::Struct(inner) if fields => inner
+            .fields()
+            .map(|(name, value)| (Key::named_field(name), value))
+            .collect(),
+        ReflectRef::TupleStruct(inner) if fields => inner
+            .fields()
+            .enumerate()
+            .map(|(index, value)| (Key::numbered_field(index), value))
+            .collect(),
+        ReflectRef::Tuple(inner) if fields => inner
+            .fields()
+            .enumerate()
+            .map(|(index, value)| (Key::numbered_field(index), value))
+            .collect(),
+        ReflectRef::Enum(inner) if fields => inner
+            .fields()
+            .enumerate()
+            .map(|(index, field)| match field {
+                VariantField::Struct(name, value) => (Key::named_field(name), value),
+                VariantField::Tuple(value) => (Key::numbered_field(index), value),
+            })
+            .collect(),
+        ReflectRef::Array(inner) if !fields => inner
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (Key::get(index), value))
+            .collect(),
+        ReflectRef::List(inner) if !fields => inner
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (Key::get(index), value))
+            .collect(),
+        ReflectRef::Map(inner) if !fields => inner
+            .iter()
+            .map(|(key, value)| (Key::Get(key.to_value()), value))
+            .collect(),
+        ReflectRef::Set(inner) if !fields => inner
+            .iter()
+            .map(|value| (Key::Get(value.to_value()), value))
+            .collect(),
+        ReflectRef::Struct(_)
+        | ReflectRef::TupleStruct(_)
+        | ReflectRef::Tuple(_)
+        | ReflectRef::Enum(_)
+        | ReflectRef::Array(_)
+        | ReflectRef::List(_)
+        | ReflectRef::Map(_)
+        | ReflectRef::Set(_)
+        | ReflectRef::Scalar(_)
+        | ReflectRef::Opaque(_) => Vec::new(),
+    }
+}
+
+fn go_mut(
+    keys: &[QueryKey],
+    value: &mut dyn Reflect,
+    path: &mut KeyPath,
+    f: &mut dyn FnMut(&KeyPath, &mut dyn Reflect),
+) {
+    let Some((query_key, rest)) = keys.split_first() else {
+        f(path, value);
+        return;
+    };
+
+    match query_key {
+        QueryKey::Key(key) => {
+            let variant = match key {
+                Key::Field(_) => variant_key(value, path),
+                Key::Get(_) | Key::Variant(_) => None,
+            };
+            let pushed = variant.is_some();
+            path.extend(variant);
+            if let Some(child) = value_at_key_mut(value, key) {
+                path.push(key.clone());
+                go_mut(rest, child, path, f);
+                path.pop();
+            }
+            if pushed {
+                path.pop();
+            }
+        }
+        QueryKey::AnyField | QueryKey::AnyElement => {
+            let variant = variant_key(value, path);
+            let pushed = variant.is_some();
+            path.extend(variant);
+            for_each_child_mut(
+                value,
+                query_key == &QueryKey::AnyField,
+                &mut |key, child| {
+                    path.push(key);
+                    go_mut(rest, child, path, f);
+                    path.pop();
+                },
+            );
+            if pushed {
+                path.pop();
+            }
+        }
+        QueryKey::Descendants => {
+            go_mut(rest, value, path, f);
+            let variant = variant_key(value, path);
+            let pushed = variant.is_some();
+            path.extend(variant);
+            for fields in [true, false] {
+                for_each_child_mut(value, fields, &mut |key, child| {
+                    path.push(key);
+                    go_mut(keys, child, path, f);
+                    path.pop();
+                });
+            }
+            if pushed {
+                path.pop();
+            }
+        }
+    }
+}
+
+// like `go_mut` but with shared references, locking each value with `reflect_guard` before looking
+// inside it
+fn go_with(
+    keys: &[QueryKey],
+    value: &dyn Reflect,
+    path: &mut KeyPath,
+    f: &mut dyn FnMut(&KeyPath, &dyn Reflect),
+) {
+    let guard = value.reflect_guard();
+    let value = &*guard;
+
+    let Some((query_key, rest)) = keys.split_first() else {
+        f(path, value);
+        return;
+    };
+
+    match query_key {
+        QueryKey::Key(key) => {
+            let variant = match key {
+                Key::Field(_) => variant_key(value, path),
+                Key::Get(_) | Key::Variant(_) => None,
+            };
+            let pushed = variant.is_some();
+            path.extend(variant);
+            if let Some(child) = value_at_key(value, key) {
+                path.push(key.clone());
+                go_with(rest, child, path, f);
+                path.pop();
+            }
+            if pushed {
+                path.pop();
+            }
+        }
+        QueryKey::AnyField | QueryKey::AnyElement => {
+            let variant = variant_key(value, path);
+            let pushed = variant.is_some();
+            path.extend(variant);
+            for (key, child) in children(value, query_key == &QueryKey::AnyField) {
+                path.push(key);
+                go_with(rest, child, path, f);
+                path.pop();
+            }
+            if pushed {
+                path.pop();
+            }
+        }
+        QueryKey::Descendants => {
+            go_with(rest, value, path, f);
+            let variant = variant_key(value, path);
+            let pushed = variant.is_some();
+            path.extend(variant);
+            for fields in [true, false] {
+                for (key, child) in children(value, fields) {
+                    path.push(key);
+                    go_with(keys, child, path, f);
+                    path.pop();
+                }
+            }
+            if pushed {
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Call `f` with the fields of `value` if `fields` is true, otherwise its elements.
+fn for_each_child_mut(
+    value: &mut dyn Reflect,
+    fields: bool,
+    f: &mut dyn FnMut(Key, &mut dyn Reflect),
+) {
+    match value.reflect_mut() {
+        ReflectMut::Struct(inner) if fields => {
+            // one field at a time, since some structs can't hand out all their fields at once
+            for index in 0..inner.fields_len() {
+                let Some(key) = inner.name_at(index).map(Key::named_field) else {
+                    continue;
+                };
+                if let Some(value) = inner.field_at_mut(index) {
+                    f(key, value);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(inner) if fields => {
+            for (index, value) in inner.fields_mut().enumerate() {
+                f(Key::numbered_field(index), value);
+            }
+        }
+        ReflectMut::Tuple(inner) if fields => {
+            for (index, value) in inner.fields_mut().enumerate() {
+                f(Key::numbered_field(index), value);
+            }
+        }
+        ReflectMut::Enum(inner) if fields => {
+            for (index, field) in inner.fields_mut().enumerate() {
+                match field {
+                    VariantFieldMut::Struct(name, value) => f(Key::named_field(name), value),
+                    VariantFieldMut::Tuple(value) => f(Key::numbered_field(index), value),
+                }
+            }
+        }
+        ReflectMut::Array(inner) if !fields => {
+            for (index, value) in inner.iter_mut().enumerate() {
+                f(Key::get(index), value);
+            }
+        }
+        ReflectMut::List(inner) if !fields => {
+            for (index, value) in inner.iter_mut().enumerate() {
+                f(Key::get(index), value);
+            }
+        }
+        ReflectMut::Map(inner) if !fields => {
+            for (key, value) in inner.iter_mut() {
+                f(Key::Get(key.to_value()), value);
+            }
+        }
+        ReflectMut::Struct(_)
+        | ReflectMut::TupleStruct(_)
+        | ReflectMut::Tuple(_)
+        | ReflectMut::Enum(_)
+        | ReflectMut::Array(_)
+        | ReflectMut::List(_)
+        | ReflectMut::Map(_)
+        // elements of sets cannot be mutated in place
+        | ReflectMut::Set(_)
+        | ReflectMut::Scalar(_)
+        | ReflectMut::Opaque(_) => {}
+    }
+}
@@ -175,6 +175,56 @@
 //! # })().unwrap();
 //! ```
 //!
+//! # Shared ownership
+//!
+//! `Arc<T>` is reflected transparently, the same way as `Box<T>`. Reading goes straight through
+//! to the shared value while mutable access uses [`Arc::make_mut`], so a value shared with other
+//! `Arc`s is cloned first and the other owners keep seeing the old value. [`FromReflect`] creates
+//! a new, unshared `Arc`.
+//!
+//! Since the clone happens when the mutable reference is handed out, any mutable access clones a
+//! shared value even if nothing ends up being written, such as a [`GetPath::at_mut`] lookup that
+//! fails or a [`try_visit_mut`](try_visit::try_visit_mut) visitor that doesn't change anything.
+//! Use the shared accessors where possible. [`Reflect::patch`] and [`Reflect::try_patch`] skip
+//! the clone if the value already equals the patch.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use mirror_mirror::{Reflect, key_path, key_path::GetPath};
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! struct Foo {
+//!     n: i32,
+//! }
+//!
+//! let shared = Arc::new(Foo { n: 1 });
+//! let mut arc = Arc::clone(&shared);
+//! *arc.get_at_mut::<i32>(&key_path!(.n)).unwrap() = 2;
+//! assert_eq!(arc.n, 2);
+//! assert_eq!(shared.n, 1);
+//! ```
+//!
+//! `Rc<T>` is out of scope. It cannot implement [`Reflect`] since it isn't `Send`, so types that
+//! derive [`Reflect`] should use `Arc<T>` instead.
+//!
+//! [`Arc::make_mut`]: alloc::sync::Arc::make_mut
+//! [`GetPath::at_mut`]: key_path::GetPath::at_mut
+//!
+//! # String-like types
+//!
+//! `Box<str>`, `Cow<'static, str>`, `Arc<str>`, and, with the `std` feature, `PathBuf` and
+//! `OsString` are described as `String` and become [`Value::String`] when converted with
+//! [`Reflect::to_value`]. [`FromReflect`] converts back from any of them.
+//! [`Reflect::reflect_ref`] returns a [`ScalarRef::String`] that borrows the string, though
+//! [`StrRef::as_reflect`] is the original value rather than a `String`. They don't contain a
+//! `String` that can be borrowed mutably so [`Reflect::reflect_mut`] returns
+//! [`ReflectMut::Opaque`] and they're changed by patching.
+//!
+//! Paths and OS strings that aren't valid UTF-8 are converted lossily, replacing invalid sequences
+//! with `U+FFFD REPLACEMENT CHARACTER`, so they don't round-trip. They can't be borrowed as a
+//! `str` either, so [`Reflect::reflect_ref`] returns [`ReflectRef::Opaque`] and shared access to
+//! the converted `String` goes through [`Reflect::reflect_guard`].
+//!
 //! # Inspiration
 //!
 //! The design of this library is heavily inspired by [`bevy_reflect`] but with a few key
@@ -201,6 +251,12 @@
 //! `serde` | Enables [`serde`] support for most types | Yes
 //! `glam` | Enables impls for [`glam`] | No
 //! `macaw` | Enables impls for [`macaw`] | No
+//! `uuid` | Enables impls for [`uuid`] | No
+//! `time` | Enables impls for [`time`] | No
+//! `chrono` | Enables impls for [`chrono`] | No
+//! `smallvec` | Enables impls for [`smallvec`] | No
+//! `arrayvec` | Enables impls for [`arrayvec`] | No
+//! `indexmap` | Enables impls for [`indexmap`] | No
 //!
 //! [`speedy`]: https://crates.io/crates/speedy
 //! [`serde`]: https://crates.io/crates/serde
@@ -208,13 +264,19 @@
 //! [`bevy`]: https://crates.io/crates/bevy
 //! [`glam`]: https://crates.io/crates/glam
 //! [`macaw`]: https://crates.io/crates/macaw
+//! [`uuid`]: https://crates.io/crates/uuid
+//! [`time`]: https://crates.io/crates/time
+//! [`chrono`]: https://crates.io/crates/chrono
+//! [`smallvec`]: https://crates.io/crates/smallvec
+//! [`arrayvec`]: https://crates.io/crates/arrayvec
+//! [`indexmap`]: https://crates.io/crates/indexmap
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(
     clippy::all,
     clippy::dbg_macro,
     clippy::todo,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::enum_glob_use,
     clippy::mem_forget,
     clippy::unused_self,
@@ -223,9 +285,7 @@
     clippy::needless_borrow,
     clippy::match_wildcard_for_single_variants,
     clippy::if_let_mutex,
-    clippy::mismatched_target_os,
     clippy::await_holding_lock,
-    clippy::match_on_vec_items,
     clippy::imprecise_flops,
     clippy::suboptimal_flops,
     clippy::lossy_float_literal,
@@ -265,6 +325,7 @@ use alloc::string::String;
 use core::any::Any;
 use core::any::TypeId;
 use core::fmt;
+use core::ops::Deref;
 
 use crate::enum_::VariantField;
 use crate::enum_::VariantKind;
@@ -298,12 +359,21 @@ macro_rules! trivial_reflect_methods {
 /// Reflected array types.
 pub mod array;
 
+/// Compact binary encoding of reflected values, driven by a type descriptor.
+pub mod compact;
+
+/// Structural diffing of reflected values.
+pub mod diff;
+
 /// Reflected enum types.
 pub mod enum_;
 
 /// Helper traits for accessing fields on reflected values.
 pub mod get_field;
 
+/// Shared access to values behind locks.
+pub mod guard;
+
 /// Iterator types.
 pub mod iter;
 
@@ -316,9 +386,22 @@ pub mod list;
 /// Reflected map types.
 pub mod map;
 
+/// Fallible patching of reflected values.
+pub mod patch;
+
+/// Serde adapters that serialize and deserialize reflected values in their natural shape.
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod reflect_serde;
+
+/// Reflected set types.
+pub mod set;
+
 /// Reflected struct types.
 pub mod struct_;
 
+/// Rust-like text format for values.
+pub mod text;
+
 /// Reflected tuple types.
 pub mod tuple;
 
@@ -331,11 +414,15 @@ pub mod type_info;
 /// Type erased value types.
 pub mod value;
 
+/// Read-only reflection of borrowed data.
+pub mod view;
+
 pub mod try_visit;
 
 mod foreign_impls;
 mod reflect_eq;
 
+pub use diff::reflect_diff;
 pub use reflect_eq::reflect_eq;
 
 #[cfg(feature = "std")]
@@ -354,10 +441,16 @@ pub use self::get_field::GetField;
 #[doc(inline)]
 pub use self::get_field::GetFieldMut;
 #[doc(inline)]
+pub use self::guard::ReflectGuard;
+#[doc(inline)]
 pub use self::list::List;
 #[doc(inline)]
 pub use self::map::Map;
 #[doc(inline)]
+pub use self::patch::PatchError;
+#[doc(inline)]
+pub use self::set::Set;
+#[doc(inline)]
 pub use self::struct_::Struct;
 #[doc(inline)]
 pub use self::tuple::Tuple;
@@ -395,8 +488,64 @@ pub trait Reflect: Any + Send + 'static {
 
     fn reflect_mut(&mut self) -> ReflectMut<'_>;
 
+    /// Get shared access to the value, locking it if it's behind a `RefCell`, `Mutex`, or other
+    /// lock. See [`ReflectGuard`] for more details.
+    ///
+    /// This doesn't wait for locks that are already held, since they might be held by the caller.
+    /// If a `RefCell` is mutably borrowed or a `Mutex` or `RwLock` is locked, the guard contains
+    /// the value itself, which is opaque. Poisoned locks are still locked.
+    ///
+    /// Paths and OS strings that aren't valid UTF-8 return a guard that owns the value converted to
+    /// a `String`.
+    ///
+    /// The default implementation doesn't lock anything and returns a guard that borrows `self`.
+    fn reflect_guard(&self) -> ReflectGuard<'_> {
+        ReflectGuard::new(self.as_reflect())
+    }
+
     fn patch(&mut self, value: &dyn Reflect);
 
+    /// Like [`patch`](Reflect::patch) but reports the parts of `value` that couldn't be patched,
+    /// rather than silently ignoring them.
+    ///
+    /// Patching doesn't stop at the first mismatch. Everything that can be patched will be, and
+    /// the error contains every mismatch along with its key path.
+    ///
+    /// The default implementation walks `self` with [`reflect_mut`](Reflect::reflect_mut) and
+    /// calls `try_patch` on each field, element, and map value. Opaque values are patched with
+    /// [`patch`](Reflect::patch) and report a mismatch if their [`to_value`](Reflect::to_value)
+    /// doesn't equal the `value` afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::{Reflect, key_path, struct_::StructValue};
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Foo {
+    ///     a: i32,
+    ///     b: bool,
+    /// }
+    ///
+    /// let mut foo = Foo { a: 1, b: false };
+    ///
+    /// let patch = StructValue::new()
+    ///     .with_field("a", "not a number")
+    ///     .with_field("b", true);
+    ///
+    /// let err = foo.try_patch(&patch).unwrap_err();
+    /// let mismatch = &err.mismatches()[0];
+    /// assert_eq!(mismatch.path(), &key_path!(.a));
+    /// assert_eq!(mismatch.expected_type_name(), "i32");
+    /// assert_eq!(mismatch.actual_type_name(), "alloc::string::String");
+    ///
+    /// // fields that could be patched still are
+    /// assert!(foo.b);
+    /// ```
+    fn try_patch(&mut self, value: &dyn Reflect) -> Result<(), PatchError> {
+        patch::try_patch(self.as_reflect_mut(), value)
+    }
+
     fn to_value(&self) -> Value;
 
     fn clone_reflect(&self) -> Box<dyn Reflect>;
@@ -495,6 +644,18 @@ pub trait Reflect: Any + Send + 'static {
         self.reflect_mut().as_map_mut()
     }
 
+    fn into_set(self: Box<Self>) -> Option<Box<dyn Set>> {
+        self.reflect_owned().into_set()
+    }
+
+    fn as_set(&self) -> Option<&dyn Set> {
+        self.reflect_ref().as_set()
+    }
+
+    fn as_set_mut(&mut self) -> Option<&mut dyn Set> {
+        self.reflect_mut().as_set_mut()
+    }
+
     fn into_scalar(self: Box<Self>) -> Option<ScalarOwned> {
         self.reflect_owned().into_scalar()
     }
@@ -623,8 +784,8 @@ impl Reflect for String {
     trivial_reflect_methods!();
 
     fn patch(&mut self, value: &dyn Reflect) {
-        if let Some(value) = value.as_any().downcast_ref::<Self>() {
-            *self = value.clone();
+        if let Some(value) = Self::from_reflect(value) {
+            *self = value;
         }
     }
 
@@ -659,7 +820,11 @@ impl Reflect for String {
 
 impl FromReflect for String {
     fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
-        Some(reflect.downcast_ref::<String>()?.clone())
+        // string-like types such as `Box<str>` reflect as `String` scalars as well
+        match reflect.reflect_ref() {
+            ReflectRef::Scalar(ScalarRef::String(string)) => Some(string.as_str().to_owned()),
+            _ => None,
+        }
     }
 }
 
@@ -671,7 +836,7 @@ impl From<String> for ScalarOwned {
 
 impl<'a> From<&'a String> for ScalarRef<'a> {
     fn from(value: &'a String) -> Self {
-        ScalarRef::String(value)
+        ScalarRef::String(StrRef::new(value, value))
     }
 }
 
@@ -700,6 +865,7 @@ pub enum ReflectOwned {
     Array(Box<dyn Array>),
     List(Box<dyn List>),
     Map(Box<dyn Map>),
+    Set(Box<dyn Set>),
     Scalar(ScalarOwned),
     /// Not all `Reflect` implementations allow access to the underlying value. This variant can be
     /// used for such types.
@@ -716,6 +882,7 @@ impl ReflectOwned {
             ReflectOwned::Array(inner) => inner.as_reflect_mut(),
             ReflectOwned::List(inner) => inner.as_reflect_mut(),
             ReflectOwned::Map(inner) => inner.as_reflect_mut(),
+            ReflectOwned::Set(inner) => inner.as_reflect_mut(),
             ReflectOwned::Scalar(inner) => inner.as_reflect_mut(),
             ReflectOwned::Opaque(inner) => inner.as_reflect_mut(),
         }
@@ -730,6 +897,7 @@ impl ReflectOwned {
             ReflectOwned::Array(inner) => inner.as_reflect(),
             ReflectOwned::List(inner) => inner.as_reflect(),
             ReflectOwned::Map(inner) => inner.as_reflect(),
+            ReflectOwned::Set(inner) => inner.as_reflect(),
             ReflectOwned::Scalar(inner) => inner.as_reflect(),
             ReflectOwned::Opaque(inner) => inner.as_reflect(),
         }
@@ -784,6 +952,13 @@ impl ReflectOwned {
         }
     }
 
+    pub fn into_set(self) -> Option<Box<dyn Set>> {
+        match self {
+            Self::Set(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
     pub fn into_scalar(self) -> Option<ScalarOwned> {
         match self {
             Self::Scalar(inner) => Some(inner),
@@ -809,6 +984,7 @@ impl Clone for ReflectOwned {
             Self::Array(inner) => inner.clone_reflect().reflect_owned(),
             Self::List(inner) => inner.clone_reflect().reflect_owned(),
             Self::Map(inner) => inner.clone_reflect().reflect_owned(),
+            Self::Set(inner) => inner.clone_reflect().reflect_owned(),
             Self::Opaque(inner) => inner.clone_reflect().reflect_owned(),
             Self::Scalar(inner) => Self::Scalar(inner.clone()),
         }
@@ -895,6 +1071,7 @@ pub enum ReflectRef<'a> {
     Array(&'a dyn Array),
     List(&'a dyn List),
     Map(&'a dyn Map),
+    Set(&'a dyn Set),
     Scalar(ScalarRef<'a>),
     /// Not all `Reflect` implementations allow access to the underlying value. This variant can be
     /// used for such types.
@@ -911,6 +1088,7 @@ impl<'a> ReflectRef<'a> {
             ReflectRef::Array(inner) => inner.as_reflect(),
             ReflectRef::List(inner) => inner.as_reflect(),
             ReflectRef::Map(inner) => inner.as_reflect(),
+            ReflectRef::Set(inner) => inner.as_reflect(),
             ReflectRef::Scalar(inner) => inner.as_reflect(),
             ReflectRef::Opaque(inner) => inner.as_reflect(),
         }
@@ -965,6 +1143,13 @@ impl<'a> ReflectRef<'a> {
         }
     }
 
+    pub fn as_set(self) -> Option<&'a dyn Set> {
+        match self {
+            Self::Set(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
     pub fn as_scalar(self) -> Option<ScalarRef<'a>> {
         match self {
             Self::Scalar(inner) => Some(inner),
@@ -999,7 +1184,7 @@ pub enum ScalarRef<'a> {
     char(char),
     f32(f32),
     f64(f64),
-    String(&'a String),
+    String(StrRef<'a>),
 }
 
 impl<'a> ScalarRef<'a> {
@@ -1020,11 +1205,65 @@ impl<'a> ScalarRef<'a> {
             ScalarRef::char(inner) => inner,
             ScalarRef::f32(inner) => inner,
             ScalarRef::f64(inner) => inner,
-            ScalarRef::String(inner) => *inner,
+            ScalarRef::String(inner) => inner.as_reflect(),
         }
     }
 }
 
+/// A borrowed string in a [`ScalarRef`].
+///
+/// Strings are reflected from `String` as well as string-like types such as `Box<str>` and
+/// `Cow<'static, str>`, so this holds the string along with the value it was borrowed from.
+/// Dereferences to `str`.
+#[derive(Copy, Clone)]
+pub struct StrRef<'a> {
+    string: &'a str,
+    value: &'a dyn Reflect,
+}
+
+impl<'a> StrRef<'a> {
+    /// Create a `StrRef` for `string` borrowed from `value`.
+    pub fn new(string: &'a str, value: &'a dyn Reflect) -> Self {
+        Self { string, value }
+    }
+
+    /// Get the string.
+    pub fn as_str(&self) -> &'a str {
+        self.string
+    }
+
+    /// Get the value the string was borrowed from, such as the `String` or `Box<str>`.
+    pub fn as_reflect(&self) -> &'a dyn Reflect {
+        self.value
+    }
+}
+
+impl Deref for StrRef<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.string
+    }
+}
+
+impl PartialEq for StrRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.string == other.string
+    }
+}
+
+impl fmt::Debug for StrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.string, f)
+    }
+}
+
+impl fmt::Display for StrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.string, f)
+    }
+}
+
 /// A mutable reflected value.
 ///
 /// Constructed with [`Reflect::reflect_mut`].
@@ -1037,6 +1276,7 @@ pub enum ReflectMut<'a> {
     Array(&'a mut dyn Array),
     List(&'a mut dyn List),
     Map(&'a mut dyn Map),
+    Set(&'a mut dyn Set),
     Scalar(ScalarMut<'a>),
     /// Not all `Reflect` implementations allow mutable access to the underlying value (such as
     /// [`core::num::NonZeroU8`]). This variant can be used for such types.
@@ -1053,6 +1293,7 @@ impl<'a> ReflectMut<'a> {
             ReflectMut::Array(inner) => inner.as_reflect_mut(),
             ReflectMut::List(inner) => inner.as_reflect_mut(),
             ReflectMut::Map(inner) => inner.as_reflect_mut(),
+            ReflectMut::Set(inner) => inner.as_reflect_mut(),
             ReflectMut::Scalar(inner) => inner.as_reflect_mut(),
             ReflectMut::Opaque(inner) => inner.as_reflect_mut(),
         }
@@ -1067,6 +1308,7 @@ impl<'a> ReflectMut<'a> {
             ReflectMut::Array(inner) => inner.as_reflect(),
             ReflectMut::List(inner) => inner.as_reflect(),
             ReflectMut::Map(inner) => inner.as_reflect(),
+            ReflectMut::Set(inner) => inner.as_reflect(),
             ReflectMut::Scalar(inner) => inner.as_reflect(),
             ReflectMut::Opaque(inner) => inner.as_reflect(),
         }
@@ -1121,6 +1363,13 @@ impl<'a> ReflectMut<'a> {
         }
     }
 
+    pub fn as_set_mut(self) -> Option<&'a mut dyn Set> {
+        match self {
+            Self::Set(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
     pub fn as_scalar_mut(self) -> Option<ScalarMut<'a>> {
         match self {
             Self::Scalar(inner) => Some(inner),
@@ -1271,6 +1520,7 @@ pub fn reflect_debug(value: &dyn Reflect, f: &mut core::fmt::Formatter<'_>) -> c
         ReflectRef::Array(inner) => f.debug_list().entries(inner.iter()).finish(),
         ReflectRef::List(inner) => f.debug_list().entries(inner.iter()).finish(),
         ReflectRef::Map(inner) => f.debug_map().entries(inner.iter()).finish(),
+        ReflectRef::Set(inner) => f.debug_set().entries(inner.iter()).finish(),
         ReflectRef::Scalar(inner) => match inner {
             ScalarRef::usize(inner) => scalar_debug(&inner, f),
             ScalarRef::u8(inner) => scalar_debug(&inner, f),
@@ -1337,4 +1587,19 @@ pub mod __private {
             self.to_owned().into_value()
         }
     }
+
+    #[track_caller]
+    pub fn typed_key_path<T>(key_path: KeyPath) -> KeyPath
+    where
+        T: DescribeType,
+    {
+        let type_descriptor = <T as DescribeType>::type_descriptor();
+        if type_descriptor.type_at(&key_path).is_none() {
+            panic!(
+                "`{key_path}` doesn't exist in `{}`",
+                type_descriptor.type_name()
+            );
+        }
+        key_path
+    }
 }
@@ -11,6 +11,16 @@ pub trait List: Array {
     fn pop(&mut self) -> Option<Box<dyn Reflect>>;
 
     fn try_remove(&mut self, index: usize) -> Option<Box<dyn Reflect>>;
+
+    /// Insert `value` at `index`, shifting all elements after it to the right.
+    ///
+    /// Returns `false` if `index > len` or `value` couldn't be converted into the element type.
+    ///
+    /// The default implementation doesn't support inserting and always returns `false`.
+    fn try_insert(&mut self, index: usize, value: &dyn Reflect) -> bool {
+        let _ = (index, value);
+        false
+    }
 }
 
 impl fmt::Debug for dyn List {
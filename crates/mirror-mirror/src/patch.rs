@@ -0,0 +1,669 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::enum_::VariantField;
+use crate::key_path::KeyPath;
+use crate::reflect_eq;
+use crate::Array;
+use crate::Enum;
+use crate::Map;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectRef;
+use crate::Set;
+use crate::Struct;
+use crate::Tuple;
+use crate::TupleStruct;
+
+/// The error returned by [`Reflect::try_patch`].
+///
+/// Patching doesn't stop at the first mismatch so this contains every part of the value that
+/// couldn't be patched.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PatchError {
+    mismatches: Vec<Mismatch>,
+}
+
+impl PatchError {
+    /// Create an error for a value that can't be converted into the type of `target`.
+    pub fn type_mismatch(target: &dyn Reflect, value: &dyn Reflect) -> Self {
+        Self {
+            mismatches: Vec::from([Mismatch::new(
+                KeyPath::default(),
+                MismatchKind::Type,
+                target,
+                value,
+            )]),
+        }
+    }
+
+    /// The mismatches, in the order they were found.
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+
+    /// Convert the error into its mismatches, in the order they were found.
+    pub fn into_mismatches(self) -> Vec<Mismatch> {
+        self.mismatches
+    }
+
+    /// Prefix the key paths of all mismatches with `path`.
+    pub(crate) fn prefixed(mut self, path: &KeyPath) -> Self {
+        for mismatch in &mut self.mismatches {
+            let mut prefixed = path.clone();
+            prefixed.extend(core::mem::take(&mut mismatch.path));
+            mismatch.path = prefixed;
+        }
+        self
+    }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut mismatches = self.mismatches.iter();
+        if let Some(first) = mismatches.next() {
+            write!(f, "{first}")?;
+        }
+        match mismatches.len() {
+            0 => Ok(()),
+            1 => write!(f, " (and 1 other mismatch)"),
+            n => write!(f, " (and {n} other mismatches)"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+/// A part of a value that couldn't be patched.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mismatch {
+    path: KeyPath,
+    kind: MismatchKind,
+    expected: String,
+    actual: String,
+}
+
+impl Mismatch {
+    fn new(path: KeyPath, kind: MismatchKind, target: &dyn Reflect, value: &dyn Reflect) -> Self {
+        Self {
+            path,
+            kind,
+            expected: target.as_reflect().type_descriptor().type_name().to_owned(),
+            actual: value.as_reflect().type_name().to_owned(),
+        }
+    }
+
+    /// The key path of the mismatch, relative to the value being patched.
+    pub fn path(&self) -> &KeyPath {
+        &self.path
+    }
+
+    /// Whether the value had the wrong type or something in it wasn't found.
+    pub fn kind(&self) -> MismatchKind {
+        self.kind
+    }
+
+    /// The type name of the value being patched.
+    ///
+    /// For [`MismatchKind::NotFound`] this is the type of the struct, list, or map that's
+    /// missing the field or element.
+    pub fn expected_type_name(&self) -> &str {
+        &self.expected
+    }
+
+    /// The type name of the value that was patched with.
+    pub fn actual_type_name(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            MismatchKind::Type => {
+                write!(f, "expected `{}`, found `{}`", self.expected, self.actual)?
+            }
+            MismatchKind::NotFound => write!(f, "no such field or element in `{}`", self.expected)?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The kind of a [`Mismatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MismatchKind {
+    /// The value couldn't be converted into the type being patched.
+    Type,
+    /// The value has a field, element, or map entry that the value being patched doesn't have.
+    NotFound,
+}
+
+/// The default implementation of [`Reflect::try_patch`].
+///
+/// Patches `target` field by field, calling `try_patch` on each field, element, or map value.
+pub(crate) fn try_patch(target: &mut dyn Reflect, value: &dyn Reflect) -> Result<(), PatchError> {
+    let value = &*value.reflect_guard();
+    let mut mismatches = Vec::new();
+
+    let type_mismatch =
+        |target: &dyn Reflect| Mismatch::new(KeyPath::default(), MismatchKind::Type, target, value);
+
+    match target.reflect_mut() {
+        ReflectMut::Struct(target) => match value.reflect_ref().as_struct() {
+            Some(value) => try_patch_struct(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::TupleStruct(target) => match value.reflect_ref().as_tuple_struct() {
+            Some(value) => try_patch_tuple_struct(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Tuple(target) => match value.reflect_ref().as_tuple() {
+            Some(value) => try_patch_tuple(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Enum(target) => match value.reflect_ref().as_enum() {
+            Some(value) => try_patch_enum(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Array(target) => match as_array_or_list(value) {
+            Some(value) => try_patch_array(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::List(target) => match as_array_or_list(value) {
+            Some(value) => try_patch_array(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Map(target) => match value.reflect_ref().as_map() {
+            Some(value) => try_patch_map(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Set(target) => match value.reflect_ref().as_set() {
+            Some(value) => try_patch_set(target, value, &mut mismatches),
+            None => mismatches.push(type_mismatch(target.as_reflect())),
+        },
+        ReflectMut::Scalar(mut target) => {
+            let target = target.as_reflect_mut();
+            if Reflect::type_id(value.as_reflect()) == Reflect::type_id(target) {
+                target.patch(value);
+            } else {
+                mismatches.push(type_mismatch(target));
+            }
+        }
+        ReflectMut::Opaque(target) => {
+            // there is no structure to follow so the best we can do is patch and then check that
+            // the values ended up being equal. `reflect_eq` can't compare opaque values so those
+            // are compared through their `Value`s instead
+            target.patch(value);
+            let patched = match reflect_eq(target, value) {
+                Some(eq) => eq,
+                None => target.to_value() == value.to_value(),
+            };
+            if !patched {
+                mismatches.push(type_mismatch(target));
+            }
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(PatchError { mismatches })
+    }
+}
+
+fn as_array_or_list(value: &dyn Reflect) -> Option<crate::array::Iter<'_>> {
+    match value.reflect_ref() {
+        ReflectRef::Array(array) => Some(array.iter()),
+        ReflectRef::List(list) => Some(list.iter()),
+        ReflectRef::Struct(_)
+        | ReflectRef::TupleStruct(_)
+        | ReflectRef::Tuple(_)
+        | ReflectRef::Enum(_)
+        | ReflectRef::Map(_)
+        | ReflectRef::Set(_)
+        | ReflectRef::Scalar(_)
+        | ReflectRef::Opaque(_) => None,
+    }
+}
+
+fn try_patch_field(
+    target: &mut dyn Reflect,
+    value: &dyn Reflect,
+    path: KeyPath,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    if let Err(err) = target.try_patch(value) {
+        mismatches.extend(err.prefixed(&path).mismatches);
+    }
+}
+
+fn not_found(
+    target: &dyn Reflect,
+    value: &dyn Reflect,
+    path: KeyPath,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    mismatches.push(Mismatch::new(path, MismatchKind::NotFound, target, value));
+}
+
+fn try_patch_struct(target: &mut dyn Struct, value: &dyn Struct, mismatches: &mut Vec<Mismatch>) {
+    for (name, value) in value.fields() {
+        let path = KeyPath::default().field(name);
+        match target.field_mut(name) {
+            Some(field) => try_patch_field(field, value, path, mismatches),
+            None => not_found(target.as_reflect(), value, path, mismatches),
+        }
+    }
+}
+
+fn try_patch_tuple_struct(
+    target: &mut dyn TupleStruct,
+    value: &dyn TupleStruct,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    for (index, value) in value.fields().enumerate() {
+        let path = KeyPath::default().field(index);
+        match target.field_at_mut(index) {
+            Some(field) => try_patch_field(field, value, path, mismatches),
+            None => not_found(target.as_reflect(), value, path, mismatches),
+        }
+    }
+}
+
+fn try_patch_tuple(target: &mut dyn Tuple, value: &dyn Tuple, mismatches: &mut Vec<Mismatch>) {
+    for (index, value) in value.fields().enumerate() {
+        let path = KeyPath::default().field(index);
+        match target.field_at_mut(index) {
+            Some(field) => try_patch_field(field, value, path, mismatches),
+            None => not_found(target.as_reflect(), value, path, mismatches),
+        }
+    }
+}
+
+fn try_patch_enum(target: &mut dyn Enum, value: &dyn Enum, mismatches: &mut Vec<Mismatch>) {
+    if target.variant_name() != value.variant_name()
+        || target.variant_kind() != value.variant_kind()
+    {
+        // changing the variant requires constructing a new value, which `patch` does through
+        // `FromReflect`
+        target.patch(value.as_reflect());
+        if target.variant_name() != value.variant_name() {
+            mismatches.push(Mismatch::new(
+                KeyPath::default(),
+                MismatchKind::Type,
+                target.as_reflect(),
+                value.as_reflect(),
+            ));
+        }
+        return;
+    }
+
+    for (index, field) in value.fields().enumerate() {
+        match field {
+            VariantField::Struct(name, value) => {
+                let path = KeyPath::default().field(name);
+                match target.field_mut(name) {
+                    Some(field) => try_patch_field(field, value, path, mismatches),
+                    None => not_found(target.as_reflect(), value, path, mismatches),
+                }
+            }
+            VariantField::Tuple(value) => {
+                let path = KeyPath::default().field(index);
+                match target.field_at_mut(index) {
+                    Some(field) => try_patch_field(field, value, path, mismatches),
+                    None => not_found(target.as_reflect(), value, path, mismatches),
+                }
+            }
+        }
+    }
+}
+
+fn try_patch_array<A>(
+    target: &mut A,
+    values: crate::array::Iter<'_>,
+    mismatches: &mut Vec<Mismatch>,
+) where
+    A: Array + ?Sized,
+{
+    for (index, value) in values.enumerate() {
+        let path = KeyPath::default().get(index);
+        match target.get_mut(index) {
+            Some(element) => try_patch_field(element, value, path, mismatches),
+            None => not_found(target.as_reflect(), value, path, mismatches),
+        }
+    }
+}
+
+fn try_patch_map(target: &mut dyn Map, value: &dyn Map, mismatches: &mut Vec<Mismatch>) {
+    for (key, value) in value.iter() {
+        let path = KeyPath::default().get(key.to_value());
+        match target.get_mut(key) {
+            Some(element) => try_patch_field(element, value, path, mismatches),
+            None => not_found(target.as_reflect(), value, path, mismatches),
+        }
+    }
+}
+
+fn try_patch_set(target: &mut dyn Set, value: &dyn Set, mismatches: &mut Vec<Mismatch>) {
+    // elements cannot be patched in place so patching replaces the elements of the set. Any
+    // element that's missing afterwards couldn't be converted into the element type
+    target.patch(value.as_reflect());
+
+    let type_descriptor = target.type_descriptor();
+    let expected = match type_descriptor.as_set() {
+        Some(set_type) => set_type.element_type().type_name(),
+        None => type_descriptor.type_name(),
+    };
+    for element in value.iter() {
+        if !target.contains(element) {
+            mismatches.push(Mismatch {
+                path: KeyPath::default().get(element.to_value()),
+                kind: MismatchKind::Type,
+                expected: expected.to_owned(),
+                actual: element.type_name().to_owned(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use core::num::NonZeroU8;
+
+    use super::*;
+    use crate::enum_::EnumValue;
+    use crate::struct_::StructValue;
+    use crate::tuple::TupleValue;
+    use crate::Value;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        n: i32,
+        non_zero: NonZeroU8,
+        bar: Bar,
+        list: Vec<i32>,
+        map: BTreeMap<String, i32>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        A { a: bool },
+        B(i32),
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            n: 1,
+            non_zero: NonZeroU8::new(1).unwrap(),
+            bar: Bar::A { a: false },
+            list: vec![1, 2],
+            map: BTreeMap::from([("a".into(), 1)]),
+        }
+    }
+
+    fn paths(err: &PatchError) -> Vec<String> {
+        err.mismatches()
+            .iter()
+            .map(|mismatch| mismatch.path().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn ok() {
+        let mut value = foo();
+        let mut new = foo();
+        new.n = 2;
+        new.bar = Bar::B(3);
+        new.list = vec![3, 4];
+        new.map.insert("a".into(), 2);
+
+        value.try_patch(&new).unwrap();
+        assert_eq!(value, new);
+
+        let mut value = foo();
+        value.try_patch(&new.to_value()).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn scalar() {
+        let mut n = 1_i32;
+        n.try_patch(&2_i32).unwrap();
+        assert_eq!(n, 2);
+
+        let err = n.try_patch(&2_i64).unwrap_err();
+        assert_eq!(err.mismatches().len(), 1);
+        assert_eq!(err.mismatches()[0].kind(), MismatchKind::Type);
+        assert_eq!(err.mismatches()[0].expected_type_name(), "i32");
+        assert_eq!(err.mismatches()[0].actual_type_name(), "i64");
+        assert_eq!(n, 2);
+
+        let mut value = Value::from(1_i32);
+        value.try_patch(&2_i32).unwrap();
+        assert_eq!(value, Value::from(2_i32));
+        assert!(value.try_patch(&true).is_err());
+    }
+
+    #[test]
+    fn via_scalar() {
+        let mut n = NonZeroU8::new(1).unwrap();
+        n.try_patch(&2_u8).unwrap();
+        assert_eq!(n.get(), 2);
+
+        let err = n.try_patch(&0_u8).unwrap_err();
+        assert_eq!(
+            err.mismatches()[0].expected_type_name(),
+            core::any::type_name::<NonZeroU8>()
+        );
+        assert_eq!(err.mismatches()[0].actual_type_name(), "u8");
+        assert_eq!(n.get(), 2);
+    }
+
+    #[test]
+    fn reports_every_mismatch() {
+        let mut value = foo();
+
+        let patch = StructValue::new()
+            .with_field("n", "foo")
+            .with_field("non_zero", 0_u8)
+            .with_field(
+                "bar",
+                EnumValue::new_struct_variant("A")
+                    .with_struct_field("a", 1)
+                    .finish(),
+            )
+            .with_field(
+                "list",
+                vec![Value::from(10), Value::from("foo"), Value::from(30)],
+            )
+            .with_field(
+                "map",
+                BTreeMap::from([(Value::from("a"), Value::from(2)), ("b".into(), 3.into())]),
+            )
+            .with_field("missing", 1);
+
+        let err = value.try_patch(&patch).unwrap_err();
+        assert_eq!(
+            paths(&err),
+            [
+                ".n",
+                ".non_zero",
+                ".bar.a",
+                ".list[1_usize]",
+                ".list[2_usize]",
+                r#".map["b"]"#,
+                ".missing",
+            ]
+        );
+        assert_eq!(
+            err.mismatches()
+                .iter()
+                .map(|mismatch| mismatch.kind())
+                .collect::<Vec<_>>(),
+            [
+                MismatchKind::Type,
+                MismatchKind::Type,
+                MismatchKind::Type,
+                MismatchKind::Type,
+                MismatchKind::NotFound,
+                MismatchKind::NotFound,
+                MismatchKind::NotFound,
+            ]
+        );
+        assert_eq!(
+            err.mismatches()[6].expected_type_name(),
+            "mirror_mirror::patch::tests::Foo"
+        );
+
+        // everything else was still patched
+        let mut expected = foo();
+        expected.list[0] = 10;
+        expected.map.insert("a".into(), 2);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn enum_variant() {
+        let mut value = Bar::A { a: false };
+        value
+            .try_patch(
+                &EnumValue::new_tuple_variant("B")
+                    .with_tuple_field(1)
+                    .finish(),
+            )
+            .unwrap();
+        assert_eq!(value, Bar::B(1));
+
+        let err = value
+            .try_patch(&EnumValue::new_unit_variant("C"))
+            .unwrap_err();
+        assert_eq!(paths(&err), [""]);
+        assert_eq!(
+            err.mismatches()[0].expected_type_name(),
+            "mirror_mirror::patch::tests::Bar"
+        );
+        assert_eq!(
+            err.mismatches()[0].actual_type_name(),
+            "mirror_mirror::enum_::EnumValue"
+        );
+        assert_eq!(value, Bar::B(1));
+    }
+
+    #[test]
+    fn kind_mismatch() {
+        let mut value = foo();
+        let err = value.try_patch(&TupleValue::new()).unwrap_err();
+        assert_eq!(paths(&err), [""]);
+        assert_eq!(
+            err.to_string(),
+            "expected `mirror_mirror::patch::tests::Foo`, found `mirror_mirror::tuple::TupleValue`"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let mut value = foo();
+        let err = value
+            .try_patch(&StructValue::new().with_field("n", true).with_field("x", 1))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected `i32`, found `bool` at `.n` (and 1 other mismatch)"
+        );
+        assert_eq!(
+            err.mismatches()[1].to_string(),
+            "no such field or element in `mirror_mirror::patch::tests::Foo` at `.x`"
+        );
+    }
+
+    #[test]
+    fn opaque() {
+        use core::any::Any;
+        use core::fmt;
+
+        use crate::type_info::graph::NodeId;
+        use crate::type_info::graph::OpaqueNode;
+        use crate::type_info::graph::TypeGraph;
+        use crate::DescribeType;
+        use crate::FromReflect;
+        use crate::ReflectOwned;
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Id(i32);
+
+        impl DescribeType for Id {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                graph.get_or_build_node_with::<Self, _>(|graph| {
+                    OpaqueNode::new::<Self>(Default::default(), graph)
+                })
+            }
+        }
+
+        impl Reflect for Id {
+            trivial_reflect_methods!();
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Opaque(self)
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Opaque(self)
+            }
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                if let Some(id) = value.downcast_ref::<Self>() {
+                    *self = *id;
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                self.0.to_value()
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                Box::new(*self)
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{self:?}")
+            }
+        }
+
+        impl FromReflect for Id {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                reflect.downcast_ref().copied()
+            }
+        }
+
+        let mut id = Id(1);
+        id.try_patch(&Id(2)).unwrap();
+        assert_eq!(id, Id(2));
+
+        let err = id.try_patch(&3_i32).unwrap_err();
+        assert_eq!(err.mismatches()[0].kind(), MismatchKind::Type);
+        assert_eq!(err.mismatches()[0].actual_type_name(), "i32");
+        assert_eq!(id, Id(2));
+    }
+
+    #[test]
+    fn boxed() {
+        let mut value = Box::new(1_i32);
+        value.try_patch(&2_i32).unwrap();
+        assert_eq!(*value, 2);
+        assert!(value.try_patch(&2_u8).is_err());
+    }
+}
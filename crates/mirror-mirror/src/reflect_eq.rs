@@ -1,12 +1,17 @@
 use crate::{
     enum_::{VariantField, VariantKind},
-    Array, Enum, List, Map, Reflect, ReflectRef, Struct, Tuple, TupleStruct,
+    guard::with_guards,
+    Array, Enum, List, Map, Reflect, ReflectRef, Set, Struct, Tuple, TupleStruct,
 };
 
 /// Compare two reflected values for equality.
 ///
 /// Returns `None` if either value contains a `ReflectRef::Opaque`.
 pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
+    with_guards(a, b, reflect_eq_guarded)
+}
+
+fn reflect_eq_guarded(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
     match (a.reflect_ref(), b.reflect_ref()) {
         (ReflectRef::Scalar(a), ReflectRef::Scalar(b)) => Some(a == b),
         (ReflectRef::Struct(a), ReflectRef::Struct(b)) => reflect_eq_struct(a, b),
@@ -16,6 +21,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
         (ReflectRef::Array(a), ReflectRef::Array(b)) => reflect_eq_array(a, b),
         (ReflectRef::List(a), ReflectRef::List(b)) => reflect_eq_list(a, b),
         (ReflectRef::Map(a), ReflectRef::Map(b)) => reflect_eq_map(a, b),
+        (ReflectRef::Set(a), ReflectRef::Set(b)) => reflect_eq_set(a, b),
         (ReflectRef::Opaque(_), _) | (_, ReflectRef::Opaque(_)) => None,
 
         (
@@ -25,6 +31,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::TupleStruct(_),
         )
@@ -35,6 +42,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::Tuple(_),
         )
@@ -45,6 +53,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::Enum(_),
         )
@@ -55,6 +64,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Enum(_)
             | ReflectRef::List(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::Array(_),
         )
@@ -65,6 +75,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Enum(_)
             | ReflectRef::Array(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::List(_),
         )
@@ -75,6 +86,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Enum(_)
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::Map(_),
         )
@@ -85,7 +97,19 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Enum(_)
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
-            | ReflectRef::Map(_),
+            | ReflectRef::Map(_)
+            | ReflectRef::Scalar(_),
+            ReflectRef::Set(_),
+        )
+        | (
+            ReflectRef::Struct(_)
+            | ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::Enum(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Map(_)
+            | ReflectRef::Set(_),
             ReflectRef::Scalar(_),
         )
         | (
@@ -95,6 +119,7 @@ pub fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
             | ReflectRef::Array(_)
             | ReflectRef::List(_)
             | ReflectRef::Map(_)
+            | ReflectRef::Set(_)
             | ReflectRef::Scalar(_),
             ReflectRef::Struct(_),
         ) => Some(false),
@@ -272,9 +297,14 @@ fn reflect_eq_map(a: &dyn Map, b: &dyn Map) -> Option<bool> {
     )
 }
 
+fn reflect_eq_set(a: &dyn Set, b: &dyn Set) -> Option<bool> {
+    Some(a.len() == b.len() && a.iter().all(|value| b.contains(value)))
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::collections::BTreeMap;
+    use alloc::collections::BTreeSet;
 
     use crate::{
         enum_::EnumValue, struct_::StructValue, tuple::TupleValue, tuple_struct::TupleStructValue,
@@ -547,4 +577,13 @@ mod tests {
         )
         .unwrap());
     }
+
+    #[test]
+    fn reflect_eq_set() {
+        assert!(reflect_eq(&BTreeSet::from([1, 2]), &BTreeSet::from([2, 1])).unwrap());
+        assert!(!reflect_eq(&BTreeSet::from([1, 2]), &BTreeSet::from([1, 3])).unwrap());
+        assert!(!reflect_eq(&BTreeSet::from([1, 2]), &BTreeSet::from([1])).unwrap());
+        assert!(reflect_eq(&BTreeSet::from([1, 2]), &BTreeSet::from([1, 2]).to_value()).unwrap());
+        assert!(!reflect_eq(&BTreeSet::from([1, 2]), &vec![1, 2]).unwrap());
+    }
 }
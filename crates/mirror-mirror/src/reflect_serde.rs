@@ -0,0 +1,1278 @@
+//! Serializing a [`Value`] with its own `Serialize` impl preserves everything needed to turn it
+//! back into a [`Value`], but the output is shaped like [`Value`] itself rather than the type it
+//! was created from. The adapters in this module work with the natural shape instead:
+//!
+//! - [`ReflectSerializer`] serializes any `&dyn Reflect` the same way `#[derive(Serialize)]`
+//!   would have serialized the type.
+//! - [`TypedReflectDeserializer`] uses a type descriptor to deserialize that shape, from any serde
+//!   format, into a [`Value`].
+//!
+//! Types like `Duration`, `Uuid`, and `NonZeroU32` are serialized and deserialized as the scalar
+//! they're described as, rather than the way their own `Serialize` impls would. Opaque types
+//! don't describe their contents and cannot be serialized or deserialized.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::reflect_serde::{ReflectSerializer, TypedReflectDeserializer};
+//! use mirror_mirror::{DescribeType, FromReflect, Reflect};
+//! use serde::de::DeserializeSeed;
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     health: u32,
+//! }
+//!
+//! let player = Player {
+//!     name: "Ferris".to_owned(),
+//!     health: 100,
+//! };
+//!
+//! let json = serde_json::to_string(&ReflectSerializer(&player)).unwrap();
+//! assert_eq!(json, r#"{"name":"Ferris","health":100}"#);
+//!
+//! let ty = <Player as DescribeType>::type_descriptor();
+//! let value = TypedReflectDeserializer::new(ty.get_type())
+//!     .deserialize(&mut serde_json::Deserializer::from_str(&json))
+//!     .unwrap();
+//! assert_eq!(Player::from_reflect(&value).unwrap(), player);
+//! ```
+//!
+//! # Enums
+//!
+//! `Option`s are serialized as serde options, and other enums with the variant name and index,
+//! like derived impls. [`Value`]s don't know which type they came from, so the variant index of an
+//! enum value isn't known. Formats that only write the index, such as `bincode`, therefore
+//! require the original type when serializing enums.
+//!
+//! [`Value`]: crate::Value
+//! [`ReflectSerializer`]: crate::reflect_serde::ReflectSerializer
+//! [`TypedReflectDeserializer`]: crate::reflect_serde::TypedReflectDeserializer
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use std::sync::RwLock;
+
+use once_cell::race::OnceBox;
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::de::IgnoredAny;
+use serde::de::Unexpected;
+use serde::ser;
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+use serde::ser::SerializeStruct;
+use serde::ser::SerializeStructVariant;
+use serde::ser::SerializeTuple;
+use serde::ser::SerializeTupleStruct;
+use serde::ser::SerializeTupleVariant;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::enum_::EnumValue;
+use crate::enum_::VariantKind;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::type_info::short_type_name;
+use crate::type_info::EnumType;
+use crate::type_info::ScalarType;
+use crate::type_info::Type;
+use crate::type_info::Variant;
+use crate::Enum;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// Serializes a reflected value with the same shape `#[derive(Serialize)]` would produce.
+///
+/// Values behind locks and other guards are looked through.
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectSerializer<'a>(pub &'a dyn Reflect);
+
+impl Serialize for ReflectSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let guard = self.0.reflect_guard();
+        let value = &*guard;
+
+        match value.reflect_ref() {
+            ReflectRef::Scalar(scalar) => match scalar {
+                ScalarRef::usize(n) => serializer.serialize_u64(n as u64),
+                ScalarRef::u8(n) => serializer.serialize_u8(n),
+                ScalarRef::u16(n) => serializer.serialize_u16(n),
+                ScalarRef::u32(n) => serializer.serialize_u32(n),
+                ScalarRef::u64(n) => serializer.serialize_u64(n),
+                ScalarRef::u128(n) => serializer.serialize_u128(n),
+                ScalarRef::i8(n) => serializer.serialize_i8(n),
+                ScalarRef::i16(n) => serializer.serialize_i16(n),
+                ScalarRef::i32(n) => serializer.serialize_i32(n),
+                ScalarRef::i64(n) => serializer.serialize_i64(n),
+                ScalarRef::i128(n) => serializer.serialize_i128(n),
+                ScalarRef::bool(b) => serializer.serialize_bool(b),
+                ScalarRef::char(c) => serializer.serialize_char(c),
+                ScalarRef::f32(n) => serializer.serialize_f32(n),
+                ScalarRef::f64(n) => serializer.serialize_f64(n),
+                ScalarRef::String(s) => serializer.serialize_str(s.as_str()),
+            },
+            ReflectRef::Struct(struct_) => {
+                let name = intern(short_type_name(value.type_name()));
+                // like `#[derive(Serialize)]` does for `struct Foo;`
+                if struct_.fields_len() == 0 {
+                    return serializer.serialize_unit_struct(name);
+                }
+                let mut state = serializer.serialize_struct(name, struct_.fields_len())?;
+                for (name, field) in struct_.fields() {
+                    state.serialize_field(intern(name), &ReflectSerializer(field))?;
+                }
+                state.end()
+            }
+            ReflectRef::TupleStruct(tuple_struct) => {
+                let name = intern(short_type_name(value.type_name()));
+                if tuple_struct.fields_len() == 1 {
+                    let field = tuple_struct.field_at(0).unwrap();
+                    return serializer.serialize_newtype_struct(name, &ReflectSerializer(field));
+                }
+                let mut state =
+                    serializer.serialize_tuple_struct(name, tuple_struct.fields_len())?;
+                for field in tuple_struct.fields() {
+                    state.serialize_field(&ReflectSerializer(field))?;
+                }
+                state.end()
+            }
+            ReflectRef::Tuple(tuple) => {
+                if tuple.fields_len() == 0 {
+                    return serializer.serialize_unit();
+                }
+                let mut state = serializer.serialize_tuple(tuple.fields_len())?;
+                for field in tuple.fields() {
+                    state.serialize_element(&ReflectSerializer(field))?;
+                }
+                state.end()
+            }
+            ReflectRef::Enum(enum_) => serialize_enum(value, enum_, serializer),
+            ReflectRef::List(list) => {
+                let mut state = serializer.serialize_seq(Some(list.len()))?;
+                for element in list.iter() {
+                    state.serialize_element(&ReflectSerializer(element))?;
+                }
+                state.end()
+            }
+            ReflectRef::Array(array) => {
+                let mut state = serializer.serialize_tuple(array.len())?;
+                for element in array.iter() {
+                    state.serialize_element(&ReflectSerializer(element))?;
+                }
+                state.end()
+            }
+            ReflectRef::Map(map) => {
+                let mut state = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map.iter() {
+                    state.serialize_entry(&ReflectSerializer(key), &ReflectSerializer(value))?;
+                }
+                state.end()
+            }
+            ReflectRef::Set(set) => {
+                let mut state = serializer.serialize_seq(Some(set.len()))?;
+                for element in set.iter() {
+                    state.serialize_element(&ReflectSerializer(element))?;
+                }
+                state.end()
+            }
+            ReflectRef::Opaque(_) => Err(ser::Error::custom(format_args!(
+                "`{}` is opaque and cannot be serialized",
+                value.type_name()
+            ))),
+        }
+    }
+}
+
+fn serialize_enum<S>(
+    value: &dyn Reflect,
+    enum_: &dyn Enum,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let descriptor = value.type_descriptor();
+    let enum_ty = descriptor.get_type().as_enum();
+
+    let is_option = match enum_ty {
+        Some(enum_ty) => enum_ty.is_option(),
+        // enum values don't know their type so go by the variant
+        None => match (enum_.variant_name(), enum_.variant_kind()) {
+            ("None", VariantKind::Unit) => true,
+            ("Some", VariantKind::Tuple) => enum_.fields_len() == 1,
+            _ => false,
+        },
+    };
+    if is_option {
+        return match enum_.field_at(0) {
+            Some(field) => serializer.serialize_some(&ReflectSerializer(field)),
+            None => serializer.serialize_none(),
+        };
+    }
+
+    let variant_index = enum_ty.and_then(|enum_ty| {
+        enum_ty
+            .variants()
+            .position(|variant_ty| variant_ty.name() == enum_.variant_name())
+    });
+    let variant_index = match variant_index {
+        Some(index) => index as u32,
+        // human readable formats go by the name
+        None if serializer.is_human_readable() => 0,
+        None => {
+            return Err(ser::Error::custom(format_args!(
+                "the index of variant `{}` of `{}` is unknown",
+                enum_.variant_name(),
+                value.type_name()
+            )))
+        }
+    };
+
+    let name = intern(short_type_name(value.type_name()));
+    let variant = intern(enum_.variant_name());
+    match enum_.variant_kind() {
+        VariantKind::Unit => serializer.serialize_unit_variant(name, variant_index, variant),
+        VariantKind::Tuple if enum_.fields_len() == 1 => serializer.serialize_newtype_variant(
+            name,
+            variant_index,
+            variant,
+            &ReflectSerializer(enum_.field_at(0).unwrap()),
+        ),
+        VariantKind::Tuple => {
+            let mut state = serializer.serialize_tuple_variant(
+                name,
+                variant_index,
+                variant,
+                enum_.fields_len(),
+            )?;
+            for index in 0..enum_.fields_len() {
+                state.serialize_field(&ReflectSerializer(enum_.field_at(index).unwrap()))?;
+            }
+            state.end()
+        }
+        VariantKind::Struct => {
+            let mut state = serializer.serialize_struct_variant(
+                name,
+                variant_index,
+                variant,
+                enum_.fields_len(),
+            )?;
+            for index in 0..enum_.fields_len() {
+                state.serialize_field(
+                    intern(enum_.name_at(index).unwrap()),
+                    &ReflectSerializer(enum_.field_at(index).unwrap()),
+                )?;
+            }
+            state.end()
+        }
+    }
+}
+
+/// Deserializes a [`Value`] of a given type, in the shape written by [`ReflectSerializer`].
+///
+/// `Option` fields that are missing from the input are deserialized as `None`, like derived
+/// impls do. Other missing fields are an error and unknown fields are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct TypedReflectDeserializer<'a> {
+    ty: Type<'a>,
+}
+
+impl<'a> TypedReflectDeserializer<'a> {
+    /// Create a deserializer for values of type `ty`.
+    pub fn new(ty: Type<'a>) -> Self {
+        Self { ty }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for TypedReflectDeserializer<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.ty {
+            Type::Scalar(scalar_ty) => {
+                let visitor = ScalarVisitor(scalar_ty);
+                match scalar_ty.underlying() {
+                    ScalarType::usize | ScalarType::u64 => deserializer.deserialize_u64(visitor),
+                    ScalarType::u8 => deserializer.deserialize_u8(visitor),
+                    ScalarType::u16 => deserializer.deserialize_u16(visitor),
+                    ScalarType::u32 => deserializer.deserialize_u32(visitor),
+                    ScalarType::u128 => deserializer.deserialize_u128(visitor),
+                    ScalarType::i8 => deserializer.deserialize_i8(visitor),
+                    ScalarType::i16 => deserializer.deserialize_i16(visitor),
+                    ScalarType::i32 => deserializer.deserialize_i32(visitor),
+                    ScalarType::i64 => deserializer.deserialize_i64(visitor),
+                    ScalarType::i128 => deserializer.deserialize_i128(visitor),
+                    ScalarType::bool => deserializer.deserialize_bool(visitor),
+                    ScalarType::char => deserializer.deserialize_char(visitor),
+                    ScalarType::f32 => deserializer.deserialize_f32(visitor),
+                    ScalarType::f64 => deserializer.deserialize_f64(visitor),
+                    ScalarType::String => deserializer.deserialize_string(visitor),
+                    _ => unreachable!("`underlying` returns the scalar `NonZero*` types contain"),
+                }
+            }
+            Type::Struct(struct_ty) => {
+                let fields = struct_ty
+                    .field_types()
+                    .map(|field_ty| (field_ty.name(), field_ty.get_type()))
+                    .collect::<Vec<_>>();
+                let name = intern(short_type_name(struct_ty.type_name()));
+                let visitor = FieldsVisitor {
+                    type_name: struct_ty.type_name(),
+                    fields: &fields,
+                };
+                let values = if fields.is_empty() {
+                    deserializer.deserialize_unit_struct(name, visitor)?
+                } else {
+                    let names = intern_fields(fields.iter().map(|(name, _)| *name));
+                    deserializer.deserialize_struct(name, names, visitor)?
+                };
+                let mut struct_ = StructValue::with_capacity(fields.len());
+                for ((name, _), value) in fields.iter().zip(values) {
+                    struct_.set_field(*name, value);
+                }
+                Ok(struct_.into())
+            }
+            Type::TupleStruct(tuple_struct_ty) => {
+                let name = intern(short_type_name(tuple_struct_ty.type_name()));
+                let visitor = TupleVisitor {
+                    type_name: tuple_struct_ty.type_name(),
+                    fields: tuple_struct_ty
+                        .field_types()
+                        .map(|field_ty| field_ty.get_type())
+                        .collect(),
+                };
+                let values = if visitor.fields.len() == 1 {
+                    deserializer.deserialize_newtype_struct(name, visitor)?
+                } else {
+                    deserializer.deserialize_tuple_struct(name, visitor.fields.len(), visitor)?
+                };
+                let mut tuple_struct = TupleStructValue::with_capacity(values.len());
+                for value in values {
+                    tuple_struct.push_field(value);
+                }
+                Ok(tuple_struct.into())
+            }
+            Type::Tuple(tuple_ty) => {
+                let visitor = TupleVisitor {
+                    type_name: tuple_ty.type_name(),
+                    fields: tuple_ty
+                        .field_types()
+                        .map(|field_ty| field_ty.get_type())
+                        .collect(),
+                };
+                let values = if visitor.fields.is_empty() {
+                    deserializer.deserialize_unit(visitor)?
+                } else {
+                    deserializer.deserialize_tuple(visitor.fields.len(), visitor)?
+                };
+                let mut tuple = TupleValue::with_capacity(values.len());
+                for value in values {
+                    tuple.push_field(value);
+                }
+                Ok(tuple.into())
+            }
+            Type::Enum(enum_ty) => {
+                if enum_ty.is_option() {
+                    let some_ty = enum_ty
+                        .variant("Some")
+                        .and_then(|variant_ty| variant_ty.field_type_at(0))
+                        .unwrap()
+                        .get_type();
+                    return deserializer.deserialize_option(OptionVisitor(some_ty));
+                }
+                let variants =
+                    intern_fields(enum_ty.variants().map(|variant_ty| variant_ty.name()));
+                deserializer.deserialize_enum(
+                    intern(short_type_name(enum_ty.type_name())),
+                    variants,
+                    EnumVisitor { enum_ty, variants },
+                )
+            }
+            Type::List(list_ty) => deserializer.deserialize_seq(ListVisitor {
+                type_name: list_ty.type_name(),
+                element_ty: list_ty.element_type(),
+                len: None,
+            }),
+            Type::Array(array_ty) => deserializer.deserialize_tuple(
+                array_ty.len(),
+                ListVisitor {
+                    type_name: array_ty.type_name(),
+                    element_ty: array_ty.element_type(),
+                    len: Some(array_ty.len()),
+                },
+            ),
+            Type::Map(map_ty) => deserializer.deserialize_map(MapVisitor {
+                type_name: map_ty.type_name(),
+                key_ty: map_ty.key_type(),
+                value_ty: map_ty.value_type(),
+            }),
+            Type::Set(set_ty) => deserializer.deserialize_seq(SetVisitor {
+                type_name: set_ty.type_name(),
+                element_ty: set_ty.element_type(),
+            }),
+            Type::Opaque(opaque_ty) => Err(de::Error::custom(format_args!(
+                "`{}` is opaque and cannot be deserialized",
+                opaque_ty.type_name()
+            ))),
+        }
+    }
+}
+
+struct ScalarVisitor(ScalarType);
+
+impl ScalarVisitor {
+    fn integer<E>(self, n: i128) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        let value = match self.0.underlying() {
+            ScalarType::usize => usize::try_from(n).ok().map(Value::usize),
+            ScalarType::u8 => u8::try_from(n).ok().map(Value::u8),
+            ScalarType::u16 => u16::try_from(n).ok().map(Value::u16),
+            ScalarType::u32 => u32::try_from(n).ok().map(Value::u32),
+            ScalarType::u64 => u64::try_from(n).ok().map(Value::u64),
+            ScalarType::u128 => u128::try_from(n).ok().map(Value::u128),
+            ScalarType::i8 => i8::try_from(n).ok().map(Value::i8),
+            ScalarType::i16 => i16::try_from(n).ok().map(Value::i16),
+            ScalarType::i32 => i32::try_from(n).ok().map(Value::i32),
+            ScalarType::i64 => i64::try_from(n).ok().map(Value::i64),
+            ScalarType::i128 => Some(Value::i128(n)),
+            ScalarType::f32 => Some(Value::f32(n as f32)),
+            ScalarType::f64 => Some(Value::f64(n as f64)),
+            ScalarType::bool | ScalarType::char | ScalarType::String => {
+                return Err(E::invalid_type(unexpected_integer(n), &self))
+            }
+            _ => unreachable!("`underlying` returns the scalar `NonZero*` types contain"),
+        };
+        value
+            .filter(|value| self.0.allows(value))
+            .ok_or_else(|| E::invalid_value(unexpected_integer(n), &self))
+    }
+}
+
+fn unexpected_integer(n: i128) -> Unexpected<'static> {
+    if let Ok(n) = u64::try_from(n) {
+        Unexpected::Unsigned(n)
+    } else if let Ok(n) = i64::try_from(n) {
+        Unexpected::Signed(n)
+    } else {
+        Unexpected::Other("integer")
+    }
+}
+
+impl<'de> de::Visitor<'de> for ScalarVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a value of type `{}`", self.0.type_name())
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.0 {
+            ScalarType::bool => Ok(Value::bool(v)),
+            _ => Err(E::invalid_type(Unexpected::Bool(v), &self)),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.integer(v.into())
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.integer(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.integer(v.into())
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match (i128::try_from(v), self.0.underlying()) {
+            (Ok(n), _) => self.integer(n),
+            (Err(_), ScalarType::u128) => Ok(Value::u128(v)),
+            (Err(_), ScalarType::f32) => Ok(Value::f32(v as f32)),
+            (Err(_), ScalarType::f64) => Ok(Value::f64(v as f64)),
+            (Err(_), _) => Err(E::invalid_value(Unexpected::Other("integer"), &self)),
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.0 {
+            ScalarType::f32 => Ok(Value::f32(v as f32)),
+            ScalarType::f64 => Ok(Value::f64(v)),
+            _ => Err(E::invalid_type(Unexpected::Float(v), &self)),
+        }
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.0 {
+            ScalarType::char => Ok(Value::char(v)),
+            ScalarType::String => Ok(Value::String(v.into())),
+            _ => Err(E::invalid_type(Unexpected::Char(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.0 {
+            ScalarType::String => Ok(Value::String(v.to_owned())),
+            // some formats, such as JSON, write chars as strings
+            ScalarType::char => {
+                let mut chars = v.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Value::char(c)),
+                    _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+                }
+            }
+            _ => Err(E::invalid_type(Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.0 {
+            ScalarType::String => Ok(Value::String(v)),
+            _ => self.visit_str(&v),
+        }
+    }
+}
+
+/// Deserializes the fields of a struct or struct variant, in the order of `fields`.
+struct FieldsVisitor<'a, 'b> {
+    type_name: &'a str,
+    fields: &'b [(&'a str, Type<'a>)],
+}
+
+impl<'de> de::Visitor<'de> for FieldsVisitor<'_, '_> {
+    type Value = Vec<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "struct `{}`", self.type_name)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if self.fields.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(E::invalid_type(Unexpected::Unit, &self))
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(self.fields.len());
+        for (index, (_, ty)) in self.fields.iter().enumerate() {
+            let value = seq
+                .next_element_seed(TypedReflectDeserializer::new(*ty))?
+                .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut values = vec![None; self.fields.len()];
+        while let Some(index) = map.next_key_seed(FieldSeed(self.fields))? {
+            let Some(index) = index else {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+            let (name, ty) = self.fields[index];
+            if values[index].is_some() {
+                return Err(de::Error::duplicate_field(intern(name)));
+            }
+            values[index] = Some(map.next_value_seed(TypedReflectDeserializer::new(ty))?);
+        }
+
+        self.fields
+            .iter()
+            .zip(values)
+            .map(|((name, ty), value)| match value {
+                Some(value) => Ok(value),
+                None => match ty.as_enum() {
+                    Some(enum_ty) if enum_ty.is_option() => {
+                        Ok(EnumValue::new_unit_variant("None").into())
+                    }
+                    _ => Err(de::Error::missing_field(intern(name))),
+                },
+            })
+            .collect()
+    }
+}
+
+/// Deserializes a field name, or index, into the index of the field. Unknown fields are `None`.
+struct FieldSeed<'a, 'b>(&'b [(&'a str, Type<'a>)]);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed<'_, '_> {
+    type Value = Option<usize>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> de::Visitor<'de> for FieldSeed<'_, '_> {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a field identifier")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(usize::try_from(v)
+            .ok()
+            .filter(|index| *index < self.0.len()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.0.iter().position(|(name, _)| *name == v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.0.iter().position(|(name, _)| name.as_bytes() == v))
+    }
+}
+
+/// Deserializes the fields of a tuple, tuple struct, or tuple variant.
+struct TupleVisitor<'a> {
+    type_name: &'a str,
+    fields: Vec<Type<'a>>,
+}
+
+impl<'de> de::Visitor<'de> for TupleVisitor<'_> {
+    type Value = Vec<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.type_name)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if self.fields.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(E::invalid_type(Unexpected::Unit, &self))
+        }
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match *self.fields {
+            [ty] => Ok(vec![
+                TypedReflectDeserializer::new(ty).deserialize(deserializer)?
+            ]),
+            _ => Err(de::Error::invalid_type(Unexpected::NewtypeStruct, &self)),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(self.fields.len());
+        for (index, ty) in self.fields.iter().enumerate() {
+            let value = seq
+                .next_element_seed(TypedReflectDeserializer::new(*ty))?
+                .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+struct OptionVisitor<'a>(Type<'a>);
+
+impl<'de> de::Visitor<'de> for OptionVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an optional `{}`", self.0.type_name())
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(EnumValue::new_unit_variant("None").into())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = TypedReflectDeserializer::new(self.0).deserialize(deserializer)?;
+        let mut variant = EnumValue::new_tuple_variant_with_capacity("Some", 1);
+        variant.push_tuple_field(value);
+        Ok(variant.finish().into())
+    }
+}
+
+struct EnumVisitor<'a> {
+    enum_ty: EnumType<'a>,
+    variants: &'static [&'static str],
+}
+
+impl<'de> de::Visitor<'de> for EnumVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "enum `{}`", self.enum_ty.type_name())
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use de::VariantAccess;
+
+        let (variant_ty, access) = data.variant_seed(VariantSeed {
+            enum_ty: self.enum_ty,
+            variants: self.variants,
+        })?;
+        let name = variant_ty.name();
+
+        let variant = match variant_ty {
+            Variant::Struct(_) => {
+                let fields = variant_ty
+                    .field_types()
+                    .map(|field_ty| (field_ty.name().unwrap(), field_ty.get_type()))
+                    .collect::<Vec<_>>();
+                let values = access.struct_variant(
+                    intern_fields(fields.iter().map(|(name, _)| *name)),
+                    FieldsVisitor {
+                        type_name: variant_ty.type_name(),
+                        fields: &fields,
+                    },
+                )?;
+                let mut variant = EnumValue::new_struct_variant_with_capacity(name, fields.len());
+                for ((name, _), value) in fields.iter().zip(values) {
+                    variant.set_struct_field(*name, value);
+                }
+                variant.finish()
+            }
+            Variant::Tuple(_) => {
+                let fields = variant_ty
+                    .field_types()
+                    .map(|field_ty| field_ty.get_type())
+                    .collect::<Vec<_>>();
+                let values = if let [ty] = *fields {
+                    vec![access.newtype_variant_seed(TypedReflectDeserializer::new(ty))?]
+                } else {
+                    access.tuple_variant(
+                        fields.len(),
+                        TupleVisitor {
+                            type_name: variant_ty.type_name(),
+                            fields,
+                        },
+                    )?
+                };
+                let mut variant = EnumValue::new_tuple_variant_with_capacity(name, values.len());
+                for value in values {
+                    variant.push_tuple_field(value);
+                }
+                variant.finish()
+            }
+            Variant::Unit(_) => {
+                access.unit_variant()?;
+                EnumValue::new_unit_variant(name)
+            }
+        };
+        Ok(variant.into())
+    }
+}
+
+/// Deserializes a variant name, or index, into the variant.
+struct VariantSeed<'a> {
+    enum_ty: EnumType<'a>,
+    variants: &'static [&'static str],
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for VariantSeed<'a> {
+    type Value = Variant<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de, 'a> de::Visitor<'de> for VariantSeed<'a> {
+    type Value = Variant<'a>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a variant of `{}`", self.enum_ty.type_name())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        usize::try_from(v)
+            .ok()
+            .and_then(|index| self.enum_ty.variants().nth(index))
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.enum_ty
+            .variant(v)
+            .ok_or_else(|| E::unknown_variant(v, self.variants))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match core::str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+/// Deserializes lists, and arrays if `len` is set.
+struct ListVisitor<'a> {
+    type_name: &'a str,
+    element_ty: Type<'a>,
+    len: Option<usize>,
+}
+
+impl<'de> de::Visitor<'de> for ListVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.type_name)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let capacity = self.len.or(seq.size_hint()).unwrap_or(0);
+        // don't trust size hints of untrusted input
+        let mut elements = Vec::with_capacity(capacity.min(4096));
+        while let Some(element) =
+            seq.next_element_seed(TypedReflectDeserializer::new(self.element_ty))?
+        {
+            elements.push(element);
+        }
+        match self.len {
+            Some(len) if len != elements.len() => {
+                Err(de::Error::invalid_length(elements.len(), &self))
+            }
+            Some(_) | None => Ok(Value::List(elements)),
+        }
+    }
+}
+
+struct MapVisitor<'a> {
+    type_name: &'a str,
+    key_ty: Type<'a>,
+    value_ty: Type<'a>,
+}
+
+impl<'de> de::Visitor<'de> for MapVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.type_name)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = BTreeMap::new();
+        while let Some((key, value)) = map.next_entry_seed(
+            TypedReflectDeserializer::new(self.key_ty),
+            TypedReflectDeserializer::new(self.value_ty),
+        )? {
+            entries.insert(key, value);
+        }
+        Ok(Value::Map(entries))
+    }
+}
+
+struct SetVisitor<'a> {
+    type_name: &'a str,
+    element_ty: Type<'a>,
+}
+
+impl<'de> de::Visitor<'de> for SetVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.type_name)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut elements = BTreeSet::new();
+        while let Some(element) =
+            seq.next_element_seed(TypedReflectDeserializer::new(self.element_ty))?
+        {
+            elements.insert(element);
+        }
+        Ok(Value::Set(elements))
+    }
+}
+
+// serde requires type, field, and variant names to be `&'static str`. Names are leaked once and
+// reused, so only the number of distinct names is leaked, not the number of values.
+fn intern(name: &str) -> &'static str {
+    static NAMES: OnceBox<RwLock<BTreeSet<&'static str>>> = OnceBox::new();
+
+    let names = NAMES.get_or_init(Default::default);
+    if let Some(name) = names.read().unwrap().get(name) {
+        return name;
+    }
+
+    let mut names = names.write().unwrap();
+    if let Some(name) = names.get(name) {
+        return name;
+    }
+    let name: &'static str = Box::leak(name.into());
+    names.insert(name);
+    name
+}
+
+fn intern_fields<'a>(names: impl Iterator<Item = &'a str>) -> &'static [&'static str] {
+    static FIELDS: OnceBox<RwLock<BTreeSet<&'static [&'static str]>>> = OnceBox::new();
+
+    let names = names.map(intern).collect::<Vec<_>>();
+    let fields = FIELDS.get_or_init(Default::default);
+    if let Some(fields) = fields.read().unwrap().get(&*names) {
+        return fields;
+    }
+
+    let mut fields = fields.write().unwrap();
+    if let Some(fields) = fields.get(&*names) {
+        return fields;
+    }
+    let names: &'static [&'static str] = Box::leak(names.into_boxed_slice());
+    fields.insert(names);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use core::num::NonZeroU32;
+    use core::time::Duration;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::DescribeType;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Save {
+        name: String,
+        level: u8,
+        offset: i64,
+        position: [f32; 3],
+        inventory: Vec<Item>,
+        flags: BTreeSet<char>,
+        scores: HashMap<String, u128>,
+        pair: (bool, f64),
+        unit: (),
+        id: Id,
+        point: Point,
+        marker: Marker,
+        best: Option<i128>,
+        worst: Option<Id>,
+    }
+
+    #[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        Sword { damage: u32 },
+        Potion(u16, u16),
+        Gold(usize),
+        Key,
+    }
+
+    #[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Id(usize);
+
+    #[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Point(i32, i32);
+
+    #[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Marker;
+
+    fn save() -> Save {
+        Save {
+            name: "Ferris".to_owned(),
+            level: 12,
+            offset: -300,
+            position: [1.0, 2.0, 3.0],
+            inventory: vec![
+                Item::Sword { damage: 50 },
+                Item::Potion(1, 2),
+                Item::Gold(100),
+                Item::Key,
+            ],
+            flags: BTreeSet::from(['a', '🦀']),
+            scores: HashMap::from([("level 1".to_owned(), u128::MAX)]),
+            pair: (true, -0.5),
+            unit: (),
+            id: Id(1234),
+            point: Point(-1, 1),
+            marker: Marker,
+            best: Some(i128::MIN),
+            worst: None,
+        }
+    }
+
+    fn deserialize<'de, T, D>(deserializer: D) -> T
+    where
+        T: FromReflect + DescribeType,
+        D: Deserializer<'de>,
+        D::Error: fmt::Debug,
+    {
+        let ty = <T as DescribeType>::type_descriptor();
+        let value = TypedReflectDeserializer::new(ty.get_type())
+            .deserialize(deserializer)
+            .unwrap();
+        T::from_reflect(&value).unwrap()
+    }
+
+    #[test]
+    fn json_matches_derive() {
+        let save = save();
+
+        let json = serde_json::to_string(&ReflectSerializer(&save)).unwrap();
+        assert_eq!(json, serde_json::to_string(&save).unwrap());
+
+        let save_again: Save = deserialize(&mut serde_json::Deserializer::from_str(&json));
+        assert_eq!(save_again, save);
+
+        // values serialize like the type they came from
+        let value = save.to_value();
+        assert_eq!(
+            serde_json::to_string(&ReflectSerializer(&value)).unwrap(),
+            json
+        );
+    }
+
+    #[test]
+    fn bincode_matches_derive() {
+        use bincode::Options;
+
+        let save = save();
+
+        let bytes = bincode::serialize(&ReflectSerializer(&save)).unwrap();
+        assert_eq!(bytes, bincode::serialize(&save).unwrap());
+
+        let save_again: Save = deserialize(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .allow_trailing_bytes(),
+        ));
+        assert_eq!(save_again, save);
+
+        // enum values don't know their variant index
+        let err = bincode::serialize(&ReflectSerializer(&Item::Key.to_value())).unwrap_err();
+        assert!(err.to_string().contains("variant `Key`"));
+    }
+
+    #[test]
+    fn scalar_backed_fields() {
+        use bincode::Options;
+
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        #[reflect(crate_name(crate))]
+        struct Timer {
+            elapsed: Duration,
+            ticks: NonZeroU32,
+        }
+
+        let timer = Timer {
+            elapsed: Duration::from_millis(1500),
+            ticks: NonZeroU32::new(3).unwrap(),
+        };
+
+        let json = serde_json::to_string(&ReflectSerializer(&timer)).unwrap();
+        assert_eq!(json, r#"{"elapsed":1500000000,"ticks":3}"#);
+        let timer_again: Timer = deserialize(&mut serde_json::Deserializer::from_str(&json));
+        assert_eq!(timer_again, timer);
+
+        let bytes = bincode::serialize(&ReflectSerializer(&timer)).unwrap();
+        let timer_again: Timer = deserialize(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .allow_trailing_bytes(),
+        ));
+        assert_eq!(timer_again, timer);
+    }
+
+    #[test]
+    fn looks_through_guards() {
+        #[derive(Reflect, Debug)]
+        #[reflect(crate_name(crate), opt_out(Clone))]
+        struct Shared {
+            inner: Mutex<Vec<u8>>,
+            name: Box<str>,
+        }
+
+        let shared = Shared {
+            inner: Mutex::new(vec![1, 2]),
+            name: "a".into(),
+        };
+        assert_eq!(
+            serde_json::to_string(&ReflectSerializer(&shared)).unwrap(),
+            r#"{"inner":[1,2],"name":"a"}"#
+        );
+    }
+
+    #[test]
+    fn lenient_like_derive() {
+        // missing options are `None`, unknown fields are ignored, and numbers are converted
+        let save: Save = deserialize(&mut serde_json::Deserializer::from_str(
+            r#"{
+                "name": "Ferris",
+                "level": 12,
+                "offset": -300,
+                "position": [1, 2, 3],
+                "inventory": ["Key", {"Gold": 100}],
+                "flags": ["a"],
+                "scores": {},
+                "pair": [true, 1],
+                "unit": null,
+                "id": 1,
+                "point": [0, 0],
+                "marker": null,
+                "extra": {"ignored": true}
+            }"#,
+        ));
+        assert_eq!(save.position, [1.0, 2.0, 3.0]);
+        assert_eq!(save.inventory, [Item::Key, Item::Gold(100)]);
+        assert_eq!(save.best, None);
+    }
+
+    #[test]
+    fn errors() {
+        fn error<T>(json: &str) -> String
+        where
+            T: DescribeType,
+        {
+            let ty = <T as DescribeType>::type_descriptor();
+            TypedReflectDeserializer::new(ty.get_type())
+                .deserialize(&mut serde_json::Deserializer::from_str(json))
+                .unwrap_err()
+                .to_string()
+        }
+
+        assert_eq!(
+            error::<u8>("256"),
+            "invalid value: integer `256`, expected a value of type `u8` at line 1 column 3"
+        );
+        assert_eq!(
+            error::<Item>(r#""Shield""#),
+            "unknown variant `Shield`, expected one of `Sword`, `Potion`, `Gold`, `Key` at line 1 column 8"
+        );
+        assert_eq!(
+            error::<Id>("[1]"),
+            "invalid type: sequence, expected a value of type `usize` at line 1 column 0"
+        );
+        assert!(error::<Save>("{}").starts_with("missing field `name`"));
+        assert!(error::<NonZeroU32>("0").starts_with("invalid value: integer `0`"));
+        assert!(error::<[u8; 2]>("[1, 2, 3]").starts_with("invalid length 3"));
+
+        assert_eq!(
+            error::<StructValue>("{}"),
+            "`mirror_mirror::struct_::StructValue` is opaque and cannot be deserialized"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::Reflect;
+
+/// A reflected set type.
+///
+/// Implemented for [`BTreeSet`] and, with the `std` feature, [`HashSet`]. [`Value::Set`] is a
+/// [`BTreeSet`] so converting a [`HashSet`] to a [`Value`] orders the elements.
+///
+/// Elements of a set cannot be mutated in place since that could change their position in the
+/// set, so there is no `iter_mut`. Remove the element and insert a new one instead.
+///
+/// [`BTreeSet`]: alloc::collections::BTreeSet
+/// [`HashSet`]: std::collections::HashSet
+/// [`Value::Set`]: crate::Value::Set
+/// [`Value`]: crate::Value
+pub trait Set: Reflect {
+    /// Returns the element in the set that is equal to `value`, if any.
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect>;
+
+    fn contains(&self, value: &dyn Reflect) -> bool;
+
+    /// Insert `value` into the set.
+    ///
+    /// Returns `false` if the set already contained the value or if `value` couldn't be converted
+    /// into the element type.
+    fn insert(&mut self, value: &dyn Reflect) -> bool;
+
+    fn remove(&mut self, value: &dyn Reflect) -> Option<Box<dyn Reflect>>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool;
+
+    fn iter(&self) -> Iter<'_>;
+}
+
+impl fmt::Debug for dyn Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_reflect().debug(f)
+    }
+}
+
+pub type Iter<'a> = Box<dyn Iterator<Item = &'a dyn Reflect> + 'a>;
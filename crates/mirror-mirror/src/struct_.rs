@@ -34,6 +34,10 @@ pub trait Struct: Reflect {
 
     fn fields(&self) -> Iter<'_>;
 
+    /// Mutable references to all fields at once.
+    ///
+    /// Types whose fields can only be borrowed one at a time, such as glam's `Quat` and `Mat2`,
+    /// return no fields here. Use [`Struct::field_at_mut`] to reach every field of any struct.
     fn fields_mut(&mut self) -> PairIterMut<'_>;
 
     fn fields_len(&self) -> usize;
@@ -74,7 +78,7 @@ impl StructValue {
 
     pub fn set_field(&mut self, name: impl Into<String>, value: impl Into<Value>) {
         let name = name.into();
-        if !self.field_names.contains(&name) {
+        if !self.fields.contains_key(&name) {
             self.field_names.push(name.clone());
         }
         self.fields.insert(name, value.into());
@@ -134,12 +134,12 @@ fn patching() {
     // whole: static.patch(static)
     let mut foo = Foo::A { a: 1 };
     foo.patch(&Foo::B { b: false });
-    assert!(matches!(dbg!(foo), Foo::B { b: false }));
+    assert!(matches!(foo, Foo::B { b: false }));
 
     // part: static.patch(static)
     let mut foo = Foo::A { a: 1 };
     foo.patch(&Foo::A { a: 42 });
-    assert!(matches!(dbg!(foo), Foo::A { a: 42 }));
+    assert!(matches!(foo, Foo::A { a: 42 }));
 
     // whole: static.patch(value)
     let mut foo = Foo::A { a: 1 };
@@ -148,7 +148,7 @@ fn patching() {
             .with_struct_field("b", false)
             .finish(),
     );
-    assert!(matches!(dbg!(foo), Foo::B { b: false }));
+    assert!(matches!(foo, Foo::B { b: false }));
 
     // part: static.patch(value)
     let mut foo = Foo::A { a: 1 };
@@ -157,7 +157,7 @@ fn patching() {
             .with_struct_field("a", 42)
             .finish(),
     );
-    assert!(matches!(dbg!(foo), Foo::A { a: 42 }));
+    assert!(matches!(foo, Foo::A { a: 42 }));
 
     // whole: value.patch(static)
     let mut foo = EnumValue::new_struct_variant("A")
@@ -222,12 +222,12 @@ fn static_tuple_enum() {
     // whole: static.patch(static)
     let mut foo = Foo::A(1, true);
     foo.patch(&Foo::B("foo".to_owned()));
-    assert!(matches!(dbg!(foo), Foo::B(s) if s == "foo"));
+    assert!(matches!(foo, Foo::B(s) if s == "foo"));
 
     // part: static.patch(static)
     let mut foo = Foo::A(1, true);
     foo.patch(&Foo::A(42, true));
-    assert!(matches!(dbg!(foo), Foo::A(42, true)));
+    assert!(matches!(foo, Foo::A(42, true)));
 
     // whole: static.patch(value)
     let mut foo = Foo::A(1, true);
@@ -236,7 +236,7 @@ fn static_tuple_enum() {
             .with_tuple_field("foo")
             .finish(),
     );
-    assert!(matches!(dbg!(foo), Foo::B(s) if s == "foo"));
+    assert!(matches!(foo, Foo::B(s) if s == "foo"));
 
     // part: static.patch(value)
     let mut foo = Foo::A(1, true);
@@ -245,7 +245,7 @@ fn static_tuple_enum() {
             .with_tuple_field(42)
             .finish(),
     );
-    assert!(matches!(dbg!(foo), Foo::A(42, true)));
+    assert!(matches!(foo, Foo::A(42, true)));
 
     // whole: value.patch(static)
     let mut foo = EnumValue::new_tuple_variant("A")
@@ -281,12 +281,12 @@ fn unit_variant() {
     // static.patch(static)
     let mut foo = Foo::A;
     foo.patch(&Foo::B);
-    assert!(matches!(dbg!(foo), Foo::B));
+    assert!(matches!(foo, Foo::B));
 
     // static.patch(value)
     let mut foo = Foo::A;
     foo.patch(&EnumValue::new_unit_variant("B"));
-    assert!(matches!(dbg!(foo), Foo::B));
+    assert!(matches!(foo, Foo::B));
 
     // value.patch(static)
     let mut foo = EnumValue::new_unit_variant("A");
@@ -301,7 +301,7 @@ fn unit_variant() {
     let mut foo = Foo::A;
 
     foo.patch(&EnumValue::new_unit_variant("Unknown"));
-    assert!(matches!(dbg!(&foo), Foo::A));
+    assert!(matches!(&foo, Foo::A));
 
     let value = foo.to_value();
     let value = value.reflect_ref().as_enum().unwrap();
@@ -471,3 +471,18 @@ fn default_value_for_enum_variant_type() {
         Foo::C { a: 0.0, b: None },
     );
 }
+
+#[test]
+fn recursive_enum() {
+    #[derive(Debug, Clone, Reflect, PartialEq)]
+    #[reflect(crate_name(crate))]
+    pub enum Chain {
+        Link(Box<Chain>),
+        End,
+    }
+
+    let type_ = <Chain as DescribeType>::type_descriptor();
+    let link = type_.as_enum().unwrap().variant("Link").unwrap();
+    let field_type = link.field_types().next().unwrap().get_type();
+    assert_eq!(field_type.type_name(), type_.get_type().type_name());
+}
@@ -0,0 +1,328 @@
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::time::Duration;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use std::thread;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::GetTypePath;
+use crate::key_path::KeyPathErrorKind;
+use crate::key_path::Query;
+use crate::reflect_diff;
+use crate::reflect_eq;
+use crate::try_visit::try_visit;
+use crate::try_visit::TryVisit;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+
+#[derive(Reflect, Debug)]
+#[reflect(crate_name(crate), opt_out(Clone))]
+struct Foo {
+    cell: Cell<i32>,
+    ref_cell: RefCell<Bar>,
+    mutex: Mutex<Bar>,
+    rw_lock: RwLock<Vec<i32>>,
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Bar {
+    n: i32,
+}
+
+fn foo() -> Foo {
+    Foo {
+        cell: Cell::new(1),
+        ref_cell: RefCell::new(Bar { n: 2 }),
+        mutex: Mutex::new(Bar { n: 3 }),
+        rw_lock: RwLock::new(Vec::from([4, 5])),
+    }
+}
+
+#[test]
+fn type_info_is_transparent() {
+    let foo_type = <Foo as DescribeType>::type_descriptor();
+    let bar_type = <Bar as DescribeType>::type_descriptor();
+    for path in [key_path!(.ref_cell), key_path!(.mutex)] {
+        assert_eq!(
+            foo_type.type_at(&path).unwrap().type_name(),
+            bar_type.type_name()
+        );
+    }
+    assert!(foo_type
+        .type_at(&key_path!(.rw_lock[0]))
+        .unwrap()
+        .as_scalar()
+        .is_some());
+}
+
+#[test]
+fn to_value_and_from_reflect() {
+    let foo = foo();
+
+    assert_eq!(foo.cell.to_value(), 1.to_value());
+    assert_eq!(foo.ref_cell.to_value(), Bar { n: 2 }.to_value());
+    assert_eq!(foo.mutex.to_value(), Bar { n: 3 }.to_value());
+    assert_eq!(foo.rw_lock.to_value(), Vec::from([4, 5]).to_value());
+
+    let new_foo = Foo::from_reflect(&foo.to_value()).unwrap();
+    assert_eq!(new_foo.to_value(), foo.to_value());
+
+    let cloned = foo.clone_reflect();
+    assert_eq!(cloned.to_value(), foo.to_value());
+
+    assert_eq!(
+        Bar::from_reflect(&*foo.mutex.reflect_guard()).unwrap(),
+        Bar { n: 3 }
+    );
+}
+
+#[test]
+fn shared_access_needs_a_guard() {
+    let foo = foo();
+
+    assert!(foo.get_at::<i32>(&key_path!(.ref_cell.n)).is_none());
+    let err = foo.try_at(&key_path!(.mutex.n)).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::Locked);
+    assert_eq!(err.resolved(), &key_path!(.mutex));
+
+    let guard = foo.at(&key_path!(.mutex)).unwrap().reflect_guard();
+    assert_eq!(guard.get_at::<i32>(&key_path!(.n)).unwrap(), &3);
+    drop(guard);
+
+    let guard = foo.rw_lock.reflect_guard();
+    assert_eq!(guard.get_at::<i32>(&key_path!([1])).unwrap(), &5);
+
+    // the lock is held while the guard is alive
+    let guard = foo.ref_cell.reflect_guard();
+    assert!(foo.ref_cell.try_borrow_mut().is_err());
+    drop(guard);
+    assert!(foo.ref_cell.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn mutable_access_is_transparent() {
+    let mut foo = foo();
+
+    *foo.get_at_mut::<Cell<i32>>(&key_path!(.cell))
+        .unwrap()
+        .get_mut() = 10;
+    *foo.get_at_mut::<i32>(&key_path!(.ref_cell.n)).unwrap() = 20;
+    *foo.get_at_mut::<i32>(&key_path!(.mutex.n)).unwrap() = 30;
+    foo.at_mut(&key_path!(.rw_lock))
+        .unwrap()
+        .as_list_mut()
+        .unwrap()
+        .push(&6);
+
+    assert_eq!(foo.cell.get(), 10);
+    assert_eq!(foo.ref_cell.borrow().n, 20);
+    assert_eq!(foo.mutex.lock().unwrap().n, 30);
+    assert_eq!(*foo.rw_lock.read().unwrap(), [4, 5, 6]);
+
+    let err = foo.try_at_mut(&key_path!(.mutex.missing)).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::NoSuchField);
+    assert_eq!(err.resolved(), &key_path!(.mutex));
+}
+
+#[test]
+fn patch_eq_and_diff() {
+    let mut a = foo();
+    let b = foo();
+    assert!(reflect_eq(&a, &b).unwrap());
+
+    b.mutex.lock().unwrap().n = 100;
+    assert!(!reflect_eq(&a, &b).unwrap());
+
+    let changes = reflect_diff(&a, &b).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].path(), &key_path!(.mutex.n));
+
+    a.patch(&b);
+    assert_eq!(a.mutex.lock().unwrap().n, 100);
+    assert!(reflect_eq(&a, &b).unwrap());
+
+    a.try_patch(&b.to_value()).unwrap();
+}
+
+#[test]
+fn downcasting_finds_the_wrapper() {
+    let mut foo = foo();
+
+    assert!(foo.mutex.as_any().is::<Mutex<Bar>>());
+    assert!(foo.mutex.as_any_mut().is::<Mutex<Bar>>());
+    assert!(foo
+        .at(&key_path!(.mutex))
+        .unwrap()
+        .downcast_ref::<Mutex<Bar>>()
+        .is_some());
+    assert!(foo
+        .at_mut(&key_path!(.mutex))
+        .unwrap()
+        .downcast_mut::<Mutex<Bar>>()
+        .is_some());
+    assert!(foo.get_at_mut::<Bar>(&key_path!(.mutex)).is_none());
+}
+
+#[test]
+fn lookups_through_locks() {
+    let foo = foo();
+
+    assert_eq!(
+        foo.with_at(&key_path!(.mutex.n), |n| n.downcast_ref::<i32>().copied()),
+        Some(Some(3))
+    );
+    assert_eq!(
+        foo.with_at(&key_path!(.ref_cell), Bar::from_reflect),
+        Some(Some(Bar { n: 2 }))
+    );
+    assert!(foo.with_at(&key_path!(.mutex.missing), |_| ()).is_none());
+
+    let mut matches = Vec::new();
+    foo.query_with(&".**".parse::<Query>().unwrap(), |path, value| {
+        if let Some(n) = value.downcast_ref::<i32>() {
+            matches.push((path.to_string(), *n));
+        }
+    });
+    assert_eq!(
+        matches,
+        [
+            (String::from(".cell"), 1),
+            (String::from(".ref_cell.n"), 2),
+            (String::from(".mutex.n"), 3),
+            (String::from(".rw_lock[0_usize]"), 4),
+            (String::from(".rw_lock[1_usize]"), 5),
+        ]
+    );
+
+    // locks that are already held are skipped
+    let _guard = foo.mutex.lock().unwrap();
+    assert!(foo.with_at(&key_path!(.mutex.n), |_| ()).is_none());
+    let mut count = 0;
+    foo.query_with(&".mutex.n".parse::<Query>().unwrap(), |_, _| count += 1);
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn values_are_only_locked_once() {
+    let foo = foo();
+
+    assert_eq!(reflect_eq(&foo, &foo), Some(true));
+    assert_eq!(reflect_eq(&foo.mutex, &foo.mutex), Some(true));
+    assert_eq!(reflect_eq(&foo.ref_cell, &foo.ref_cell), Some(true));
+    assert!(reflect_diff(&foo, &foo).unwrap().is_empty());
+    assert!(reflect_diff(&foo.mutex, &foo.mutex).unwrap().is_empty());
+
+    // patching a value with itself fails rather than deadlocking or panicking
+    let mut bar = foo.mutex.lock().unwrap();
+    assert!(bar.try_patch(&foo.mutex).is_err());
+    drop(bar);
+
+    let mut bar = foo.ref_cell.borrow_mut();
+    assert!(bar.try_patch(&foo.ref_cell).is_err());
+    drop(bar);
+}
+
+#[test]
+fn locked_values_are_opaque() {
+    let foo = foo();
+
+    let bar = foo.ref_cell.borrow_mut();
+    assert!(RefCell::<Bar>::from_reflect(&foo.ref_cell).is_none());
+    assert!(reflect_eq(&foo.ref_cell, &foo.ref_cell).is_none());
+    let err = foo.try_at(&key_path!(.ref_cell.n)).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::Locked);
+    drop(bar);
+
+    let bar = foo.mutex.lock().unwrap();
+    assert!(Mutex::<Bar>::from_reflect(&foo.mutex).is_none());
+    drop(bar);
+    let mutex = Mutex::<Bar>::from_reflect(&foo.mutex).unwrap();
+    assert_eq!(mutex.into_inner().unwrap(), Bar { n: 3 });
+}
+
+#[test]
+fn to_value_waits_for_locks() {
+    let foo = foo();
+
+    let (locked_tx, locked_rx) = mpsc::channel();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            let mut bar = foo.mutex.lock().unwrap();
+            locked_tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            bar.n = 30;
+        });
+
+        locked_rx.recv().unwrap();
+        assert_eq!(foo.mutex.to_value(), Bar { n: 30 }.to_value());
+    });
+
+    let new_foo = Foo::from_reflect(&foo.to_value()).unwrap();
+    assert_eq!(new_foo.mutex.into_inner().unwrap(), Bar { n: 30 });
+}
+
+#[test]
+#[should_panic = "already mutably borrowed"]
+fn to_value_panics_for_mutably_borrowed_ref_cells() {
+    let foo = foo();
+    let _bar = foo.ref_cell.borrow_mut();
+    foo.to_value();
+}
+
+#[test]
+fn visit() {
+    #[derive(Default)]
+    struct Collect(Vec<i32>);
+
+    impl TryVisit for Collect {
+        type Error = Infallible;
+
+        fn try_visit_i32(&mut self, value: i32) -> Result<(), Self::Error> {
+            self.0.push(value);
+            Ok(())
+        }
+    }
+
+    let foo = foo();
+    let mut visitor = Collect::default();
+    try_visit(
+        &mut visitor,
+        &foo,
+        <Foo as DescribeType>::type_descriptor().get_type(),
+    )
+    .unwrap();
+    assert_eq!(visitor.0, [1, 2, 3, 4, 5]);
+
+    // locked values are skipped by default
+    let _guard = foo.mutex.lock().unwrap();
+    let mut visitor = Collect::default();
+    try_visit(
+        &mut visitor,
+        &foo,
+        <Foo as DescribeType>::type_descriptor().get_type(),
+    )
+    .unwrap();
+    assert_eq!(visitor.0, [1, 2, 4, 5]);
+}
+
+#[test]
+fn debug() {
+    let foo = foo();
+    assert_eq!(format!("{:?}", foo.mutex.as_reflect()), "Bar { n: 3 }");
+
+    let guard = foo.mutex.lock().unwrap();
+    assert_eq!(format!("{:?}", foo.mutex.as_reflect()), "<locked>");
+    drop(guard);
+
+    let _guard = foo.ref_cell.borrow_mut();
+    assert_eq!(format!("{:?}", foo.ref_cell.as_reflect()), "<locked>");
+}
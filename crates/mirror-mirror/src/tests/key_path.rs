@@ -6,6 +6,7 @@ use crate::type_info::ScalarType;
 use crate::type_info::TypeAtPath;
 use crate::DescribeType;
 use crate::Reflect;
+use crate::Value;
 
 #[test]
 #[allow(clippy::bool_assert_comparison)]
@@ -67,6 +68,57 @@ fn display() {
     );
 }
 
+#[test]
+fn macro_syntax() {
+    let index = 2;
+    assert_eq!(
+        key_path!(.r#type.0.1[index + 1]::Some),
+        KeyPath::default()
+            .field("type")
+            .field(0)
+            .field(1)
+            .get(3)
+            .variant("Some"),
+    );
+    assert_eq!(key_path!(), KeyPath::default());
+    assert_eq!(key_path!([["a", "b"].len()]), key_path!([2_usize]));
+}
+
+#[test]
+fn typed_macro() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        bar: Vec<Option<(i32, Bar)>>,
+        map: BTreeMap<String, Bar>,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        A { a: bool },
+    }
+
+    assert_eq!(
+        key_path!(Foo, .bar[0]::Some.0.1::A.a),
+        key_path!(.bar[0]::Some.0.1::A.a),
+    );
+    assert_eq!(key_path!(Foo, .map["x"]), key_path!(.map["x"]));
+    assert_eq!(key_path!(Foo,), KeyPath::default());
+}
+
+#[test]
+#[should_panic = "`.bar[0]::Some.0.2` doesn't exist in"]
+fn typed_macro_panics_on_missing_path() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        bar: Vec<Option<(i32, bool)>>,
+    }
+
+    key_path!(Foo, .bar[0]::Some.0.2);
+}
+
 #[test]
 fn query_type_info_struct() {
     #[derive(Reflect, Clone, Debug)]
@@ -102,7 +154,7 @@ fn query_type_info_struct() {
     let type_info = <User as DescribeType>::type_descriptor();
 
     assert!(matches!(
-        dbg!(type_info.type_at(&key_path).unwrap()),
+        type_info.type_at(&key_path).unwrap(),
         TypeAtPath::Scalar(ScalarType::String)
     ));
 }
@@ -118,16 +170,16 @@ fn query_type_info_enum() {
     }
 
     assert!(matches!(
-        dbg!(<Foo as DescribeType>::type_descriptor()
+        <Foo as DescribeType>::type_descriptor()
             .type_at(&key_path!(::A.a))
-            .unwrap()),
+            .unwrap(),
         TypeAtPath::Scalar(ScalarType::String)
     ));
 
     assert!(matches!(
-        dbg!(<Foo as DescribeType>::type_descriptor()
+        <Foo as DescribeType>::type_descriptor()
             .type_at(&key_path!(::B.0))
-            .unwrap()),
+            .unwrap(),
         TypeAtPath::Scalar(ScalarType::i32)
     ));
 
@@ -172,3 +224,433 @@ fn breadcrumbs() {
         assert_eq!(a, b);
     }
 }
+
+#[test]
+fn parse_round_trips() {
+    let paths = [
+        key_path!(.a.0.b.c[1]["foo"]::D.e[3]),
+        KeyPath::default()
+            .field("snake_case")
+            .field(10)
+            .variant("Some"),
+        KeyPath::default()
+            .get(1_usize)
+            .get(1_u8)
+            .get(1_u16)
+            .get(1_u32),
+        KeyPath::default().get(1_u64).get(u128::MAX),
+        KeyPath::default()
+            .get(-1_i8)
+            .get(-1_i16)
+            .get(i32::MIN)
+            .get(-1_i64),
+        KeyPath::default().get(i128::MIN),
+        KeyPath::default()
+            .get(1.5_f32)
+            .get(-2.0_f64)
+            .get(1e-7_f64)
+            .get(f32::INFINITY),
+        KeyPath::default().get(f64::NEG_INFINITY),
+        KeyPath::default().get(true).get(false),
+        KeyPath::default().get('a').get('\'').get('\n'),
+        KeyPath::default().get("").get("quote \" and \\ backslash"),
+        KeyPath::default().get("tab\tnewline\n null\0 bell\u{7} ünïcödé"),
+        KeyPath::default(),
+    ];
+
+    for path in paths {
+        let string = path.to_string();
+        assert_eq!(string.parse::<KeyPath>().unwrap(), path, "{string}");
+    }
+
+    let nan = key_path::parse("[NaN_f32]").unwrap();
+    assert!(matches!(
+        nan.iter().next(),
+        Some(Key::Get(Value::f32(n))) if n.is_nan()
+    ));
+}
+
+#[test]
+fn parse_literals() {
+    assert_eq!(key_path::parse("[1]").unwrap(), key_path!([1_i32]));
+    assert_eq!(key_path::parse("[1.0]").unwrap(), key_path!([1.0_f64]));
+    assert_eq!(key_path::parse("[1e3]").unwrap(), key_path!([1000.0_f64]));
+    assert_eq!(key_path::parse("[1u8]").unwrap(), key_path!([1_u8]));
+    assert_eq!(
+        key_path::parse("[1_000_u32]").unwrap(),
+        key_path!([1000_u32])
+    );
+    assert_eq!(
+        key_path::parse("[ 2_usize ]").unwrap(),
+        key_path!([2_usize])
+    );
+    assert_eq!(
+        key_path::parse(r#"["\u{1F980}"]"#).unwrap(),
+        key_path!(["🦀"])
+    );
+    assert_eq!(key_path::parse("[1].0").unwrap(), key_path!([1].0));
+}
+
+#[test]
+fn parse_errors() {
+    #[track_caller]
+    fn assert_error(input: &str, column: usize, kind: ParseErrorKind) {
+        let err = key_path::parse(input).unwrap_err();
+        assert_eq!((err.column(), err.kind()), (column, kind), "{input}: {err}");
+    }
+
+    assert_error("a", 1, ParseErrorKind::ExpectedKey);
+    assert_error(".a b", 3, ParseErrorKind::ExpectedKey);
+    assert_error(".", 2, ParseErrorKind::ExpectedField);
+    assert_error(".a.-", 4, ParseErrorKind::ExpectedField);
+    assert_error("::", 3, ParseErrorKind::ExpectedVariant);
+    assert_error("[1", 3, ParseErrorKind::ExpectedCloseBracket);
+    assert_error(".a[]", 4, ParseErrorKind::ExpectedLiteral);
+    assert_error(".a[foo]", 4, ParseErrorKind::ExpectedLiteral);
+    assert_error(".a[1_foo]", 5, ParseErrorKind::InvalidSuffix);
+    assert_error(".a[1.5_u8]", 7, ParseErrorKind::InvalidSuffix);
+    assert_error(".a[256_u8]", 4, ParseErrorKind::InvalidNumber);
+    assert_error(".a[-1_usize]", 4, ParseErrorKind::InvalidNumber);
+    assert_error(".a[3000000000]", 4, ParseErrorKind::InvalidNumber);
+    assert_error(r#".a["foo"#, 8, ParseErrorKind::UnterminatedString);
+    assert_error(r#".a["\q"]"#, 5, ParseErrorKind::InvalidEscape);
+    assert_error(".a['ab']", 6, ParseErrorKind::InvalidChar);
+    assert_error(".a['']", 4, ParseErrorKind::InvalidChar);
+    // columns count characters, not bytes
+    assert_error(r#"["ü"]x"#, 6, ParseErrorKind::ExpectedKey);
+
+    assert_eq!(
+        key_path::parse(".a b").unwrap_err().to_string(),
+        "expected `.`, `[`, or `::` at column 3"
+    );
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Node {
+    name: String,
+    health: i32,
+    children: Vec<Node>,
+    tags: BTreeMap<String, Tag>,
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+enum Tag {
+    Health { health: i32 },
+    Empty,
+}
+
+fn node(name: &str, health: i32, children: impl Into<Vec<Node>>) -> Node {
+    Node {
+        name: name.to_owned(),
+        health,
+        children: children.into(),
+        tags: BTreeMap::new(),
+    }
+}
+
+fn query_paths(value: &dyn Reflect, query: &str) -> Vec<String> {
+    let query = query.parse::<Query>().unwrap();
+    value
+        .query(&query)
+        .map(|(path, matched)| {
+            assert_eq!(
+                crate::reflect_eq(value.at(&path).unwrap(), matched),
+                Some(true)
+            );
+            path.to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn query_any_element() {
+    let root = node("root", 1, [node("a", 2, []), node("b", 3, [])]);
+
+    assert_eq!(
+        query_paths(&root, ".children[*].name"),
+        [".children[0_usize].name", ".children[1_usize].name"]
+    );
+    assert_eq!(query_paths(&root, ".children[ * ]").len(), 2);
+    assert!(query_paths(&root, ".name[*]").is_empty());
+}
+
+#[test]
+fn query_any_field() {
+    let mut root = node("root", 1, []);
+    root.tags
+        .insert("hp".to_owned(), Tag::Health { health: 10 });
+
+    assert_eq!(
+        query_paths(&root, ".*"),
+        [".name", ".health", ".children", ".tags"]
+    );
+    assert_eq!(
+        query_paths(&root, r#".tags[*].*"#),
+        [r#".tags["hp"]::Health.health"#]
+    );
+    assert_eq!(
+        query_paths(&root, r#".tags[*]::Health.*"#),
+        [r#".tags["hp"]::Health.health"#]
+    );
+    assert_eq!(
+        query_paths(&root, r#".tags[*].health"#),
+        [r#".tags["hp"]::Health.health"#]
+    );
+    assert!(query_paths(&root, r#".tags[*]::Empty.*"#).is_empty());
+    assert_eq!(query_paths(&(1, "a".to_owned()), ".*"), [".0", ".1"]);
+}
+
+#[test]
+fn query_descendants() {
+    let mut root = node(
+        "root",
+        1,
+        [node("a", 2, [node("c", 4, [])]), node("b", 3, [])],
+    );
+    root.children[1]
+        .tags
+        .insert("hp".to_owned(), Tag::Health { health: 10 });
+
+    assert_eq!(
+        query_paths(&root, ".**.health"),
+        [
+            ".health",
+            ".children[0_usize].health",
+            ".children[0_usize].children[0_usize].health",
+            ".children[1_usize].health",
+            r#".children[1_usize].tags["hp"]::Health.health"#,
+        ]
+    );
+
+    // `.**` matches the value itself first
+    let all = query_paths(&root, ".**");
+    assert_eq!(all[0], "");
+    assert_eq!(all[1], ".name");
+    assert_eq!(all, query_paths(&root, ".**.**"));
+}
+
+#[test]
+fn query_mut() {
+    let mut root = node(
+        "root",
+        1,
+        [node("a", 2, [node("c", 4, [])]), node("b", 3, [])],
+    );
+    root.tags
+        .insert("hp".to_owned(), Tag::Health { health: 10 });
+
+    let mut paths = Vec::new();
+    root.query_mut(&".**.health".parse().unwrap(), |path, value| {
+        paths.push(path.to_string());
+        value.patch(&0);
+    });
+
+    assert_eq!(
+        paths,
+        [
+            ".health",
+            ".children[0_usize].health",
+            ".children[0_usize].children[0_usize].health",
+            ".children[1_usize].health",
+            r#".tags["hp"]::Health.health"#,
+        ]
+    );
+
+    let immutable = root
+        .query(&".**.health".parse().unwrap())
+        .map(|(_, value)| *value.downcast_ref::<i32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(immutable, [0, 0, 0, 0, 0]);
+
+    root.query_mut(&".children[*].name".parse().unwrap(), |_, value| {
+        value.patch(&"changed".to_owned());
+    });
+    assert_eq!(root.children[0].name, "changed");
+    assert_eq!(root.children[1].name, "changed");
+    assert_eq!(root.children[0].children[0].name, "c");
+}
+
+#[test]
+fn query_display_and_parse() {
+    let query = Query::default()
+        .descendants()
+        .field("items")
+        .any_element()
+        .variant("Some")
+        .any_field()
+        .get("key")
+        .field(1);
+
+    assert_eq!(query.to_string(), r#".**.items[*]::Some.*["key"].1"#);
+    assert_eq!(query.to_string().parse::<Query>().unwrap(), query);
+
+    assert_eq!(
+        Query::from(key_path!(.a[1])),
+        Query::default().field("a").get(1)
+    );
+
+    let err = key_path::parse_query(".a[*").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::ExpectedCloseBracket);
+    assert_eq!(err.column(), 5);
+}
+
+#[test]
+fn try_at_errors() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        list: Vec<i32>,
+        map: BTreeMap<String, i32>,
+        option: Option<(i32,)>,
+    }
+
+    let mut foo = Foo {
+        list: Vec::from([1, 2]),
+        map: BTreeMap::from([("a".to_owned(), 1)]),
+        option: None,
+    };
+
+    #[track_caller]
+    fn assert_error(foo: &Foo, path: KeyPath, resolved: KeyPath, kind: KeyPathErrorKind) {
+        let err = foo.try_at(&path).unwrap_err();
+        assert_eq!(err.resolved(), &resolved, "{err}");
+        assert_eq!(err.kind(), &kind, "{err}");
+        assert_eq!(err.key(), path.iter().nth(resolved.len()).unwrap());
+    }
+
+    assert_error(
+        &foo,
+        key_path!(.missing),
+        key_path!(),
+        KeyPathErrorKind::NoSuchField,
+    );
+    assert_error(
+        &foo,
+        key_path!(.list[2]),
+        key_path!(.list),
+        KeyPathErrorKind::IndexOutOfBounds { len: 2 },
+    );
+    assert_error(
+        &foo,
+        key_path!(.list["a"]),
+        key_path!(.list),
+        KeyPathErrorKind::KeyNotConvertible,
+    );
+    assert_error(
+        &foo,
+        key_path!(.map["b"]),
+        key_path!(.map),
+        KeyPathErrorKind::NoSuchKey,
+    );
+    assert_error(
+        &foo,
+        key_path!(.map[1]),
+        key_path!(.map),
+        KeyPathErrorKind::KeyNotConvertible,
+    );
+    assert_error(
+        &foo,
+        key_path!(.option::Some.0),
+        key_path!(.option),
+        KeyPathErrorKind::VariantMismatch {
+            actual: "None".to_owned(),
+        },
+    );
+    assert_error(
+        &foo,
+        key_path!(.list.a),
+        key_path!(.list),
+        KeyPathErrorKind::UnsupportedKey,
+    );
+    assert_error(
+        &foo,
+        key_path!(.list[0][0]),
+        key_path!(.list[0]),
+        KeyPathErrorKind::UnsupportedKey,
+    );
+    assert_error(
+        &foo,
+        key_path!(::Some),
+        key_path!(),
+        KeyPathErrorKind::UnsupportedKey,
+    );
+
+    assert_eq!(
+        foo.try_at(&key_path!(.list[5])).unwrap_err().to_string(),
+        format!(
+            "cannot access `[5]` at `.list`: index out of bounds for `{}` of length 2",
+            core::any::type_name::<Vec<i32>>()
+        )
+    );
+    assert_eq!(
+        foo.try_at(&key_path!(.nope)).unwrap_err().to_string(),
+        format!(
+            "cannot access `.nope`: no such field in `{}`",
+            core::any::type_name::<Foo>()
+        )
+    );
+
+    assert_eq!(
+        foo.try_at(&key_path!(.list[1]))
+            .unwrap()
+            .downcast_ref::<i32>(),
+        Some(&2)
+    );
+    *foo.try_at_mut(&key_path!(.map["a"]))
+        .unwrap()
+        .downcast_mut::<i32>()
+        .unwrap() = 10;
+    assert_eq!(foo.map["a"], 10);
+    assert_eq!(
+        foo.try_at_mut(&key_path!(.option.0)).unwrap_err().kind(),
+        &KeyPathErrorKind::NoSuchField
+    );
+}
+
+/// A type that isn't `Reflect` and relies on the default methods of `GetPath`.
+struct Handle(Vec<i32>);
+
+impl GetPath for Handle {
+    fn at(&self, key_path: &KeyPath) -> Option<&dyn Reflect> {
+        self.0.at(key_path)
+    }
+
+    fn at_mut(&mut self, key_path: &KeyPath) -> Option<&mut dyn Reflect> {
+        self.0.at_mut(key_path)
+    }
+}
+
+#[test]
+fn default_get_path_methods() {
+    let mut handle = Handle(Vec::from([1, 2, 3]));
+
+    let query = "[*]".parse::<Query>().unwrap();
+    assert_eq!(handle.query(&query).count(), 3);
+    handle.query_mut(&query, |_, value| {
+        *value.downcast_mut::<i32>().unwrap() *= 10;
+    });
+    assert_eq!(handle.0, [10, 20, 30]);
+
+    let mut sum = 0;
+    handle.query_with(&query, |_, value| {
+        sum += value.downcast_ref::<i32>().unwrap();
+    });
+    assert_eq!(sum, 60);
+    assert_eq!(
+        handle.with_at(&key_path!([1]), |value| value.to_value()),
+        Some(20.to_value())
+    );
+
+    assert_eq!(
+        handle.try_at(&key_path!([3])).unwrap_err().kind(),
+        &KeyPathErrorKind::IndexOutOfBounds { len: 3 }
+    );
+    *handle
+        .try_at_mut(&key_path!([0]))
+        .unwrap()
+        .downcast_mut::<i32>()
+        .unwrap() = 1;
+    assert_eq!(handle.0, [1, 20, 30]);
+}
@@ -1,4 +1,9 @@
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPathErrorKind;
+use crate::reflect_eq;
 use crate::FromReflect;
+use crate::List;
 use crate::Reflect;
 
 #[test]
@@ -43,3 +48,153 @@ fn remove() {
     assert!(list.try_remove(2).is_none());
     assert!(list.try_remove(1337).is_none());
 }
+
+#[test]
+fn vec_deque() {
+    use alloc::collections::VecDeque;
+
+    let mut list = VecDeque::from([2, 3]);
+    list.push_front(1);
+
+    assert_eq!(list.to_value(), Vec::from([1, 2, 3]).to_value());
+    assert_eq!(
+        VecDeque::<i32>::from_reflect(&Vec::from([1, 2, 3])).unwrap(),
+        list
+    );
+    assert_eq!(list.get_at::<i32>(&key_path!([0])).unwrap(), &1);
+
+    let list_mut = list.as_list_mut().unwrap();
+    *list_mut.get_mut(1).unwrap().downcast_mut::<i32>().unwrap() = 20;
+    assert!(list_mut.try_insert(0, &0));
+    list_mut.push(&4);
+    assert_eq!(
+        list_mut
+            .try_remove(2)
+            .unwrap()
+            .downcast_ref::<i32>()
+            .unwrap(),
+        &20
+    );
+    assert!(list_mut.try_remove(4).is_none());
+    assert_eq!(list, VecDeque::from([0, 1, 3, 4]));
+
+    list.patch(&Vec::from([10, 11]));
+    assert_eq!(list, VecDeque::from([10, 11, 3, 4]));
+}
+
+#[test]
+fn linked_list() {
+    use alloc::collections::LinkedList;
+
+    let mut list = LinkedList::from([1, 2, 3]);
+
+    assert_eq!(list.to_value(), Vec::from([1, 2, 3]).to_value());
+    assert_eq!(
+        LinkedList::<i32>::from_reflect(&Vec::from([1, 2, 3])).unwrap(),
+        list
+    );
+    assert_eq!(list.get_at::<i32>(&key_path!([2])).unwrap(), &3);
+
+    let list_mut = list.as_list_mut().unwrap();
+    *list_mut.get_mut(1).unwrap().downcast_mut::<i32>().unwrap() = 20;
+    assert!(list_mut.try_insert(3, &4));
+    assert!(list_mut.try_insert(1, &10));
+    assert!(!list_mut.try_insert(6, &5));
+    assert_eq!(
+        list_mut
+            .try_remove(0)
+            .unwrap()
+            .downcast_ref::<i32>()
+            .unwrap(),
+        &1
+    );
+    assert!(list_mut.try_remove(4).is_none());
+    assert_eq!(list, LinkedList::from([10, 20, 3, 4]));
+
+    list.patch(&Vec::from([1, 2]));
+    assert_eq!(list, LinkedList::from([1, 2, 3, 4]));
+}
+
+#[test]
+fn binary_heap() {
+    use alloc::collections::BinaryHeap;
+
+    let mut heap = BinaryHeap::from([1, 5, 3]);
+
+    // elements are in ascending order, whatever order the heap keeps them in
+    assert_eq!(heap.get_at::<i32>(&key_path!([0])).unwrap(), &1);
+    assert_eq!(heap.to_value(), Vec::from([1, 3, 5]).to_value());
+    assert_eq!(heap.to_value(), BinaryHeap::from([5, 3, 1]).to_value(),);
+    assert_eq!(reflect_eq(&heap, &BinaryHeap::from([3, 1, 5])), Some(true));
+
+    // the heap can only be changed by patching through reflection
+    assert!(heap.as_list_mut().is_none());
+    let err = heap.try_at_mut(&key_path!([0])).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::Immutable);
+
+    let list: &mut dyn List = &mut heap;
+    assert!(list.get_mut(0).is_none());
+    assert_eq!(list.iter_mut().count(), 0);
+    assert!(!list.try_insert(0, &10));
+    list.push(&10);
+    assert_eq!(list.get(3).unwrap().downcast_ref::<i32>().unwrap(), &10);
+    assert_eq!(list.pop().unwrap().downcast_ref::<i32>().unwrap(), &10);
+    assert_eq!(
+        list.try_remove(1).unwrap().downcast_ref::<i32>().unwrap(),
+        &3
+    );
+    assert_eq!(heap.clone().into_sorted_vec(), [1, 5]);
+
+    heap.patch(&Vec::from([7, 8, 9]));
+    assert_eq!(heap.clone().into_sorted_vec(), [7, 8, 9]);
+
+    heap.try_patch(&Vec::from([1, 2])).unwrap();
+    assert_eq!(heap.clone().into_sorted_vec(), [1, 2]);
+    assert!(heap.try_patch(&Vec::from([true])).is_err());
+    assert_eq!(heap.clone().into_sorted_vec(), [1, 2]);
+}
+
+#[test]
+fn binary_heap_visit_mut() {
+    use alloc::collections::BinaryHeap;
+    use core::convert::Infallible;
+
+    use crate::key_path::KeyPath;
+    use crate::try_visit::try_visit_mut;
+    use crate::try_visit::TryVisitMut;
+    use crate::DescribeType;
+
+    struct Negate;
+
+    impl TryVisitMut for Negate {
+        type Error = Infallible;
+
+        fn try_visit_i32(&mut self, value: &mut i32, _: &KeyPath) -> Result<(), Self::Error> {
+            *value = -*value;
+            Ok(())
+        }
+    }
+
+    let mut heap = BinaryHeap::from([1, 5, 3]);
+    let ty = <BinaryHeap<i32> as DescribeType>::type_descriptor();
+    try_visit_mut(&mut Negate, &mut heap, ty.get_type()).unwrap();
+
+    // the heap is rebuilt so the greatest element is still first
+    assert_eq!(heap.peek(), Some(&-1));
+    assert_eq!(heap.into_sorted_vec(), [-5, -3, -1]);
+}
+
+#[test]
+fn binary_heap_of_reverse() {
+    use alloc::collections::BinaryHeap;
+    use core::cmp::Reverse;
+
+    let heap = BinaryHeap::from([Reverse(1), Reverse(5), Reverse(3)]);
+
+    // elements are sorted by `Reverse` and reflected as the value they contain
+    assert_eq!(heap.get_at::<i32>(&key_path!([0])).unwrap(), &5);
+    assert_eq!(heap.to_value(), Vec::from([5, 3, 1]).to_value());
+
+    let new_heap = BinaryHeap::<Reverse<i32>>::from_reflect(&Vec::from([2, 4])).unwrap();
+    assert_eq!(new_heap.into_sorted_vec(), [Reverse(4), Reverse(2)]);
+}
@@ -3,6 +3,7 @@ use alloc::collections::BTreeMap;
 use crate::key_path;
 use crate::key_path::GetPath;
 use crate::DescribeType;
+use crate::FromReflect;
 use crate::GetField;
 use crate::GetFieldMut;
 use crate::Map;
@@ -63,3 +64,35 @@ fn exoctic_value_type() {
     map.as_map_mut().unwrap().insert(&1, &foo_default_value);
     assert_eq!(map.len(), 1);
 }
+
+#[test]
+fn hash_map() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Reflect)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        map: HashMap<String, i32>,
+    }
+
+    let mut foo = Foo {
+        map: HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]),
+    };
+
+    assert_eq!(foo.get_at::<i32>(&key_path!(.map["a"])).unwrap(), &1);
+    *foo.get_at_mut::<i32>(&key_path!(.map["b"])).unwrap() = 3;
+    assert_eq!(foo.map["b"], 3);
+
+    // the value doesn't depend on the iteration order
+    let value = foo.to_value();
+    let other = Foo {
+        map: HashMap::from([("b".to_owned(), 3), ("a".to_owned(), 1)]),
+    };
+    assert_eq!(value, other.to_value());
+
+    let from_value = Foo::from_reflect(&value).unwrap();
+    assert_eq!(from_value.map, foo.map);
+
+    foo.map.patch(&BTreeMap::from([("a".to_owned(), 10)]));
+    assert_eq!(foo.map["a"], 10);
+}
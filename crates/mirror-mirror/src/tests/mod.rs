@@ -2,11 +2,15 @@ use crate::Reflect;
 
 mod array;
 mod enum_;
+mod guard;
 mod key_path;
 mod list;
 mod map;
 mod meta;
+mod patch_only;
+mod set;
 mod simple_type_name;
+mod string;
 mod struct_;
 mod tuple;
 mod tuple_struct;
@@ -0,0 +1,173 @@
+use core::any::Any;
+use core::convert::Infallible;
+use core::fmt;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPath;
+use crate::key_path::KeyPathErrorKind;
+use crate::struct_::StructValue;
+use crate::try_visit::try_visit_mut;
+use crate::try_visit::TryVisitMut;
+use crate::type_info::graph::NamedFieldNode;
+use crate::type_info::graph::NodeId;
+use crate::type_info::graph::StructNode;
+use crate::type_info::graph::TypeGraph;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ReflectGuard;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::Value;
+
+/// A color packed into a `u32`. It's described as a struct with a field per channel but the
+/// channels can't be borrowed, so it's reflected as opaque, read through a guard that owns a
+/// `StructValue`, and changed by patching.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rgba(u32);
+
+impl Rgba {
+    fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self(u32::from_be_bytes([r, g, b, a]))
+    }
+}
+
+impl DescribeType for Rgba {
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        graph.get_or_build_node_with::<Self, _>(|graph| {
+            let fields = &["r", "g", "b", "a"]
+                .map(|name| NamedFieldNode::new::<u8>(name, Default::default(), &[], graph));
+            StructNode::new::<Self>(fields, Default::default(), &[])
+        })
+    }
+}
+
+impl Reflect for Rgba {
+    trivial_reflect_methods!();
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Opaque(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Opaque(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Opaque(self)
+    }
+
+    fn reflect_guard(&self) -> ReflectGuard<'_> {
+        ReflectGuard::from_guard(Box::new(self.to_value()))
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        let mut channels = self.to_value();
+        channels.patch(value);
+        if let Some(new) = Self::from_reflect(&channels) {
+            *self = new;
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let [r, g, b, a] = self.0.to_be_bytes();
+        StructValue::new()
+            .with_field("r", r)
+            .with_field("g", g)
+            .with_field("b", b)
+            .with_field("a", a)
+            .into()
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        Box::new(*self)
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromReflect for Rgba {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let Some(rgba) = reflect.downcast_ref::<Self>() {
+            return Some(*rgba);
+        }
+        let struct_ = reflect.reflect_ref().as_struct()?;
+        let channel = |name| u8::from_reflect(struct_.field(name)?);
+        Some(Self::new(
+            channel("r")?,
+            channel("g")?,
+            channel("b")?,
+            channel("a")?,
+        ))
+    }
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Sprite {
+    tint: Rgba,
+    scale: u8,
+}
+
+#[test]
+fn mutable_lookups_stop_at_patch_only_values() {
+    let mut sprite = Sprite {
+        tint: Rgba::new(1, 2, 3, 4),
+        scale: 1,
+    };
+
+    assert_eq!(
+        sprite
+            .tint
+            .reflect_guard()
+            .get_at::<u8>(&key_path!(.g))
+            .unwrap(),
+        &2
+    );
+
+    let err = sprite.try_at_mut(&key_path!(.tint.g)).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::Immutable);
+    assert_eq!(err.resolved(), &key_path!(.tint));
+
+    let err = sprite.try_at_mut(&key_path!(.tint.nope)).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::NoSuchField);
+    assert_eq!(err.resolved(), &key_path!(.tint));
+
+    sprite
+        .at_mut(&key_path!(.tint))
+        .unwrap()
+        .patch(&StructValue::new().with_field("g", 20_u8));
+    assert_eq!(sprite.tint, Rgba::new(1, 20, 3, 4));
+}
+
+#[test]
+fn visit_mut_patches_a_copy_back() {
+    struct Double;
+
+    impl TryVisitMut for Double {
+        type Error = Infallible;
+
+        fn try_visit_u8(&mut self, value: &mut u8, _key_path: &KeyPath) -> Result<(), Self::Error> {
+            *value *= 2;
+            Ok(())
+        }
+    }
+
+    let mut sprite = Sprite {
+        tint: Rgba::new(1, 2, 3, 4),
+        scale: 1,
+    };
+    let ty = <Sprite as DescribeType>::type_descriptor();
+    try_visit_mut(&mut Double, &mut sprite, ty.get_type()).unwrap();
+    assert_eq!(
+        sprite,
+        Sprite {
+            tint: Rgba::new(2, 4, 6, 8),
+            scale: 2,
+        }
+    );
+}
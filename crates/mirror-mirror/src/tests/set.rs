@@ -0,0 +1,162 @@
+use alloc::collections::BTreeSet;
+use std::collections::HashSet;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPathErrorKind;
+use crate::reflect_diff;
+use crate::reflect_eq;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::Set;
+use crate::Value;
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    tags: BTreeSet<String>,
+    ids: HashSet<i32>,
+}
+
+fn foo() -> Foo {
+    Foo {
+        tags: BTreeSet::from(["a".to_owned(), "b".to_owned()]),
+        ids: HashSet::from([3, 1, 2]),
+    }
+}
+
+#[test]
+fn works() {
+    let mut set = BTreeSet::from([1, 2]);
+    let set: &mut dyn Set = set.as_set_mut().unwrap();
+
+    assert!(set.contains(&1));
+    assert!(!set.contains(&3));
+    assert!(!set.contains(&"1".to_owned()));
+    assert_eq!(set.get(&2).unwrap().downcast_ref::<i32>().unwrap(), &2);
+
+    assert!(set.insert(&3));
+    assert!(!set.insert(&3));
+    assert!(!set.insert(&"4".to_owned()));
+    assert_eq!(set.len(), 3);
+
+    let removed = set.remove(&1).unwrap();
+    assert_eq!(removed.downcast_ref::<i32>().unwrap(), &1);
+    assert!(set.remove(&1).is_none());
+
+    let elements = set
+        .iter()
+        .map(|value| *value.downcast_ref::<i32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(elements, [2, 3]);
+}
+
+#[test]
+fn hash_set() {
+    let mut set = HashSet::from([3, 1, 2]);
+
+    let value = set.to_value();
+    assert_eq!(
+        value,
+        Value::Set(BTreeSet::from([1.to_value(), 2.to_value(), 3.to_value()]))
+    );
+    assert_eq!(HashSet::<i32>::from_reflect(&value).unwrap(), set);
+
+    let set_mut = set.as_set_mut().unwrap();
+    assert!(set_mut.insert(&4));
+    assert!(set_mut.remove(&1).is_some());
+    assert_eq!(set, HashSet::from([2, 3, 4]));
+
+    set.patch(&BTreeSet::from([5, 6]));
+    assert_eq!(set, HashSet::from([5, 6]));
+
+    // sets aren't lists
+    assert!(HashSet::<i32>::from_reflect(&Vec::from([1, 2])).is_none());
+}
+
+#[test]
+fn from_reflect() {
+    let foo = foo();
+    let value = foo.to_value();
+    assert_eq!(Foo::from_reflect(&value).unwrap(), foo);
+    assert_eq!(
+        BTreeSet::<i32>::from_reflect(&HashSet::from([1, 2])).unwrap(),
+        BTreeSet::from([1, 2])
+    );
+}
+
+#[test]
+fn type_info() {
+    let type_descriptor = <Foo as DescribeType>::type_descriptor();
+    let ty = type_descriptor.as_struct().unwrap();
+    let tags = ty.field_type("tags").unwrap().get_type().as_set().unwrap();
+    assert_eq!(tags.element_type().type_name(), "alloc::string::String");
+    assert!(tags.has_default_value());
+    assert_eq!(tags.default_value(), Value::Set(BTreeSet::new()));
+}
+
+#[test]
+fn key_path() {
+    let mut foo = foo();
+
+    assert_eq!(foo.get_at::<i32>(&key_path!(.ids[2])).unwrap(), &2);
+    assert!(foo.get_at::<i32>(&key_path!(.ids[4])).is_none());
+
+    let err = foo.try_at_mut(&key_path!(.ids[2])).unwrap_err();
+    assert!(matches!(err.kind(), KeyPathErrorKind::Immutable));
+    assert!(foo.at_mut(&key_path!(.ids[2])).is_none());
+
+    // the set itself can be mutated
+    foo.at_mut(&key_path!(.ids))
+        .unwrap()
+        .as_set_mut()
+        .unwrap()
+        .insert(&4);
+    assert!(foo.ids.contains(&4));
+}
+
+#[test]
+fn patch() {
+    let mut foo = foo();
+
+    foo.tags.patch(&BTreeSet::from(["c".to_owned()]));
+    assert_eq!(foo.tags, BTreeSet::from(["c".to_owned()]));
+
+    let err = foo
+        .ids
+        .try_patch(&BTreeSet::from([1.to_value(), "x".to_owned().to_value()]).to_value())
+        .unwrap_err();
+    assert_eq!(foo.ids, HashSet::from([1]));
+    assert_eq!(
+        err.to_string(),
+        r#"expected `i32`, found `alloc::string::String` at `["x"]`"#
+    );
+}
+
+#[test]
+fn eq_and_diff() {
+    let a = foo();
+    let mut b = foo();
+    assert!(reflect_eq(&a, &b).unwrap());
+
+    b.ids.remove(&1);
+    b.ids.insert(4);
+    assert!(!reflect_eq(&a, &b).unwrap());
+
+    let changes = reflect_diff(&a, &b).unwrap();
+    assert_eq!(changes.len(), 2);
+
+    let mut c = a.clone();
+    for change in &changes {
+        change.apply(&mut c).unwrap();
+    }
+    assert_eq!(c, b);
+}
+
+#[test]
+fn debug() {
+    let set = BTreeSet::from([1, 2]);
+    assert_eq!(format!("{:?}", set.as_reflect()), "{1, 2}");
+    assert_eq!(format!("{:?}", set.to_value().as_reflect()), "{1, 2}");
+}
@@ -0,0 +1,257 @@
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use core::cell::RefCell;
+use core::convert::Infallible;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::GetTypePath;
+use crate::key_path::KeyPath;
+use crate::key_path::KeyPathErrorKind;
+use crate::reflect_diff;
+use crate::reflect_eq;
+use crate::try_visit::try_visit;
+use crate::try_visit::try_visit_mut;
+use crate::try_visit::TryVisit;
+use crate::try_visit::TryVisitMut;
+use crate::type_info::ScalarType;
+use crate::DescribeType;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ScalarRef;
+use crate::Value;
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Asset {
+    name: Box<str>,
+    label: Cow<'static, str>,
+    shared: Arc<str>,
+    path: PathBuf,
+    os: OsString,
+}
+
+fn asset() -> Asset {
+    Asset {
+        name: "name".into(),
+        label: Cow::Borrowed("label"),
+        shared: "shared".into(),
+        path: PathBuf::from("assets/player.png"),
+        os: OsString::from("os"),
+    }
+}
+
+#[test]
+fn to_value_and_from_reflect() {
+    let asset = asset();
+
+    assert_eq!(asset.name.to_value(), Value::String("name".to_owned()));
+    assert_eq!(
+        asset.path.to_value(),
+        Value::String("assets/player.png".to_owned())
+    );
+
+    let value = asset.to_value();
+    assert_eq!(Asset::from_reflect(&value).unwrap(), asset);
+    assert_eq!(Asset::from_reflect(&asset).unwrap(), asset);
+
+    assert_eq!(
+        <Box<str>>::from_reflect(&"foo".to_owned()).unwrap(),
+        "foo".into()
+    );
+    assert_eq!(String::from_reflect(&asset.shared).unwrap(), "shared");
+    assert!(<Box<str>>::from_reflect(&1).is_none());
+
+    let cloned = asset.label.clone_reflect();
+    assert_eq!(cloned.downcast_ref::<Cow<'static, str>>().unwrap(), "label");
+}
+
+#[test]
+fn type_info() {
+    let type_info = <Asset as DescribeType>::type_descriptor();
+    for path in [
+        key_path!(.name),
+        key_path!(.label),
+        key_path!(.shared),
+        key_path!(.path),
+        key_path!(.os),
+    ] {
+        assert_eq!(
+            type_info.type_at(&path).unwrap().as_scalar().unwrap(),
+            ScalarType::String
+        );
+    }
+}
+
+#[test]
+fn key_path() {
+    let mut asset = asset();
+
+    assert_eq!(
+        &**asset.get_at::<Box<str>>(&key_path!(.name)).unwrap(),
+        "name"
+    );
+    *asset.get_at_mut::<PathBuf>(&key_path!(.path)).unwrap() = PathBuf::from("other.png");
+    assert_eq!(asset.path, PathBuf::from("other.png"));
+
+    let err = asset.try_at(&key_path!(.name[0])).unwrap_err();
+    assert_eq!(err.kind(), &KeyPathErrorKind::UnsupportedKey);
+}
+
+#[test]
+fn reflect_as_string_scalars() {
+    let asset = asset();
+
+    for (value, expected) in [
+        (asset.name.as_reflect(), "name"),
+        (asset.label.as_reflect(), "label"),
+        (asset.shared.as_reflect(), "shared"),
+        (asset.path.as_reflect(), "assets/player.png"),
+        (asset.os.as_reflect(), "os"),
+    ] {
+        let Some(ScalarRef::String(string)) = value.as_scalar() else {
+            panic!("`{value:?}` isn't a string scalar");
+        };
+        assert_eq!(&*string, expected);
+        assert!(core::ptr::eq(
+            string.as_reflect() as *const dyn Reflect as *const (),
+            value as *const dyn Reflect as *const (),
+        ));
+    }
+
+    let label = asset.at(&key_path!(.label)).unwrap();
+    assert_eq!(
+        label.reflect_ref().as_scalar(),
+        Some(ScalarRef::from(&"label".to_owned()))
+    );
+}
+
+#[test]
+fn strings_are_not_read_through_locks() {
+    let mutex = Mutex::new("locked".to_owned());
+    assert!(String::from_reflect(&mutex).is_none());
+
+    let ref_cell = RefCell::new("borrowed".to_owned());
+    let _borrow = ref_cell.borrow_mut();
+    assert!(String::from_reflect(&ref_cell).is_none());
+    let mut string = String::new();
+    string.patch(&ref_cell);
+    assert!(string.is_empty());
+}
+
+#[test]
+fn patch_eq_and_diff() {
+    let mut a = asset();
+    let mut b = asset();
+    assert!(reflect_eq(&a, &b).unwrap());
+    assert!(reflect_eq(&a.name, &"name".to_owned()).unwrap());
+
+    b.name = "new name".into();
+    b.path = PathBuf::from("new/path.png");
+    assert!(!reflect_eq(&a, &b).unwrap());
+
+    let changes = reflect_diff(&a, &b).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].path(), &key_path!(.name));
+    assert_eq!(changes[1].path(), &key_path!(.path));
+
+    a.patch(&b.to_value());
+    assert_eq!(a, b);
+
+    a.label.patch(&"patched".to_owned());
+    assert_eq!(a.label, "patched");
+
+    let mut string = String::new();
+    string.patch(&a.shared);
+    assert_eq!(string, "shared");
+
+    a.try_patch(&asset()).unwrap();
+    assert_eq!(a, asset());
+
+    let err = a.name.try_patch(&1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected `alloc::string::String`, found `i32`"
+    );
+}
+
+#[test]
+fn visit() {
+    #[derive(Default)]
+    struct Collect(Vec<String>);
+
+    impl TryVisit for Collect {
+        type Error = Infallible;
+
+        fn try_visit_string(&mut self, value: &String) -> Result<(), Self::Error> {
+            self.0.push(value.clone());
+            Ok(())
+        }
+    }
+
+    struct Uppercase;
+
+    impl TryVisitMut for Uppercase {
+        type Error = Infallible;
+
+        fn try_visit_string(
+            &mut self,
+            value: &mut String,
+            _key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            *value = value.to_uppercase();
+            Ok(())
+        }
+    }
+
+    let mut asset = asset();
+    let ty = <Asset as DescribeType>::type_descriptor();
+
+    let mut visitor = Collect::default();
+    try_visit(&mut visitor, &asset, ty.get_type()).unwrap();
+    assert_eq!(
+        visitor.0,
+        ["name", "label", "shared", "assets/player.png", "os"]
+    );
+
+    try_visit_mut(&mut Uppercase, &mut asset, ty.get_type()).unwrap();
+    assert_eq!(&*asset.name, "NAME");
+    assert_eq!(asset.label, "LABEL");
+    assert_eq!(&*asset.shared, "SHARED");
+    assert_eq!(asset.path, PathBuf::from("ASSETS/PLAYER.PNG"));
+    assert_eq!(asset.os, OsString::from("OS"));
+}
+
+#[test]
+fn debug() {
+    let asset = asset();
+    assert_eq!(format!("{:?}", asset.name.as_reflect()), "\"name\"");
+    assert_eq!(
+        format!("{:?}", asset.path.as_reflect()),
+        "\"assets/player.png\""
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_paths_are_lossy() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = PathBuf::from(OsStr::from_bytes(b"foo\xFF.png"));
+    assert_eq!(path.to_value(), Value::String("foo\u{FFFD}.png".to_owned()));
+
+    let round_tripped = PathBuf::from_reflect(&path.to_value()).unwrap();
+    assert_ne!(round_tripped, path);
+    assert_eq!(round_tripped, PathBuf::from("foo\u{FFFD}.png"));
+
+    // there's no `str` to borrow so shared access goes through the guard
+    assert!(path.as_scalar().is_none());
+    assert_eq!(
+        path.reflect_guard().downcast_ref::<String>().unwrap(),
+        "foo\u{FFFD}.png"
+    );
+}
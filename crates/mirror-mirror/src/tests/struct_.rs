@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 
 use crate::enum_::VariantField;
 use crate::key_path;
+use crate::key_path::GetPath;
 use crate::key_path::GetTypePath;
 use crate::struct_::StructValue;
 use crate::type_info::GetMeta;
@@ -328,3 +329,63 @@ fn consistent_iteration_order_of_struct_variant_fields() {
 
     assert_eq!(by_value, by_type);
 }
+
+#[test]
+fn arc_field() {
+    use alloc::sync::Arc;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Outer {
+        inner: Arc<Inner>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Inner {
+        n: i32,
+    }
+
+    let shared = Arc::new(Inner { n: 1 });
+    let mut outer = Outer {
+        inner: Arc::clone(&shared),
+    };
+
+    // `Arc`s are transparent
+    let ty = <Outer as DescribeType>::type_descriptor();
+    assert_eq!(
+        ty.type_at(&key_path!(.inner)).unwrap().type_name(),
+        <Inner as DescribeType>::type_descriptor().type_name()
+    );
+    assert_eq!(outer.get_field::<Inner>("inner").unwrap(), &*shared);
+
+    // mutating clones the shared value
+    *outer
+        .as_reflect_mut()
+        .get_at_mut::<i32>(&key_path!(.inner.n))
+        .unwrap() = 2;
+    assert_eq!(outer.inner.n, 2);
+    assert_eq!(shared.n, 1);
+
+    outer.patch(&Outer {
+        inner: Arc::new(Inner { n: 3 }),
+    });
+    assert_eq!(outer.inner.n, 3);
+    assert_eq!(shared.n, 1);
+
+    let value = outer.to_value();
+    assert_eq!(
+        value,
+        Outer {
+            inner: Arc::new(Inner { n: 3 })
+        }
+        .to_value()
+    );
+    assert_eq!(Outer::from_reflect(&value).unwrap(), outer);
+
+    // patching with an equal value doesn't clone
+    let shared = Arc::clone(&outer.inner);
+    outer.patch(&value);
+    outer.try_patch(&value).unwrap();
+    assert!(Arc::ptr_eq(&outer.inner, &shared));
+}
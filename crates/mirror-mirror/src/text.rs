@@ -0,0 +1,1345 @@
+//! The syntax mirrors Rust, and what [`reflect_debug`] prints, so values can be written and
+//! diffed by hand:
+//!
+//! - Structs are written as `Name { field: value }`, tuple structs as `Name(value)`, and enums as
+//!   `Name::Variant`, `Name::Variant(value)`, or `Name::Variant { field: value }`. Names are
+//!   written without their module path or generics. `_` stands for a name that isn't known, such
+//!   as for a [`Value`] created by [`Reflect::to_value`].
+//! - Tuples are written as `(a, b)`, lists and arrays as `[a, b]`, maps as `{key: value}`, and
+//!   sets as `{a, b}`.
+//! - Scalars are written as literals such as `1`, `1.5`, `true`, `'a'`, and `"foo"`. Numbers
+//!   other than `i32` and `f64` have a type suffix, such as `1_u8`, like in [key paths].
+//! - Trailing commas, whitespace, and `//` comments are allowed anywhere between values.
+//!
+//! [`parse`] reads text back into a [`Value`], relying on the suffixes and names to tell values
+//! apart. [`parse_typed`] instead checks the text against a type, which catches misspelled fields
+//! and variants, and allows numbers without suffixes.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::{text, DescribeType, FromReflect, Reflect};
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! enum Shape {
+//!     Circle { radius: f32 },
+//!     Square(f32),
+//! }
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Prefab {
+//!     name: String,
+//!     shapes: Vec<Shape>,
+//! }
+//!
+//! let prefab = Prefab {
+//!     name: "Wheel".to_owned(),
+//!     shapes: vec![Shape::Circle { radius: 1.0 }, Shape::Square(0.5)],
+//! };
+//!
+//! assert_eq!(
+//!     text::print(&prefab).unwrap(),
+//!     r#"Prefab { name: "Wheel", shapes: [Shape::Circle { radius: 1.0_f32 }, Shape::Square(0.5_f32)] }"#,
+//! );
+//!
+//! let ty = <Prefab as DescribeType>::type_descriptor();
+//! let value = text::parse_typed(
+//!     r#"
+//!     Prefab {
+//!         name: "Wheel",
+//!         // the type tells these are `f32`s
+//!         shapes: [Shape::Circle { radius: 1 }, Shape::Square(0.5)],
+//!     }
+//!     "#,
+//!     ty.get_type(),
+//! )
+//! .unwrap();
+//! assert_eq!(Prefab::from_reflect(&value).unwrap(), prefab);
+//! ```
+//!
+//! [`reflect_debug`]: crate::reflect_debug
+//! [`Value`]: crate::Value
+//! [`Reflect::to_value`]: crate::Reflect::to_value
+//! [key paths]: crate::key_path::parse
+//! [`parse`]: crate::text::parse
+//! [`parse_typed`]: crate::text::parse_typed
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::enum_::EnumValue;
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::key_path;
+use crate::key_path::parse_literal;
+use crate::key_path::Cursor;
+use crate::key_path::Key;
+use crate::key_path::KeyPath;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::type_info::short_type_name;
+use crate::type_info::ScalarType;
+use crate::type_info::Type;
+use crate::type_info::Variant;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// Print `value` on a single line.
+///
+/// Values behind locks and other guards are looked through.
+pub fn print(value: &dyn Reflect) -> Result<String, PrintError> {
+    Printer::new(false).print(value)
+}
+
+/// Print `value` over multiple lines, indented by four spaces per level, like `{:#?}`.
+pub fn print_pretty(value: &dyn Reflect) -> Result<String, PrintError> {
+    Printer::new(true).print(value)
+}
+
+/// Parse a value without knowing its type.
+///
+/// `{}` is parsed as an empty map, since it could also be an empty set. Values can be nested at
+/// most 128 levels deep.
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    Parser::new(input).parse(None)
+}
+
+/// Parse a value of type `ty`.
+///
+/// Numbers are converted to the type of the field they're in, if they fit, so they don't need a
+/// suffix. Type names must match the type, or be `_`. Every field must be given, and fields that
+/// the type doesn't have are an error. The contents of opaque types are parsed without a type.
+/// Values can be nested at most 128 levels deep, like with [`parse`].
+pub fn parse_typed(input: &str, ty: Type<'_>) -> Result<Value, ParseError> {
+    Parser::new(input).parse(Some(ty))
+}
+
+/// The error returned by [`print()`] and [`print_pretty`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrintError {
+    path: KeyPath,
+    type_name: String,
+}
+
+impl PrintError {
+    /// The key path of the value that couldn't be printed.
+    pub fn path(&self) -> &KeyPath {
+        &self.path
+    }
+
+    /// The name of the opaque type that couldn't be printed.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is opaque and cannot be printed", self.type_name)?;
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrintError {}
+
+/// The error returned by [`parse`] and [`parse_typed`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// The line, starting at 1, where the error occurred.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column, counted in characters and starting at 1, where the error occurred.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.kind, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The kind of a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// Expected a value.
+    ExpectedValue,
+    /// Expected the given token, such as `}`.
+    ExpectedToken(&'static str),
+    /// Expected a field name.
+    ExpectedField,
+    /// Expected a variant name after `::`.
+    ExpectedVariant,
+    /// The input continues after the value.
+    TrailingCharacters,
+    /// A literal couldn't be parsed, such as a number with an unknown suffix.
+    InvalidLiteral(key_path::ParseErrorKind),
+    /// A field was given more than once.
+    DuplicateField(String),
+    /// The value doesn't have the shape of the type, or a number doesn't fit in it.
+    TypeMismatch {
+        /// The name of the expected type.
+        expected: String,
+    },
+    /// The name before a struct or enum doesn't match the type.
+    WrongTypeName {
+        /// The name of the type without its path or generics.
+        expected: String,
+        /// The name in the input.
+        found: String,
+    },
+    /// A field the type doesn't have.
+    UnknownField(String),
+    /// A field the type has is missing.
+    MissingField(String),
+    /// A variant the type doesn't have.
+    UnknownVariant(String),
+    /// An array, tuple, or tuple struct with the wrong number of elements.
+    LengthMismatch {
+        /// The number of elements in the type.
+        expected: usize,
+        /// The number of elements in the input.
+        found: usize,
+    },
+    /// Values are nested more than 128 levels deep.
+    TooDeep,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::ExpectedValue => write!(f, "expected value"),
+            ParseErrorKind::ExpectedToken(token) => write!(f, "expected `{token}`"),
+            ParseErrorKind::ExpectedField => write!(f, "expected field name"),
+            ParseErrorKind::ExpectedVariant => write!(f, "expected variant name"),
+            ParseErrorKind::TrailingCharacters => write!(f, "trailing characters after the value"),
+            ParseErrorKind::InvalidLiteral(kind) => write!(f, "{kind}"),
+            ParseErrorKind::DuplicateField(name) => write!(f, "duplicate field `{name}`"),
+            ParseErrorKind::TypeMismatch { expected } => write!(f, "expected `{expected}`"),
+            ParseErrorKind::WrongTypeName { expected, found } => {
+                write!(f, "expected `{expected}`, found `{found}`")
+            }
+            ParseErrorKind::UnknownField(name) => write!(f, "unknown field `{name}`"),
+            ParseErrorKind::MissingField(name) => write!(f, "missing field `{name}`"),
+            ParseErrorKind::UnknownVariant(name) => write!(f, "unknown variant `{name}`"),
+            ParseErrorKind::LengthMismatch { expected, found } => {
+                write!(f, "expected {expected} elements, found {found}")
+            }
+            ParseErrorKind::TooDeep => write!(f, "values are nested too deeply"),
+        }
+    }
+}
+
+const TAB: &str = "    ";
+
+struct Printer {
+    out: String,
+    pretty: bool,
+    indent: usize,
+    key_path: KeyPath,
+}
+
+impl Printer {
+    fn new(pretty: bool) -> Self {
+        Self {
+            out: String::new(),
+            pretty,
+            indent: 0,
+            key_path: KeyPath::default(),
+        }
+    }
+
+    fn print(mut self, value: &dyn Reflect) -> Result<String, PrintError> {
+        self.print_value(value)?;
+        Ok(self.out)
+    }
+
+    fn print_value(&mut self, value: &dyn Reflect) -> Result<(), PrintError> {
+        let guard = value.reflect_guard();
+        let value = &*guard;
+
+        match value.reflect_ref() {
+            ReflectRef::Struct(struct_) => {
+                let name = type_name(value, struct_.as_any().is::<StructValue>());
+                self.out.push_str(name);
+                self.out.push(' ');
+                self.print_fields(struct_.fields())
+            }
+            ReflectRef::TupleStruct(tuple_struct) => {
+                let name = type_name(value, tuple_struct.as_any().is::<TupleStructValue>());
+                self.out.push_str(name);
+                self.print_tuple(tuple_struct.fields().enumerate(), false)
+            }
+            ReflectRef::Tuple(tuple) => self.print_tuple(tuple.fields().enumerate(), true),
+            ReflectRef::Enum(enum_) => {
+                let name = type_name(value, enum_.as_any().is::<EnumValue>());
+                let variant = enum_.variant_name();
+                write!(self.out, "{name}::{variant}").unwrap();
+
+                self.key_path.push(Key::variant(variant));
+                let result = match enum_.variant_kind() {
+                    VariantKind::Struct => {
+                        self.out.push(' ');
+                        self.print_fields(enum_.fields().map(|field| match field {
+                            VariantField::Struct(name, value) => (name, value),
+                            VariantField::Tuple(_) => {
+                                unreachable!("struct variant yielded tuple field")
+                            }
+                        }))
+                    }
+                    VariantKind::Tuple => self.print_tuple(
+                        enum_
+                            .fields()
+                            .enumerate()
+                            .map(|(index, field)| match field {
+                                VariantField::Tuple(value) => (index, value),
+                                VariantField::Struct(_, _) => {
+                                    unreachable!("tuple variant yielded struct field")
+                                }
+                            }),
+                        false,
+                    ),
+                    VariantKind::Unit => Ok(()),
+                };
+                self.key_path.pop();
+                result
+            }
+            ReflectRef::List(list) => self.print_elements(list.iter()),
+            ReflectRef::Array(array) => self.print_elements(array.iter()),
+            ReflectRef::Map(map) => {
+                let entries = map.iter().collect::<Vec<_>>();
+                self.print_seq("{", "}", false, entries.len(), |this, index| {
+                    let (key, value) = entries[index];
+                    this.print_value(key)?;
+                    this.out.push_str(": ");
+                    this.key_path.push(Key::Get(key.to_value()));
+                    this.print_value(value)?;
+                    this.key_path.pop();
+                    Ok(())
+                })
+            }
+            ReflectRef::Set(set) => {
+                let elements = set.iter().collect::<Vec<_>>();
+                self.print_seq("{", "}", false, elements.len(), |this, index| {
+                    this.print_value(elements[index])
+                })
+            }
+            ReflectRef::Scalar(scalar) => {
+                let out = &mut self.out;
+                match scalar {
+                    ScalarRef::usize(n) => write!(out, "{n}_usize"),
+                    ScalarRef::u8(n) => write!(out, "{n}_u8"),
+                    ScalarRef::u16(n) => write!(out, "{n}_u16"),
+                    ScalarRef::u32(n) => write!(out, "{n}_u32"),
+                    ScalarRef::u64(n) => write!(out, "{n}_u64"),
+                    ScalarRef::u128(n) => write!(out, "{n}_u128"),
+                    ScalarRef::i8(n) => write!(out, "{n}_i8"),
+                    ScalarRef::i16(n) => write!(out, "{n}_i16"),
+                    ScalarRef::i32(n) => write!(out, "{n}"),
+                    ScalarRef::i64(n) => write!(out, "{n}_i64"),
+                    ScalarRef::i128(n) => write!(out, "{n}_i128"),
+                    ScalarRef::f32(n) => write!(out, "{n:?}_f32"),
+                    ScalarRef::f64(n) => write!(out, "{n:?}"),
+                    ScalarRef::bool(b) => write!(out, "{b}"),
+                    ScalarRef::char(c) => write!(out, "{c:?}"),
+                    ScalarRef::String(s) => write!(out, "{s:?}"),
+                }
+                .unwrap();
+                Ok(())
+            }
+            ReflectRef::Opaque(_) => Err(PrintError {
+                path: self.key_path.clone(),
+                type_name: value.type_name().to_owned(),
+            }),
+        }
+    }
+
+    fn print_fields<'a>(
+        &mut self,
+        fields: impl Iterator<Item = (&'a str, &'a dyn Reflect)>,
+    ) -> Result<(), PrintError> {
+        let fields = fields.collect::<Vec<_>>();
+        self.print_seq("{", "}", true, fields.len(), |this, index| {
+            let (name, value) = fields[index];
+            write!(this.out, "{name}: ").unwrap();
+            this.key_path.push(Key::named_field(name));
+            this.print_value(value)?;
+            this.key_path.pop();
+            Ok(())
+        })
+    }
+
+    fn print_tuple<'a>(
+        &mut self,
+        fields: impl Iterator<Item = (usize, &'a dyn Reflect)>,
+        is_tuple: bool,
+    ) -> Result<(), PrintError> {
+        let fields = fields.collect::<Vec<_>>();
+        // `(1,)` is a tuple but `(1)` is not, like in Rust
+        if is_tuple && fields.len() == 1 && !self.pretty {
+            self.out.push('(');
+            self.key_path.push(Key::numbered_field(0));
+            self.print_value(fields[0].1)?;
+            self.key_path.pop();
+            self.out.push_str(",)");
+            return Ok(());
+        }
+        self.print_seq("(", ")", false, fields.len(), |this, index| {
+            let (index, value) = fields[index];
+            this.key_path.push(Key::numbered_field(index));
+            this.print_value(value)?;
+            this.key_path.pop();
+            Ok(())
+        })
+    }
+
+    fn print_elements<'a>(
+        &mut self,
+        elements: impl Iterator<Item = &'a dyn Reflect>,
+    ) -> Result<(), PrintError> {
+        let elements = elements.collect::<Vec<_>>();
+        self.print_seq("[", "]", false, elements.len(), |this, index| {
+            this.key_path.push(Key::Get(index.into()));
+            this.print_value(elements[index])?;
+            this.key_path.pop();
+            Ok(())
+        })
+    }
+
+    /// Print `len` items between `open` and `close`, separated by commas.
+    ///
+    /// On a single line the items are padded with spaces if `padded` is set, like in
+    /// `Foo { a: 1 }`. Pretty printing puts each item on its own line with a trailing comma.
+    fn print_seq(
+        &mut self,
+        open: &str,
+        close: &str,
+        padded: bool,
+        len: usize,
+        mut print_item: impl FnMut(&mut Self, usize) -> Result<(), PrintError>,
+    ) -> Result<(), PrintError> {
+        self.out.push_str(open);
+        if len == 0 {
+            self.out.push_str(close);
+            return Ok(());
+        }
+
+        if self.pretty {
+            self.indent += 1;
+            for index in 0..len {
+                self.out.push('\n');
+                self.push_indent();
+                print_item(self, index)?;
+                self.out.push(',');
+            }
+            self.indent -= 1;
+            self.out.push('\n');
+            self.push_indent();
+        } else {
+            if padded {
+                self.out.push(' ');
+            }
+            for index in 0..len {
+                if index != 0 {
+                    self.out.push_str(", ");
+                }
+                print_item(self, index)?;
+            }
+            if padded {
+                self.out.push(' ');
+            }
+        }
+
+        self.out.push_str(close);
+        Ok(())
+    }
+
+    fn push_indent(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str(TAB);
+        }
+    }
+}
+
+/// The name to print for a struct or enum, `_` if `value` doesn't know its type.
+fn type_name(value: &dyn Reflect, is_value: bool) -> &str {
+    if is_value {
+        "_"
+    } else {
+        short_type_name(value.type_name())
+    }
+}
+
+/// How deeply values can be nested before parsing fails, so deeply nested input can't overflow
+/// the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    input: &'a str,
+    cursor: Cursor<'a>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            cursor: Cursor::new(input),
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self, ty: Option<Type<'_>>) -> Result<Value, ParseError> {
+        let value = self.parse_value(ty)?;
+        self.skip_trivia();
+        if !self.cursor.is_at_end() {
+            return Err(self.error(ParseErrorKind::TrailingCharacters));
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self, ty: Option<Type<'_>>) -> Result<Value, ParseError> {
+        // opaque types don't say what they contain
+        let ty = ty.filter(|ty| !matches!(ty, Type::Opaque(_)));
+
+        self.skip_trivia();
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(ParseErrorKind::TooDeep));
+        }
+        self.depth += 1;
+        let value = self.parse_value_inner(ty);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_value_inner(&mut self, ty: Option<Type<'_>>) -> Result<Value, ParseError> {
+        let start = self.cursor.position();
+        match self.cursor.peek() {
+            Some('[') => self.parse_list(ty, start),
+            Some('{') => self.parse_map_or_set(ty, start),
+            Some('(') => self.parse_tuple(ty, start),
+            Some(c) if (c.is_alphabetic() || c == '_') && !self.is_literal_keyword() => {
+                let name = self.cursor.ident().unwrap();
+                self.parse_named(name, ty, start)
+            }
+            Some(_) => self.parse_scalar(ty, start),
+            None => Err(self.error(ParseErrorKind::ExpectedValue)),
+        }
+    }
+
+    /// Whether the next identifier is a literal such as `true` or `inf_f32`, rather than a name.
+    fn is_literal_keyword(&self) -> bool {
+        let ident = self.cursor.clone().ident().unwrap_or_default();
+        match ident {
+            "true" | "false" => true,
+            _ => ["inf", "NaN"].iter().any(|keyword| {
+                ident.strip_prefix(keyword).map_or(false, |suffix| {
+                    matches!(suffix, "" | "_f32" | "_f64" | "f32" | "f64")
+                })
+            }),
+        }
+    }
+
+    fn parse_scalar(&mut self, ty: Option<Type<'_>>, start: usize) -> Result<Value, ParseError> {
+        let value = parse_literal(&mut self.cursor).map_err(|err| {
+            // key path errors count characters from the start of the input
+            let position = self
+                .input
+                .char_indices()
+                .nth(err.column() - 1)
+                .map_or(self.input.len(), |(position, _)| position);
+            self.error_at(position, ParseErrorKind::InvalidLiteral(err.kind()))
+        })?;
+
+        match ty {
+            Some(Type::Scalar(scalar_ty)) => convert_scalar(value, scalar_ty)
+                .ok_or_else(|| self.type_mismatch(Type::Scalar(scalar_ty), start)),
+            Some(ty) => Err(self.type_mismatch(ty, start)),
+            None => Ok(value),
+        }
+    }
+
+    fn parse_named(
+        &mut self,
+        name: &str,
+        ty: Option<Type<'_>>,
+        start: usize,
+    ) -> Result<Value, ParseError> {
+        if let Some(ty) = ty {
+            let expected = short_type_name(ty.type_name());
+            if name != "_"
+                && name != expected
+                && matches!(ty, Type::Struct(_) | Type::TupleStruct(_) | Type::Enum(_))
+            {
+                return Err(self.error_at(
+                    start,
+                    ParseErrorKind::WrongTypeName {
+                        expected: expected.to_owned(),
+                        found: name.to_owned(),
+                    },
+                ));
+            }
+        }
+
+        self.skip_trivia();
+        if self.cursor.eat("::") {
+            let enum_ty = match ty {
+                Some(Type::Enum(enum_ty)) => Some(enum_ty),
+                Some(ty) => return Err(self.type_mismatch(ty, start)),
+                None => None,
+            };
+
+            self.skip_trivia();
+            let variant_start = self.cursor.position();
+            let variant = self
+                .cursor
+                .ident()
+                .ok_or_else(|| self.error(ParseErrorKind::ExpectedVariant))?;
+            let variant_ty = match enum_ty {
+                Some(enum_ty) => Some(enum_ty.variant(variant).ok_or_else(|| {
+                    self.error_at(
+                        variant_start,
+                        ParseErrorKind::UnknownVariant(variant.to_owned()),
+                    )
+                })?),
+                None => None,
+            };
+
+            self.skip_trivia();
+            let value = match self.cursor.peek() {
+                Some('{') => {
+                    let fields = match variant_ty {
+                        Some(Variant::Struct(_)) | None => {
+                            self.parse_fields(variant_ty.map(|variant_ty| {
+                                variant_ty
+                                    .field_types()
+                                    .map(|field_ty| (field_ty.name().unwrap(), field_ty.get_type()))
+                                    .collect()
+                            }))?
+                        }
+                        Some(Variant::Tuple(_) | Variant::Unit(_)) => {
+                            return Err(self.type_mismatch(ty.unwrap(), start))
+                        }
+                    };
+                    let mut variant =
+                        EnumValue::new_struct_variant_with_capacity(variant, fields.len());
+                    for (name, value) in fields {
+                        variant.set_struct_field(name, value);
+                    }
+                    variant.finish()
+                }
+                Some('(') => {
+                    let fields = match variant_ty {
+                        Some(Variant::Tuple(_)) | None => {
+                            self.parse_unnamed_fields(variant_ty.map(|variant_ty| {
+                                variant_ty
+                                    .field_types()
+                                    .map(|field_ty| field_ty.get_type())
+                                    .collect()
+                            }))?
+                        }
+                        Some(Variant::Struct(_) | Variant::Unit(_)) => {
+                            return Err(self.type_mismatch(ty.unwrap(), start))
+                        }
+                    };
+                    let mut variant =
+                        EnumValue::new_tuple_variant_with_capacity(variant, fields.len());
+                    for value in fields {
+                        variant.push_tuple_field(value);
+                    }
+                    variant.finish()
+                }
+                _ => match variant_ty {
+                    Some(Variant::Unit(_)) | None => EnumValue::new_unit_variant(variant),
+                    Some(Variant::Struct(_) | Variant::Tuple(_)) => {
+                        return Err(self.type_mismatch(ty.unwrap(), start))
+                    }
+                },
+            };
+            return Ok(value.into());
+        }
+
+        match self.cursor.peek() {
+            Some('{') => {
+                let fields = match ty {
+                    Some(Type::Struct(struct_ty)) => self.parse_fields(Some(
+                        struct_ty
+                            .field_types()
+                            .map(|field_ty| (field_ty.name(), field_ty.get_type()))
+                            .collect(),
+                    ))?,
+                    Some(ty) => return Err(self.type_mismatch(ty, start)),
+                    None => self.parse_fields(None)?,
+                };
+                let mut struct_ = StructValue::with_capacity(fields.len());
+                for (name, value) in fields {
+                    struct_.set_field(name, value);
+                }
+                Ok(struct_.into())
+            }
+            Some('(') => {
+                let fields = match ty {
+                    Some(Type::TupleStruct(tuple_struct_ty)) => self.parse_unnamed_fields(Some(
+                        tuple_struct_ty
+                            .field_types()
+                            .map(|field_ty| field_ty.get_type())
+                            .collect(),
+                    ))?,
+                    Some(ty) => return Err(self.type_mismatch(ty, start)),
+                    None => self.parse_unnamed_fields(None)?,
+                };
+                let mut tuple_struct = TupleStructValue::with_capacity(fields.len());
+                for value in fields {
+                    tuple_struct.push_field(value);
+                }
+                Ok(tuple_struct.into())
+            }
+            _ => Err(self.error(ParseErrorKind::ExpectedToken("{"))),
+        }
+    }
+
+    /// Parse `{ name: value, .. }`. If `field_tys` is given, the fields are returned in that
+    /// order, otherwise in the order of the input.
+    fn parse_fields(
+        &mut self,
+        field_tys: Option<Vec<(&str, Type<'_>)>>,
+    ) -> Result<Vec<(String, Value)>, ParseError> {
+        let start = self.cursor.position();
+        let mut fields: Vec<(String, Value)> = Vec::new();
+        let mut names = BTreeSet::new();
+        let mut typed_fields = field_tys
+            .as_ref()
+            .map(|field_tys| vec![None; field_tys.len()]);
+
+        self.parse_seq("{", "}", |this| {
+            let name_start = this.cursor.position();
+            let name = this
+                .cursor
+                .ident()
+                .ok_or_else(|| this.error(ParseErrorKind::ExpectedField))?;
+            this.skip_trivia();
+            if !this.cursor.eat(":") {
+                return Err(this.error(ParseErrorKind::ExpectedToken(":")));
+            }
+
+            let duplicate = |this: &Self| {
+                this.error_at(name_start, ParseErrorKind::DuplicateField(name.to_owned()))
+            };
+            match (&field_tys, &mut typed_fields) {
+                (Some(field_tys), Some(typed_fields)) => {
+                    let index = field_tys
+                        .iter()
+                        .position(|(field_name, _)| *field_name == name)
+                        .ok_or_else(|| {
+                            this.error_at(name_start, ParseErrorKind::UnknownField(name.to_owned()))
+                        })?;
+                    if typed_fields[index].is_some() {
+                        return Err(duplicate(this));
+                    }
+                    typed_fields[index] = Some(this.parse_value(Some(field_tys[index].1))?);
+                }
+                _ => {
+                    if !names.insert(name) {
+                        return Err(duplicate(this));
+                    }
+                    let value = this.parse_value(None)?;
+                    fields.push((name.to_owned(), value));
+                }
+            }
+            Ok(())
+        })?;
+
+        match (field_tys, typed_fields) {
+            (Some(field_tys), Some(typed_fields)) => field_tys
+                .into_iter()
+                .zip(typed_fields)
+                .map(|((name, _), value)| match value {
+                    Some(value) => Ok((name.to_owned(), value)),
+                    None => {
+                        Err(self.error_at(start, ParseErrorKind::MissingField(name.to_owned())))
+                    }
+                })
+                .collect(),
+            _ => Ok(fields),
+        }
+    }
+
+    /// Parse `(value, ..)`, checking the number of fields if `field_tys` is given.
+    fn parse_unnamed_fields(
+        &mut self,
+        field_tys: Option<Vec<Type<'_>>>,
+    ) -> Result<Vec<Value>, ParseError> {
+        let start = self.cursor.position();
+        let mut fields = Vec::new();
+        self.parse_seq("(", ")", |this| {
+            let ty = match &field_tys {
+                Some(field_tys) => match field_tys.get(fields.len()) {
+                    Some(ty) => Some(*ty),
+                    None => {
+                        return Err(this.error_at(
+                            start,
+                            ParseErrorKind::LengthMismatch {
+                                expected: field_tys.len(),
+                                found: fields.len() + 1,
+                            },
+                        ))
+                    }
+                },
+                None => None,
+            };
+            fields.push(this.parse_value(ty)?);
+            Ok(())
+        })?;
+
+        match field_tys {
+            Some(field_tys) if field_tys.len() != fields.len() => Err(self.error_at(
+                start,
+                ParseErrorKind::LengthMismatch {
+                    expected: field_tys.len(),
+                    found: fields.len(),
+                },
+            )),
+            Some(_) | None => Ok(fields),
+        }
+    }
+
+    fn parse_tuple(&mut self, ty: Option<Type<'_>>, start: usize) -> Result<Value, ParseError> {
+        let field_tys = match ty {
+            Some(Type::Tuple(tuple_ty)) => Some(
+                tuple_ty
+                    .field_types()
+                    .map(|field_ty| field_ty.get_type())
+                    .collect(),
+            ),
+            Some(ty) => return Err(self.type_mismatch(ty, start)),
+            None => None,
+        };
+        let fields = self.parse_unnamed_fields(field_tys)?;
+        let mut tuple = TupleValue::with_capacity(fields.len());
+        for value in fields {
+            tuple.push_field(value);
+        }
+        Ok(tuple.into())
+    }
+
+    fn parse_list(&mut self, ty: Option<Type<'_>>, start: usize) -> Result<Value, ParseError> {
+        let (element_ty, len) = match ty {
+            Some(Type::List(list_ty)) => (Some(list_ty.element_type()), None),
+            Some(Type::Array(array_ty)) => (Some(array_ty.element_type()), Some(array_ty.len())),
+            Some(ty) => return Err(self.type_mismatch(ty, start)),
+            None => (None, None),
+        };
+
+        let mut elements = Vec::new();
+        self.parse_seq("[", "]", |this| {
+            elements.push(this.parse_value(element_ty)?);
+            Ok(())
+        })?;
+
+        match len {
+            Some(len) if len != elements.len() => Err(self.error_at(
+                start,
+                ParseErrorKind::LengthMismatch {
+                    expected: len,
+                    found: elements.len(),
+                },
+            )),
+            Some(_) | None => Ok(Value::List(elements)),
+        }
+    }
+
+    fn parse_map_or_set(
+        &mut self,
+        ty: Option<Type<'_>>,
+        start: usize,
+    ) -> Result<Value, ParseError> {
+        enum Entries {
+            Unknown,
+            Map(BTreeMap<Value, Value>),
+            Set(BTreeSet<Value>),
+        }
+
+        let (mut entries, key_ty, value_ty) = match ty {
+            Some(Type::Map(map_ty)) => (
+                Entries::Map(BTreeMap::new()),
+                Some(map_ty.key_type()),
+                Some(map_ty.value_type()),
+            ),
+            Some(Type::Set(set_ty)) => (
+                Entries::Set(BTreeSet::new()),
+                Some(set_ty.element_type()),
+                None,
+            ),
+            Some(ty) => return Err(self.type_mismatch(ty, start)),
+            None => (Entries::Unknown, None, None),
+        };
+
+        self.parse_seq("{", "}", |this| {
+            let key = this.parse_value(key_ty)?;
+            this.skip_trivia();
+            let is_entry = this.cursor.peek() == Some(':');
+            match (&mut entries, is_entry) {
+                (Entries::Unknown, true) => entries = Entries::Map(BTreeMap::new()),
+                (Entries::Unknown, false) => entries = Entries::Set(BTreeSet::new()),
+                (Entries::Map(_), true) | (Entries::Set(_), false) => {}
+                (Entries::Map(_), false) => {
+                    return Err(this.error(ParseErrorKind::ExpectedToken(":")))
+                }
+                (Entries::Set(_), true) => {
+                    return Err(this.error(ParseErrorKind::ExpectedToken(",")))
+                }
+            }
+            match &mut entries {
+                Entries::Map(map) => {
+                    this.cursor.bump();
+                    let value = this.parse_value(value_ty)?;
+                    map.insert(key, value);
+                }
+                Entries::Set(set) => {
+                    set.insert(key);
+                }
+                Entries::Unknown => unreachable!(),
+            }
+            Ok(())
+        })?;
+
+        match entries {
+            Entries::Map(map) => Ok(Value::Map(map)),
+            Entries::Set(set) => Ok(Value::Set(set)),
+            // `{}` could be either
+            Entries::Unknown => Ok(Value::Map(BTreeMap::new())),
+        }
+    }
+
+    /// Parse items separated by commas between `open` and `close`, allowing a trailing comma.
+    fn parse_seq(
+        &mut self,
+        open: &'static str,
+        close: &'static str,
+        mut parse_item: impl FnMut(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        self.skip_trivia();
+        if !self.cursor.eat(open) {
+            return Err(self.error(ParseErrorKind::ExpectedToken(open)));
+        }
+        loop {
+            self.skip_trivia();
+            if self.cursor.eat(close) {
+                return Ok(());
+            }
+            parse_item(self)?;
+            self.skip_trivia();
+            if self.cursor.eat(close) {
+                return Ok(());
+            }
+            if !self.cursor.eat(",") {
+                return Err(self.error(ParseErrorKind::ExpectedToken(close)));
+            }
+        }
+    }
+
+    /// Skip whitespace and `//` comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.cursor.skip_whitespace();
+            if !self.cursor.eat("//") {
+                return;
+            }
+            self.cursor.take_while(|c| c != '\n');
+        }
+    }
+
+    fn type_mismatch(&self, ty: Type<'_>, position: usize) -> ParseError {
+        self.error_at(
+            position,
+            ParseErrorKind::TypeMismatch {
+                expected: ty.type_name().to_owned(),
+            },
+        )
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.cursor.position(), kind)
+    }
+
+    fn error_at(&self, position: usize, kind: ParseErrorKind) -> ParseError {
+        error_at(self.input, position, kind)
+    }
+}
+
+fn error_at(input: &str, position: usize, kind: ParseErrorKind) -> ParseError {
+    let before = &input[..position];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    ParseError {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        kind,
+    }
+}
+
+/// Convert a number to another number type if it fits. Other scalars must already be of type
+/// `ty`.
+fn convert_scalar(value: Value, ty: ScalarType) -> Option<Value> {
+    let n = match value {
+        Value::usize(n) => n as i128,
+        Value::u8(n) => n.into(),
+        Value::u16(n) => n.into(),
+        Value::u32(n) => n.into(),
+        Value::u64(n) => n.into(),
+        Value::u128(n) => match i128::try_from(n) {
+            Ok(n) => n,
+            Err(_) => return (ty.underlying() == ScalarType::u128).then_some(Value::u128(n)),
+        },
+        Value::i8(n) => n.into(),
+        Value::i16(n) => n.into(),
+        Value::i32(n) => n.into(),
+        Value::i64(n) => n.into(),
+        Value::i128(n) => n,
+        Value::f32(n) => return convert_float(n.into(), ty),
+        Value::f64(n) => return convert_float(n, ty),
+        Value::bool(_) | Value::char(_) | Value::String(_) => {
+            let is_type = matches!(
+                (&value, ty),
+                (Value::bool(_), ScalarType::bool)
+                    | (Value::char(_), ScalarType::char)
+                    | (Value::String(_), ScalarType::String)
+            );
+            return is_type.then_some(value);
+        }
+        Value::StructValue(_)
+        | Value::EnumValue(_)
+        | Value::TupleStructValue(_)
+        | Value::TupleValue(_)
+        | Value::List(_)
+        | Value::Map(_)
+        | Value::Set(_) => return None,
+    };
+
+    let value = match ty.underlying() {
+        ScalarType::usize => usize::try_from(n).ok().map(Value::usize),
+        ScalarType::u8 => u8::try_from(n).ok().map(Value::u8),
+        ScalarType::u16 => u16::try_from(n).ok().map(Value::u16),
+        ScalarType::u32 => u32::try_from(n).ok().map(Value::u32),
+        ScalarType::u64 => u64::try_from(n).ok().map(Value::u64),
+        ScalarType::u128 => u128::try_from(n).ok().map(Value::u128),
+        ScalarType::i8 => i8::try_from(n).ok().map(Value::i8),
+        ScalarType::i16 => i16::try_from(n).ok().map(Value::i16),
+        ScalarType::i32 => i32::try_from(n).ok().map(Value::i32),
+        ScalarType::i64 => i64::try_from(n).ok().map(Value::i64),
+        ScalarType::i128 => Some(Value::i128(n)),
+        ScalarType::f32 => Some(Value::f32(n as f32)),
+        ScalarType::f64 => Some(Value::f64(n as f64)),
+        ScalarType::bool | ScalarType::char | ScalarType::String => None,
+        _ => unreachable!("`underlying` returns the scalar `NonZero*` types contain"),
+    };
+    value.filter(|value| ty.allows(value))
+}
+
+fn convert_float(n: f64, ty: ScalarType) -> Option<Value> {
+    match ty {
+        ScalarType::f32 => Some(Value::f32(n as f32)),
+        ScalarType::f64 => Some(Value::f64(n)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::key_path;
+    use crate::key_path::GetPath;
+    use crate::DescribeType;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Save {
+        name: String,
+        level: u8,
+        offset: i64,
+        position: [f32; 3],
+        inventory: Vec<Item>,
+        flags: BTreeSet<char>,
+        scores: HashMap<String, u128>,
+        pair: (bool, f64),
+        single: (i32,),
+        unit: (),
+        id: Id,
+        best: Option<i128>,
+        empty: Empty,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        Sword { damage: u32 },
+        Potion(u16, u16),
+        Key,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Id(usize);
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Empty {}
+
+    fn save() -> Save {
+        Save {
+            name: "Ferris \"the crab\"\n".to_owned(),
+            level: 12,
+            offset: -300,
+            position: [1.0, f32::INFINITY, f32::NAN],
+            inventory: vec![Item::Sword { damage: 50 }, Item::Potion(1, 2), Item::Key],
+            flags: BTreeSet::from(['a', '🦀']),
+            scores: HashMap::from([("level 1".to_owned(), u128::MAX)]),
+            pair: (true, -0.5),
+            single: (1,),
+            unit: (),
+            id: Id(1234),
+            best: None,
+            empty: Empty {},
+        }
+    }
+
+    fn ty<T>() -> Type<'static>
+    where
+        T: DescribeType,
+    {
+        match <T as DescribeType>::type_descriptor() {
+            alloc::borrow::Cow::Borrowed(descriptor) => descriptor.get_type(),
+            alloc::borrow::Cow::Owned(_) => unreachable!("descriptors are cached with std"),
+        }
+    }
+
+    #[test]
+    fn print() {
+        let save = save();
+        assert_eq!(
+            super::print(&save).unwrap(),
+            r#"Save { name: "Ferris \"the crab\"\n", level: 12_u8, offset: -300_i64, position: [1.0_f32, inf_f32, NaN_f32], inventory: [Item::Sword { damage: 50_u32 }, Item::Potion(1_u16, 2_u16), Item::Key], flags: {'a', '🦀'}, scores: {"level 1": 340282366920938463463374607431768211455_u128}, pair: (true, -0.5), single: (1,), unit: (), id: Id(1234_usize), best: Option::None, empty: Empty {} }"#,
+        );
+
+        assert_eq!(
+            super::print_pretty(&(Item::Sword { damage: 1 }, vec![Some(Id(1))], ())).unwrap(),
+            "(
+    Item::Sword {
+        damage: 1_u32,
+    },
+    [
+        Option::Some(
+            Id(
+                1_usize,
+            ),
+        ),
+    ],
+    (),
+)"
+        );
+
+        // values don't know their type names
+        assert_eq!(
+            super::print(&Item::Potion(1, 2).to_value()).unwrap(),
+            "_::Potion(1_u16, 2_u16)"
+        );
+        assert_eq!(super::print(&Id(1).to_value()).unwrap(), "_(1_usize)");
+    }
+
+    #[test]
+    fn round_trip() {
+        let save = save();
+        let value = save.to_value();
+
+        for text in [
+            super::print(&save).unwrap(),
+            super::print_pretty(&save).unwrap(),
+        ] {
+            let parsed = parse(&text).unwrap();
+            // `NaN` isn't equal to itself so compare the text
+            assert_eq!(
+                super::print(&parsed).unwrap(),
+                super::print(&value).unwrap()
+            );
+
+            let parsed = parse_typed(&text, ty::<Save>()).unwrap();
+            let save_again = Save::from_reflect(&parsed).unwrap();
+            assert_eq!(
+                super::print(&save_again).unwrap(),
+                super::print(&save).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn parse_untyped() {
+        assert_eq!(parse("1_u8").unwrap(), Value::u8(1));
+        assert_eq!(parse("-inf_f32").unwrap(), Value::f32(f32::NEG_INFINITY));
+        assert_eq!(
+            parse("[1, {'a': true}, {1_u8, 2_u8}, {}]").unwrap(),
+            Value::List(vec![
+                1.into(),
+                Value::Map(BTreeMap::from([('a'.into(), true.into())])),
+                Value::Set(BTreeSet::from([1_u8.into(), 2_u8.into()])),
+                Value::Map(BTreeMap::new()),
+            ])
+        );
+
+        let value = parse(
+            "
+            // comments and trailing commas are fine
+            Foo {
+                a: Bar(1,), // numbers are `i32` by default
+                b: _::Baz { c: \"c\" },
+            }
+            ",
+        )
+        .unwrap();
+        assert_eq!(value.get_at::<i32>(&key_path!(.a.0)).unwrap(), &1);
+        assert_eq!(value.get_at::<String>(&key_path!(.b::Baz.c)).unwrap(), "c");
+    }
+
+    #[test]
+    fn parse_typed_converts_numbers() {
+        let value = parse_typed("(1, -1, 1.5, 1, _(2))", ty::<(u8, i64, f32, f64, Id)>()).unwrap();
+        assert_eq!(
+            <(u8, i64, f32, f64, Id)>::from_reflect(&value).unwrap(),
+            (1, -1, 1.5, 1.0, Id(2))
+        );
+    }
+
+    #[test]
+    fn looks_through_guards() {
+        #[derive(Reflect, Debug)]
+        #[reflect(crate_name(crate), opt_out(Clone))]
+        struct Shared {
+            inner: Mutex<Vec<u8>>,
+            name: Box<str>,
+        }
+
+        let shared = Shared {
+            inner: Mutex::new(vec![1, 2]),
+            name: "a".into(),
+        };
+        assert_eq!(
+            super::print(&shared).unwrap(),
+            r#"Shared { inner: [1_u8, 2_u8], name: "a" }"#
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+
+        let err = parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+        assert_eq!(err.to_string(), "values are nested too deeply at 1:129");
+
+        let err = parse(&"[".repeat(200_000)).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn errors() {
+        fn error(input: &str, ty: Option<Type<'_>>) -> String {
+            match ty {
+                Some(ty) => parse_typed(input, ty),
+                None => parse(input),
+            }
+            .unwrap_err()
+            .to_string()
+        }
+
+        assert_eq!(error("[1, 2", None), "expected `]` at 1:6");
+        assert_eq!(
+            error("1 2", None),
+            "trailing characters after the value at 1:3"
+        );
+        assert_eq!(error("\n  1_foo", None), "invalid number suffix at 2:4");
+        assert_eq!(error("Foo", None), "expected `{` at 1:4");
+        // map keys are values, not names
+        assert_eq!(error("{a: 1}", None), "expected `{` at 1:3");
+        assert_eq!(
+            error("Foo { a: 1, a: 2 }", None),
+            "duplicate field `a` at 1:13"
+        );
+
+        let item = Some(ty::<Item>());
+        assert_eq!(
+            error("Item::Shield", item),
+            "unknown variant `Shield` at 1:7"
+        );
+        assert_eq!(
+            error("Thing::Key", item),
+            "expected `Item`, found `Thing` at 1:1"
+        );
+        assert_eq!(
+            error("Item::Key(1)", item),
+            "expected `mirror_mirror::text::tests::Item` at 1:1"
+        );
+        assert_eq!(
+            error("Item::Sword { damage: 1, weight: 2 }", item),
+            "unknown field `weight` at 1:26"
+        );
+        assert_eq!(
+            error("Item::Sword {}", item),
+            "missing field `damage` at 1:13"
+        );
+        assert_eq!(
+            error("Item::Sword { damage: -1 }", item),
+            "expected `u32` at 1:23"
+        );
+        assert_eq!(
+            error("0", Some(ty::<core::num::NonZeroU8>())),
+            format!(
+                "expected `{}` at 1:1",
+                core::any::type_name::<core::num::NonZeroU8>()
+            )
+        );
+        assert_eq!(
+            error("Item::Potion(1)", item),
+            "expected 2 elements, found 1 at 1:13"
+        );
+        assert_eq!(
+            error("[1, 2]", Some(ty::<[u8; 3]>())),
+            "expected 3 elements, found 2 at 1:1"
+        );
+    }
+}
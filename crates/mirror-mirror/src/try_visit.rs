@@ -1,8 +1,33 @@
 use crate::{
-    type_info::{OpaqueType, Type, VariantField},
-    Reflect, ScalarRef,
+    array::Array,
+    enum_::Enum,
+    key_path::{Key, KeyPath, NamedOrNumbered},
+    list::List,
+    map::Map,
+    set::Set,
+    struct_::Struct,
+    tuple::Tuple,
+    tuple_struct::TupleStruct,
+    type_info::{
+        ArrayType, EnumType, ListType, MapType, OpaqueType, SetType, StructType, TupleStructType,
+        TupleType, Type, Variant, VariantField,
+    },
+    PatchError, Reflect, ReflectMut, ReflectRef, ScalarMut, ScalarRef, Value,
 };
+use alloc::borrow::ToOwned;
 use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Returned by the `enter_*` hooks of [`TryVisit`] and [`TryVisitMut`] to control whether the
+/// value should be visited further.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Enter {
+    /// Visit the fields or elements of the value.
+    #[default]
+    Continue,
+    /// Skip the fields or elements of the value. The matching `leave_*` hook is still called.
+    Skip,
+}
 
 macro_rules! visit_scalar_fn {
     ($name:ident, $ty:ty) => {
@@ -14,6 +39,98 @@ macro_rules! visit_scalar_fn {
     };
 }
 
+macro_rules! enter_leave_fns {
+    ($enter:ident, $leave:ident, $value:ty, $ty:ty) => {
+        #[inline]
+        fn $enter(
+            &mut self,
+            value: $value,
+            ty: $ty,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            Ok(Enter::Continue)
+        }
+
+        #[inline]
+        fn $leave(
+            &mut self,
+            value: $value,
+            ty: $ty,
+            key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    };
+}
+
+/// Visit the scalars and opaque values in a value, guided by its [`Type`].
+///
+/// In addition to the callbacks for scalars and opaque values there are hooks that are called
+/// when the visitor enters or leaves containers, fields, list elements, and map entries. The hooks
+/// receive the [`KeyPath`] of the value being entered, so the scalars visited between
+/// `enter_field` and `leave_field` are at that path. The key path of enum variants ends with
+/// `::VariantName`. Map keys are visited before map values and both are at the key path of the
+/// entry.
+///
+/// Returning [`Enter::Skip`] from an `enter_*` hook skips the contents of the value.
+///
+/// # Example
+///
+/// ```
+/// use core::convert::Infallible;
+/// use mirror_mirror::{
+///     key_path::{KeyPath, NamedOrNumbered},
+///     try_visit::{try_visit, Enter, TryVisit},
+///     type_info::DescribeType,
+///     Reflect,
+/// };
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct User {
+///     name: String,
+///     password: String,
+/// }
+///
+/// // collect every string except passwords
+/// #[derive(Default)]
+/// struct Strings(Vec<String>);
+///
+/// impl TryVisit for Strings {
+///     type Error = Infallible;
+///
+///     fn try_visit_string(&mut self, value: &String) -> Result<(), Self::Error> {
+///         self.0.push(value.clone());
+///         Ok(())
+///     }
+///
+///     fn enter_field(
+///         &mut self,
+///         field: &NamedOrNumbered,
+///         _: &KeyPath,
+///     ) -> Result<Enter, Self::Error> {
+///         if field == &NamedOrNumbered::Named("password".to_owned()) {
+///             Ok(Enter::Skip)
+///         } else {
+///             Ok(Enter::Continue)
+///         }
+///     }
+/// }
+///
+/// let user = User {
+///     name: "Alice".to_owned(),
+///     password: "hunter2".to_owned(),
+/// };
+///
+/// let mut visitor = Strings::default();
+/// try_visit(
+///     &mut visitor,
+///     &user,
+///     <User as DescribeType>::type_descriptor().get_type(),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(visitor.0, ["Alice"]);
+/// ```
 #[allow(unused_variables)]
 pub trait TryVisit {
     type Error;
@@ -43,12 +160,130 @@ pub trait TryVisit {
     ) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    /// Called instead of visiting a value that's locked, such as a `Mutex` that's held by another
+    /// thread or a `RefCell` that's mutably borrowed.
+    ///
+    /// `value` is the lock itself. The default implementation skips the value.
+    #[inline]
+    fn try_visit_locked(
+        &mut self,
+        value: &dyn Reflect,
+        ty: Type<'_>,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    enter_leave_fns!(enter_struct, leave_struct, &dyn Struct, StructType<'_>);
+    enter_leave_fns!(
+        enter_tuple_struct,
+        leave_tuple_struct,
+        &dyn TupleStruct,
+        TupleStructType<'_>
+    );
+    enter_leave_fns!(enter_tuple, leave_tuple, &dyn Tuple, TupleType<'_>);
+    enter_leave_fns!(enter_enum, leave_enum, &dyn Enum, EnumType<'_>);
+    enter_leave_fns!(enter_variant, leave_variant, &dyn Enum, Variant<'_>);
+    enter_leave_fns!(enter_list, leave_list, &dyn List, ListType<'_>);
+    enter_leave_fns!(enter_array, leave_array, &dyn Array, ArrayType<'_>);
+    enter_leave_fns!(enter_map, leave_map, &dyn Map, MapType<'_>);
+    enter_leave_fns!(enter_set, leave_set, &dyn Set, SetType<'_>);
+
+    /// Called before visiting a field of a struct, tuple struct, tuple, or enum variant.
+    #[inline]
+    fn enter_field(
+        &mut self,
+        field: &NamedOrNumbered,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_field(
+        &mut self,
+        field: &NamedOrNumbered,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting an element of a list or array.
+    #[inline]
+    fn enter_list_element(
+        &mut self,
+        index: usize,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_list_element(&mut self, index: usize, key_path: &KeyPath) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting the key and value of a map entry.
+    #[inline]
+    fn enter_map_entry(
+        &mut self,
+        key: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_map_entry(
+        &mut self,
+        key: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting an element of a set.
+    #[inline]
+    fn enter_set_element(
+        &mut self,
+        element: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_set_element(
+        &mut self,
+        element: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
 pub fn try_visit<V>(visitor: &mut V, value: &dyn Reflect, ty: Type<'_>) -> Result<(), V::Error>
 where
     V: TryVisit,
 {
+    try_visit_at(visitor, value, ty, &mut KeyPath::default())
+}
+
+fn try_visit_at<V>(
+    visitor: &mut V,
+    value: &dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisit,
+{
+    let value = &*value.reflect_guard();
+    if !matches!(ty, Type::Opaque(_)) && matches!(value.reflect_ref(), ReflectRef::Opaque(_)) {
+        return visitor.try_visit_locked(value, ty, key_path);
+    }
+
     match ty {
         Type::Scalar(_) => {
             let scalar = value.as_scalar().unwrap();
@@ -68,72 +303,141 @@ where
                 ScalarRef::i128(inner) => visitor.try_visit_i128(inner)?,
                 ScalarRef::char(inner) => visitor.try_visit_char(inner)?,
                 ScalarRef::f64(inner) => visitor.try_visit_f64(inner)?,
-                ScalarRef::String(inner) => visitor.try_visit_string(inner)?,
+                // string-like types such as `Box<str>` don't contain a `String` to visit
+                ScalarRef::String(inner) => match inner.as_reflect().downcast_ref::<String>() {
+                    Some(string) => visitor.try_visit_string(string)?,
+                    None => visitor.try_visit_string(&inner.as_str().to_owned())?,
+                },
             }
         }
         Type::Struct(struct_ty) => {
             let struct_ = value.as_struct().unwrap();
 
-            for field_ty in struct_ty.field_types() {
-                let field = struct_.field(field_ty.name()).unwrap();
-                try_visit(visitor, field, field_ty.get_type())?;
+            if visitor.enter_struct(struct_, struct_ty, key_path)? == Enter::Continue {
+                for field_ty in struct_ty.field_types() {
+                    let field = struct_.field(field_ty.name()).unwrap();
+                    let name = NamedOrNumbered::Named(field_ty.name().to_owned());
+                    try_visit_field(visitor, name, field, field_ty.get_type(), key_path)?;
+                }
             }
+
+            visitor.leave_struct(struct_, struct_ty, key_path)?;
         }
         Type::TupleStruct(tuple_struct_ty) => {
             let tuple_struct = value.as_tuple_struct().unwrap();
 
-            for (idx, field_ty) in tuple_struct_ty.field_types().enumerate() {
-                let field = tuple_struct.field_at(idx).unwrap();
-                try_visit(visitor, field, field_ty.get_type())?;
+            if visitor.enter_tuple_struct(tuple_struct, tuple_struct_ty, key_path)?
+                == Enter::Continue
+            {
+                for (idx, field_ty) in tuple_struct_ty.field_types().enumerate() {
+                    let field = tuple_struct.field_at(idx).unwrap();
+                    let index = NamedOrNumbered::Numbered(idx);
+                    try_visit_field(visitor, index, field, field_ty.get_type(), key_path)?;
+                }
             }
+
+            visitor.leave_tuple_struct(tuple_struct, tuple_struct_ty, key_path)?;
         }
         Type::Tuple(tuple_ty) => {
             let tuple = value.as_tuple().unwrap();
 
-            for (idx, field_ty) in tuple_ty.field_types().enumerate() {
-                let field = tuple.field_at(idx).unwrap();
-                try_visit(visitor, field, field_ty.get_type())?;
+            if visitor.enter_tuple(tuple, tuple_ty, key_path)? == Enter::Continue {
+                for (idx, field_ty) in tuple_ty.field_types().enumerate() {
+                    let field = tuple.field_at(idx).unwrap();
+                    let index = NamedOrNumbered::Numbered(idx);
+                    try_visit_field(visitor, index, field, field_ty.get_type(), key_path)?;
+                }
             }
+
+            visitor.leave_tuple(tuple, tuple_ty, key_path)?;
         }
         Type::Enum(enum_ty) => {
             let enum_ = value.as_enum().unwrap();
-            let variant_ty = enum_ty.variant(enum_.variant_name()).unwrap();
 
-            for (idx, field_ty) in variant_ty.field_types().enumerate() {
-                let field = match field_ty {
-                    VariantField::Named(named_field_ty) => {
-                        enum_.field(named_field_ty.name()).unwrap()
+            if visitor.enter_enum(enum_, enum_ty, key_path)? == Enter::Continue {
+                let variant_ty = enum_ty.variant(enum_.variant_name()).unwrap();
+                key_path.push(Key::variant(variant_ty.name()));
+
+                if visitor.enter_variant(enum_, variant_ty, key_path)? == Enter::Continue {
+                    for (idx, field_ty) in variant_ty.field_types().enumerate() {
+                        let (field, key) = match field_ty {
+                            VariantField::Named(named_field_ty) => (
+                                enum_.field(named_field_ty.name()).unwrap(),
+                                NamedOrNumbered::Named(named_field_ty.name().to_owned()),
+                            ),
+                            VariantField::Unnamed(_) => {
+                                (enum_.field_at(idx).unwrap(), NamedOrNumbered::Numbered(idx))
+                            }
+                        };
+                        try_visit_field(visitor, key, field, field_ty.get_type(), key_path)?;
                     }
-                    VariantField::Unnamed(_) => enum_.field_at(idx).unwrap(),
-                };
-                try_visit(visitor, field, field_ty.get_type())?;
+                }
+
+                visitor.leave_variant(enum_, variant_ty, key_path)?;
+                key_path.pop();
             }
+
+            visitor.leave_enum(enum_, enum_ty, key_path)?;
         }
         Type::List(list_ty) => {
             let list = value.as_list().unwrap();
             let element_ty = list_ty.element_type();
 
-            for element in list.iter() {
-                try_visit(visitor, element, element_ty)?;
+            if visitor.enter_list(list, list_ty, key_path)? == Enter::Continue {
+                for (idx, element) in list.iter().enumerate() {
+                    try_visit_element(visitor, idx, element, element_ty, key_path)?;
+                }
             }
+
+            visitor.leave_list(list, list_ty, key_path)?;
         }
         Type::Array(array_ty) => {
             let array = value.as_array().unwrap();
             let element_ty = array_ty.element_type();
 
-            for element in array.iter() {
-                try_visit(visitor, element, element_ty)?;
+            if visitor.enter_array(array, array_ty, key_path)? == Enter::Continue {
+                for (idx, element) in array.iter().enumerate() {
+                    try_visit_element(visitor, idx, element, element_ty, key_path)?;
+                }
             }
+
+            visitor.leave_array(array, array_ty, key_path)?;
         }
         Type::Map(map_ty) => {
             let map = value.as_map().unwrap();
             let key_ty = map_ty.key_type();
             let value_ty = map_ty.value_type();
 
-            for (key, value) in map.iter() {
-                try_visit(visitor, key, key_ty)?;
-                try_visit(visitor, value, value_ty)?;
+            if visitor.enter_map(map, map_ty, key_path)? == Enter::Continue {
+                for (key, value) in map.iter() {
+                    key_path.push(Key::Get(key.to_value()));
+                    if visitor.enter_map_entry(key, key_path)? == Enter::Continue {
+                        try_visit_at(visitor, key, key_ty, key_path)?;
+                        try_visit_at(visitor, value, value_ty, key_path)?;
+                    }
+                    visitor.leave_map_entry(key, key_path)?;
+                    key_path.pop();
+                }
+            }
+
+            visitor.leave_map(map, map_ty, key_path)?;
+        }
+        Type::Set(set_ty) => {
+            let set = value.as_set().unwrap();
+            let element_ty = set_ty.element_type();
+
+            if visitor.enter_set(set, set_ty, key_path)? == Enter::Continue {
+                for element in set.iter() {
+                    key_path.push(Key::Get(element.to_value()));
+                    if visitor.enter_set_element(element, key_path)? == Enter::Continue {
+                        try_visit_at(visitor, element, element_ty, key_path)?;
+                    }
+                    visitor.leave_set_element(element, key_path)?;
+                    key_path.pop();
+                }
             }
+
+            visitor.leave_set(set, set_ty, key_path)?;
         }
         Type::Opaque(opaque_ty) => {
             visitor.try_visit_opaque(value, opaque_ty)?;
@@ -143,6 +447,525 @@ where
     Ok(())
 }
 
+fn try_visit_field<V>(
+    visitor: &mut V,
+    field: NamedOrNumbered,
+    value: &dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisit,
+{
+    key_path.push(Key::Field(field.clone()));
+    if visitor.enter_field(&field, key_path)? == Enter::Continue {
+        try_visit_at(visitor, value, ty, key_path)?;
+    }
+    visitor.leave_field(&field, key_path)?;
+    key_path.pop();
+    Ok(())
+}
+
+fn try_visit_element<V>(
+    visitor: &mut V,
+    index: usize,
+    value: &dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisit,
+{
+    key_path.push(Key::get(index));
+    if visitor.enter_list_element(index, key_path)? == Enter::Continue {
+        try_visit_at(visitor, value, ty, key_path)?;
+    }
+    visitor.leave_list_element(index, key_path)?;
+    key_path.pop();
+    Ok(())
+}
+
+macro_rules! visit_scalar_mut_fn {
+    ($name:ident, $ty:ty) => {
+        #[inline]
+        fn $name(&mut self, value: &mut $ty, key_path: &KeyPath) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    };
+}
+
+/// Like [`TryVisit`] but visits values mutably.
+///
+/// Every method receives the [`KeyPath`] of the value being visited. The `enter_*` hooks are
+/// called before the fields or elements of a value are visited and the `leave_*` hooks after.
+/// Changes made by `enter_*` hooks, such as switching an enum to another variant, are visited.
+///
+/// Map keys cannot be changed in place so, unlike [`TryVisit`], only the values of maps are
+/// visited. Set elements are visited through a copy which replaces the element in the set if the
+/// visitor changed it.
+///
+/// Values that can only be changed by patching, such as `Box<str>`, are also visited through a
+/// copy that is patched back afterwards. Failures to do so are passed to
+/// [`patch_failed`](TryVisitMut::patch_failed).
+///
+/// # Example
+///
+/// ```
+/// use core::convert::Infallible;
+/// use mirror_mirror::{
+///     key_path::KeyPath,
+///     try_visit::{try_visit_mut, TryVisitMut},
+///     type_info::DescribeType,
+///     Reflect,
+/// };
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     health: f32,
+///     items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Item {
+///     durability: f32,
+/// }
+///
+/// // clamp every `f32` between 0 and 1
+/// struct Clamp;
+///
+/// impl TryVisitMut for Clamp {
+///     type Error = Infallible;
+///
+///     fn try_visit_f32(&mut self, value: &mut f32, _: &KeyPath) -> Result<(), Self::Error> {
+///         *value = value.clamp(0.0, 1.0);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut player = Player {
+///     health: 2.0,
+///     items: Vec::from([Item { durability: -1.0 }]),
+/// };
+///
+/// try_visit_mut(
+///     &mut Clamp,
+///     &mut player,
+///     <Player as DescribeType>::type_descriptor().get_type(),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(player.health, 1.0);
+/// assert_eq!(player.items[0].durability, 0.0);
+/// ```
+#[allow(unused_variables)]
+pub trait TryVisitMut {
+    type Error;
+
+    visit_scalar_mut_fn!(try_visit_usize, usize);
+    visit_scalar_mut_fn!(try_visit_u8, u8);
+    visit_scalar_mut_fn!(try_visit_u16, u16);
+    visit_scalar_mut_fn!(try_visit_u32, u32);
+    visit_scalar_mut_fn!(try_visit_u64, u64);
+    visit_scalar_mut_fn!(try_visit_u128, u128);
+    visit_scalar_mut_fn!(try_visit_i8, i8);
+    visit_scalar_mut_fn!(try_visit_i16, i16);
+    visit_scalar_mut_fn!(try_visit_i32, i32);
+    visit_scalar_mut_fn!(try_visit_i64, i64);
+    visit_scalar_mut_fn!(try_visit_i128, i128);
+    visit_scalar_mut_fn!(try_visit_bool, bool);
+    visit_scalar_mut_fn!(try_visit_char, char);
+    visit_scalar_mut_fn!(try_visit_f32, f32);
+    visit_scalar_mut_fn!(try_visit_f64, f64);
+    visit_scalar_mut_fn!(try_visit_string, String);
+
+    #[inline]
+    fn try_visit_opaque(
+        &mut self,
+        value: &mut dyn Reflect,
+        ty: OpaqueType<'_>,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    enter_leave_fns!(enter_struct, leave_struct, &mut dyn Struct, StructType<'_>);
+    enter_leave_fns!(
+        enter_tuple_struct,
+        leave_tuple_struct,
+        &mut dyn TupleStruct,
+        TupleStructType<'_>
+    );
+    enter_leave_fns!(enter_tuple, leave_tuple, &mut dyn Tuple, TupleType<'_>);
+    enter_leave_fns!(enter_enum, leave_enum, &mut dyn Enum, EnumType<'_>);
+    // the key path of variants ends with `::VariantName`
+    enter_leave_fns!(enter_variant, leave_variant, &mut dyn Enum, Variant<'_>);
+    enter_leave_fns!(enter_list, leave_list, &mut dyn List, ListType<'_>);
+    enter_leave_fns!(enter_array, leave_array, &mut dyn Array, ArrayType<'_>);
+    enter_leave_fns!(enter_map, leave_map, &mut dyn Map, MapType<'_>);
+    enter_leave_fns!(enter_set, leave_set, &mut dyn Set, SetType<'_>);
+
+    /// Called before visiting a field of a struct, tuple struct, tuple, or enum variant.
+    #[inline]
+    fn enter_field(
+        &mut self,
+        field: &NamedOrNumbered,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_field(
+        &mut self,
+        field: &NamedOrNumbered,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting an element of a list or array.
+    #[inline]
+    fn enter_list_element(
+        &mut self,
+        index: usize,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_list_element(&mut self, index: usize, key_path: &KeyPath) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting the value of a map entry.
+    #[inline]
+    fn enter_map_entry(
+        &mut self,
+        key: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_map_entry(
+        &mut self,
+        key: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called before visiting a copy of an element of a set.
+    #[inline]
+    fn enter_set_element(
+        &mut self,
+        element: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<Enter, Self::Error> {
+        Ok(Enter::Continue)
+    }
+
+    #[inline]
+    fn leave_set_element(
+        &mut self,
+        element: &dyn Reflect,
+        key_path: &KeyPath,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called when a visited copy couldn't be written back, such as when a visitor sets a
+    /// `NonZeroU8` to zero or changes a set element to a value of the wrong type.
+    ///
+    /// The value at `key_path` is left as it was before being visited. The key paths of the
+    /// mismatches in `error` are relative to the root of the visit.
+    ///
+    /// The default implementation ignores the error.
+    #[inline]
+    fn patch_failed(&mut self, error: PatchError, key_path: &KeyPath) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+pub fn try_visit_mut<V>(
+    visitor: &mut V,
+    value: &mut dyn Reflect,
+    ty: Type<'_>,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    try_visit_mut_at(visitor, value, ty, &mut KeyPath::default())
+}
+
+fn try_visit_mut_at<V>(
+    visitor: &mut V,
+    value: &mut dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    // types such as `Box<str>` and `glam::Quat` cannot be borrowed as the type they're described
+    // as so visit a copy and patch the changes back
+    if !matches!(ty, Type::Opaque(_)) && matches!(value.reflect_mut(), ReflectMut::Opaque(_)) {
+        let mut copy = value.to_value();
+        try_visit_mut_at(visitor, &mut copy, ty, key_path)?;
+        if let Err(err) = value.try_patch(&copy) {
+            visitor.patch_failed(err.prefixed(key_path), key_path)?;
+        }
+        return Ok(());
+    }
+
+    match ty {
+        Type::Scalar(_) => {
+            let scalar = value.as_scalar_mut().unwrap();
+            try_visit_scalar_mut(visitor, scalar, key_path)?;
+        }
+        Type::Struct(struct_ty) => {
+            let struct_ = value.as_struct_mut().unwrap();
+
+            if visitor.enter_struct(struct_, struct_ty, key_path)? == Enter::Continue {
+                for field_ty in struct_ty.field_types() {
+                    let field = struct_.field_mut(field_ty.name()).unwrap();
+                    let name = NamedOrNumbered::Named(field_ty.name().to_owned());
+                    try_visit_field_mut(visitor, name, field, field_ty.get_type(), key_path)?;
+                }
+            }
+
+            visitor.leave_struct(struct_, struct_ty, key_path)?;
+        }
+        Type::TupleStruct(tuple_struct_ty) => {
+            let tuple_struct = value.as_tuple_struct_mut().unwrap();
+
+            if visitor.enter_tuple_struct(tuple_struct, tuple_struct_ty, key_path)?
+                == Enter::Continue
+            {
+                for (idx, field_ty) in tuple_struct_ty.field_types().enumerate() {
+                    let field = tuple_struct.field_at_mut(idx).unwrap();
+                    let index = NamedOrNumbered::Numbered(idx);
+                    try_visit_field_mut(visitor, index, field, field_ty.get_type(), key_path)?;
+                }
+            }
+
+            visitor.leave_tuple_struct(tuple_struct, tuple_struct_ty, key_path)?;
+        }
+        Type::Tuple(tuple_ty) => {
+            let tuple = value.as_tuple_mut().unwrap();
+
+            if visitor.enter_tuple(tuple, tuple_ty, key_path)? == Enter::Continue {
+                for (idx, field_ty) in tuple_ty.field_types().enumerate() {
+                    let field = tuple.field_at_mut(idx).unwrap();
+                    let index = NamedOrNumbered::Numbered(idx);
+                    try_visit_field_mut(visitor, index, field, field_ty.get_type(), key_path)?;
+                }
+            }
+
+            visitor.leave_tuple(tuple, tuple_ty, key_path)?;
+        }
+        Type::Enum(enum_ty) => {
+            let enum_ = value.as_enum_mut().unwrap();
+
+            if visitor.enter_enum(enum_, enum_ty, key_path)? == Enter::Continue {
+                let variant_ty = enum_ty.variant(enum_.variant_name()).unwrap();
+                key_path.push(Key::variant(variant_ty.name()));
+
+                if visitor.enter_variant(enum_, variant_ty, key_path)? == Enter::Continue {
+                    for (idx, field_ty) in variant_ty.field_types().enumerate() {
+                        let (field, key) = match field_ty {
+                            VariantField::Named(named_field_ty) => (
+                                enum_.field_mut(named_field_ty.name()).unwrap(),
+                                NamedOrNumbered::Named(named_field_ty.name().to_owned()),
+                            ),
+                            VariantField::Unnamed(_) => (
+                                enum_.field_at_mut(idx).unwrap(),
+                                NamedOrNumbered::Numbered(idx),
+                            ),
+                        };
+                        try_visit_field_mut(visitor, key, field, field_ty.get_type(), key_path)?;
+                    }
+                }
+
+                visitor.leave_variant(enum_, variant_ty, key_path)?;
+                key_path.pop();
+            }
+
+            visitor.leave_enum(enum_, enum_ty, key_path)?;
+        }
+        Type::List(list_ty) => {
+            let list = value.as_list_mut().unwrap();
+
+            if visitor.enter_list(list, list_ty, key_path)? == Enter::Continue {
+                let element_ty = list_ty.element_type();
+                for (idx, element) in list.iter_mut().enumerate() {
+                    try_visit_element_mut(visitor, idx, element, element_ty, key_path)?;
+                }
+            }
+
+            visitor.leave_list(list, list_ty, key_path)?;
+        }
+        Type::Array(array_ty) => {
+            let array = value.as_array_mut().unwrap();
+
+            if visitor.enter_array(array, array_ty, key_path)? == Enter::Continue {
+                let element_ty = array_ty.element_type();
+                for (idx, element) in array.iter_mut().enumerate() {
+                    try_visit_element_mut(visitor, idx, element, element_ty, key_path)?;
+                }
+            }
+
+            visitor.leave_array(array, array_ty, key_path)?;
+        }
+        Type::Map(map_ty) => {
+            let map = value.as_map_mut().unwrap();
+
+            if visitor.enter_map(map, map_ty, key_path)? == Enter::Continue {
+                let value_ty = map_ty.value_type();
+                for (key, value) in map.iter_mut() {
+                    key_path.push(Key::Get(key.to_value()));
+                    if visitor.enter_map_entry(key, key_path)? == Enter::Continue {
+                        try_visit_mut_at(visitor, value, value_ty, key_path)?;
+                    }
+                    visitor.leave_map_entry(key, key_path)?;
+                    key_path.pop();
+                }
+            }
+
+            visitor.leave_map(map, map_ty, key_path)?;
+        }
+        Type::Set(set_ty) => {
+            let set = value.as_set_mut().unwrap();
+
+            if visitor.enter_set(set, set_ty, key_path)? == Enter::Continue {
+                let element_ty = set_ty.element_type();
+                // elements cannot be changed in place so visit copies and replace the elements
+                // that changed once all of them have been visited, so a changed element isn't
+                // mistaken for one that's yet to be visited
+                let elements = set.iter().map(Reflect::to_value).collect::<Vec<_>>();
+                let mut changed = Vec::new();
+                for element in elements {
+                    key_path.push(Key::Get(element.clone()));
+                    if visitor.enter_set_element(&element, key_path)? == Enter::Continue {
+                        let mut copy = element.clone();
+                        try_visit_mut_at(visitor, &mut copy, element_ty, key_path)?;
+                        if copy != element {
+                            changed.push((element.clone(), copy));
+                        }
+                    }
+                    visitor.leave_set_element(&element, key_path)?;
+                    key_path.pop();
+                }
+                replace_set_elements(visitor, set, changed, key_path)?;
+            }
+
+            visitor.leave_set(set, set_ty, key_path)?;
+        }
+        Type::Opaque(opaque_ty) => {
+            visitor.try_visit_opaque(value, opaque_ty, key_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn try_visit_field_mut<V>(
+    visitor: &mut V,
+    field: NamedOrNumbered,
+    value: &mut dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    key_path.push(Key::Field(field.clone()));
+    if visitor.enter_field(&field, key_path)? == Enter::Continue {
+        try_visit_mut_at(visitor, value, ty, key_path)?;
+    }
+    visitor.leave_field(&field, key_path)?;
+    key_path.pop();
+    Ok(())
+}
+
+fn try_visit_element_mut<V>(
+    visitor: &mut V,
+    index: usize,
+    value: &mut dyn Reflect,
+    ty: Type<'_>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    key_path.push(Key::get(index));
+    if visitor.enter_list_element(index, key_path)? == Enter::Continue {
+        try_visit_mut_at(visitor, value, ty, key_path)?;
+    }
+    visitor.leave_list_element(index, key_path)?;
+    key_path.pop();
+    Ok(())
+}
+
+/// Replace the `(old, new)` pairs of elements in `set`. Old elements are put back if the new one
+/// has the wrong type.
+fn replace_set_elements<V>(
+    visitor: &mut V,
+    set: &mut dyn Set,
+    changed: Vec<(Value, Value)>,
+    key_path: &mut KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    let removed = changed
+        .into_iter()
+        .filter_map(|(old, new)| Some((set.remove(&old)?, new)))
+        .collect::<Vec<_>>();
+
+    for (old, new) in removed {
+        if !set.insert(&new) && !set.contains(&new) {
+            set.insert(&*old);
+            key_path.push(Key::Get(old.to_value()));
+            let err = PatchError::type_mismatch(&*old, &new).prefixed(key_path);
+            visitor.patch_failed(err, key_path)?;
+            key_path.pop();
+        }
+    }
+
+    Ok(())
+}
+
+fn try_visit_scalar_mut<V>(
+    visitor: &mut V,
+    scalar: ScalarMut<'_>,
+    key_path: &KeyPath,
+) -> Result<(), V::Error>
+where
+    V: TryVisitMut + ?Sized,
+{
+    match scalar {
+        ScalarMut::usize(inner) => visitor.try_visit_usize(inner, key_path),
+        ScalarMut::u8(inner) => visitor.try_visit_u8(inner, key_path),
+        ScalarMut::u16(inner) => visitor.try_visit_u16(inner, key_path),
+        ScalarMut::u32(inner) => visitor.try_visit_u32(inner, key_path),
+        ScalarMut::u64(inner) => visitor.try_visit_u64(inner, key_path),
+        ScalarMut::u128(inner) => visitor.try_visit_u128(inner, key_path),
+        ScalarMut::i8(inner) => visitor.try_visit_i8(inner, key_path),
+        ScalarMut::i16(inner) => visitor.try_visit_i16(inner, key_path),
+        ScalarMut::i32(inner) => visitor.try_visit_i32(inner, key_path),
+        ScalarMut::i64(inner) => visitor.try_visit_i64(inner, key_path),
+        ScalarMut::i128(inner) => visitor.try_visit_i128(inner, key_path),
+        ScalarMut::bool(inner) => visitor.try_visit_bool(inner, key_path),
+        ScalarMut::char(inner) => visitor.try_visit_char(inner, key_path),
+        ScalarMut::f32(inner) => visitor.try_visit_f32(inner, key_path),
+        ScalarMut::f64(inner) => visitor.try_visit_f64(inner, key_path),
+        ScalarMut::String(inner) => visitor.try_visit_string(inner, key_path),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,7 +973,7 @@ mod tests {
     use alloc::collections::BTreeMap;
     use core::convert::Infallible;
 
-    #[derive(Debug, Clone, Reflect)]
+    #[derive(Debug, Clone, Reflect, PartialEq)]
     #[reflect(crate_name(crate))]
     struct Foo {
         a: String,
@@ -158,7 +981,7 @@ mod tests {
         c: Vec<Bar>,
     }
 
-    #[derive(Debug, Clone, Reflect)]
+    #[derive(Debug, Clone, Reflect, PartialEq)]
     #[reflect(crate_name(crate))]
     enum Bar {
         A(BTreeMap<i32, i32>),
@@ -226,4 +1049,476 @@ mod tests {
         assert_eq!(visitor.string_count, 0);
         assert_eq!(visitor.i32_count, 3);
     }
+
+    #[derive(Default, Debug)]
+    struct Trace(Vec<String>);
+
+    impl TryVisit for Trace {
+        type Error = Infallible;
+
+        fn try_visit_i32(&mut self, value: i32) -> Result<(), Self::Error> {
+            self.0.push(format!("{value}"));
+            Ok(())
+        }
+
+        fn enter_struct(
+            &mut self,
+            _: &dyn Struct,
+            _: StructType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.0.push(format!("enter struct `{key_path}`"));
+            Ok(Enter::Continue)
+        }
+
+        fn leave_struct(
+            &mut self,
+            _: &dyn Struct,
+            _: StructType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            self.0.push(format!("leave struct `{key_path}`"));
+            Ok(())
+        }
+
+        fn enter_field(
+            &mut self,
+            field: &NamedOrNumbered,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.0.push(format!("enter field `{key_path}`"));
+            if field == &NamedOrNumbered::Named("a".to_owned()) {
+                Ok(Enter::Skip)
+            } else {
+                Ok(Enter::Continue)
+            }
+        }
+
+        fn enter_variant(
+            &mut self,
+            _: &dyn Enum,
+            _: Variant<'_>,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.0.push(format!("enter variant `{key_path}`"));
+            Ok(Enter::Continue)
+        }
+
+        fn enter_list_element(
+            &mut self,
+            _: usize,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.0.push(format!("enter element `{key_path}`"));
+            Ok(Enter::Continue)
+        }
+
+        fn enter_map_entry(
+            &mut self,
+            key: &dyn Reflect,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.0.push(format!("enter entry `{key_path}`"));
+            if key.downcast_ref::<i32>() == Some(&2) {
+                Ok(Enter::Skip)
+            } else {
+                Ok(Enter::Continue)
+            }
+        }
+    }
+
+    #[test]
+    fn hooks() {
+        let foo = Foo {
+            a: "a".to_owned(),
+            b: 1,
+            c: Vec::from([Bar::A(BTreeMap::from([(1, 10), (2, 20)]))]),
+        };
+
+        let mut visitor = Trace::default();
+        try_visit(
+            &mut visitor,
+            &foo,
+            <Foo as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            visitor.0,
+            [
+                "enter struct ``",
+                "enter field `.a`",
+                "enter field `.b`",
+                "1",
+                "enter field `.c`",
+                "enter element `.c[0_usize]`",
+                "enter variant `.c[0_usize]::A`",
+                "enter field `.c[0_usize]::A.0`",
+                "enter entry `.c[0_usize]::A.0[1]`",
+                "1",
+                "10",
+                "enter entry `.c[0_usize]::A.0[2]`",
+                "leave struct ``",
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_struct() {
+        struct SkipAll(usize);
+
+        impl TryVisit for SkipAll {
+            type Error = Infallible;
+
+            fn try_visit_i32(&mut self, _: i32) -> Result<(), Self::Error> {
+                self.0 += 1;
+                Ok(())
+            }
+
+            fn enter_struct(
+                &mut self,
+                _: &dyn Struct,
+                _: StructType<'_>,
+                _: &KeyPath,
+            ) -> Result<Enter, Self::Error> {
+                Ok(Enter::Skip)
+            }
+        }
+
+        let foo = Foo {
+            a: "a".to_owned(),
+            b: 1,
+            c: Vec::new(),
+        };
+
+        let mut visitor = SkipAll(0);
+        try_visit(
+            &mut visitor,
+            &foo,
+            <Foo as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap();
+        assert_eq!(visitor.0, 0);
+    }
+
+    #[derive(Default, Debug)]
+    struct Sanitize {
+        entered: Vec<String>,
+        left: Vec<String>,
+    }
+
+    impl TryVisitMut for Sanitize {
+        type Error = String;
+
+        fn try_visit_i32(&mut self, value: &mut i32, _: &KeyPath) -> Result<(), Self::Error> {
+            *value = (*value).max(0);
+            Ok(())
+        }
+
+        fn try_visit_u8(&mut self, value: &mut u8, _: &KeyPath) -> Result<(), Self::Error> {
+            *value += 1;
+            Ok(())
+        }
+
+        fn try_visit_string(
+            &mut self,
+            value: &mut String,
+            key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            if value == "error" {
+                return Err(key_path.to_string());
+            }
+            *value = value.trim().to_owned();
+            Ok(())
+        }
+
+        fn enter_struct(
+            &mut self,
+            _: &mut dyn Struct,
+            _: StructType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.entered.push(format!("{key_path} struct"));
+            Ok(Enter::Continue)
+        }
+
+        fn leave_struct(
+            &mut self,
+            _: &mut dyn Struct,
+            _: StructType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            self.left.push(format!("{key_path} struct"));
+            Ok(())
+        }
+
+        fn enter_variant(
+            &mut self,
+            _: &mut dyn Enum,
+            ty: Variant<'_>,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.entered.push(format!("{key_path} {}", ty.name()));
+            Ok(Enter::Continue)
+        }
+
+        fn enter_list(
+            &mut self,
+            value: &mut dyn List,
+            _: ListType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<Enter, Self::Error> {
+            self.entered.push(format!("{key_path} list"));
+            // changes made when entering are visited
+            value.push(&Bar::A(BTreeMap::from([(-1, -1)])));
+            Ok(Enter::Continue)
+        }
+
+        fn leave_map(
+            &mut self,
+            value: &mut dyn Map,
+            _: MapType<'_>,
+            key_path: &KeyPath,
+        ) -> Result<(), Self::Error> {
+            self.left.push(format!("{key_path} map {}", value.len()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn visit_mut() {
+        #[derive(Debug, Clone, Reflect, PartialEq)]
+        #[reflect(crate_name(crate))]
+        struct Outer {
+            foo: Foo,
+            n: u8,
+        }
+
+        let mut value = Outer {
+            foo: Foo {
+                a: "  a ".to_owned(),
+                b: -1,
+                c: Vec::from([Bar::A(BTreeMap::from([(1, -2)]))]),
+            },
+            n: 1,
+        };
+
+        let mut visitor = Sanitize::default();
+        try_visit_mut(
+            &mut visitor,
+            &mut value,
+            <Outer as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap();
+
+        assert_eq!(value.foo.a, "a");
+        assert_eq!(value.foo.b, 0);
+        assert_eq!(
+            value.foo.c,
+            Vec::from([
+                Bar::A(BTreeMap::from([(1, 0)])),
+                Bar::A(BTreeMap::from([(-1, 0)])),
+            ])
+        );
+        assert_eq!(value.n, 2);
+
+        assert_eq!(
+            visitor.entered,
+            [
+                " struct",
+                ".foo struct",
+                ".foo.c list",
+                ".foo.c[0_usize]::A A",
+                ".foo.c[1_usize]::A A",
+            ]
+        );
+        assert_eq!(
+            visitor.left,
+            [
+                ".foo.c[0_usize]::A.0 map 1",
+                ".foo.c[1_usize]::A.0 map 1",
+                ".foo struct",
+                " struct",
+            ]
+        );
+    }
+
+    #[test]
+    fn visit_mut_hooks() {
+        use alloc::collections::BTreeSet;
+
+        #[derive(Debug, Clone, Reflect, PartialEq)]
+        #[reflect(crate_name(crate))]
+        struct Numbers {
+            list: Vec<i32>,
+            map: BTreeMap<String, i32>,
+            set: BTreeSet<i32>,
+            skipped: i32,
+        }
+
+        #[derive(Default)]
+        struct Double(Vec<String>);
+
+        impl TryVisitMut for Double {
+            type Error = Infallible;
+
+            fn try_visit_i32(&mut self, value: &mut i32, _: &KeyPath) -> Result<(), Self::Error> {
+                *value *= 2;
+                Ok(())
+            }
+
+            fn enter_field(
+                &mut self,
+                field: &NamedOrNumbered,
+                key_path: &KeyPath,
+            ) -> Result<Enter, Self::Error> {
+                self.0.push(format!("field {key_path}"));
+                if field == &NamedOrNumbered::Named("skipped".to_owned()) {
+                    Ok(Enter::Skip)
+                } else {
+                    Ok(Enter::Continue)
+                }
+            }
+
+            fn enter_list_element(
+                &mut self,
+                _: usize,
+                key_path: &KeyPath,
+            ) -> Result<Enter, Self::Error> {
+                self.0.push(format!("element {key_path}"));
+                Ok(Enter::Continue)
+            }
+
+            fn enter_map_entry(
+                &mut self,
+                _: &dyn Reflect,
+                key_path: &KeyPath,
+            ) -> Result<Enter, Self::Error> {
+                self.0.push(format!("entry {key_path}"));
+                Ok(Enter::Continue)
+            }
+
+            fn leave_set_element(
+                &mut self,
+                _: &dyn Reflect,
+                key_path: &KeyPath,
+            ) -> Result<(), Self::Error> {
+                self.0.push(format!("set element {key_path}"));
+                Ok(())
+            }
+        }
+
+        let mut value = Numbers {
+            list: Vec::from([1]),
+            map: BTreeMap::from([("a".to_owned(), 2)]),
+            set: BTreeSet::from([1, 2, 3]),
+            skipped: 1,
+        };
+
+        let mut visitor = Double::default();
+        try_visit_mut(
+            &mut visitor,
+            &mut value,
+            <Numbers as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            Numbers {
+                list: Vec::from([2]),
+                map: BTreeMap::from([("a".to_owned(), 4)]),
+                set: BTreeSet::from([2, 4, 6]),
+                skipped: 1,
+            }
+        );
+        assert_eq!(
+            visitor.0,
+            [
+                "field .list",
+                "element .list[0_usize]",
+                "field .map",
+                r#"entry .map["a"]"#,
+                "field .set",
+                "set element .set[1]",
+                "set element .set[2]",
+                "set element .set[3]",
+                "field .skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn visit_mut_patch_failed() {
+        use core::num::NonZeroU8;
+
+        #[derive(Debug, Clone, Reflect, PartialEq)]
+        #[reflect(crate_name(crate))]
+        struct Counter {
+            count: NonZeroU8,
+            total: u8,
+        }
+
+        #[derive(Default)]
+        struct Zero(Vec<String>);
+
+        impl TryVisitMut for Zero {
+            type Error = Infallible;
+
+            fn try_visit_u8(&mut self, value: &mut u8, _: &KeyPath) -> Result<(), Self::Error> {
+                *value = 0;
+                Ok(())
+            }
+
+            fn patch_failed(
+                &mut self,
+                error: PatchError,
+                key_path: &KeyPath,
+            ) -> Result<(), Self::Error> {
+                self.0.push(format!("{key_path}: {error}"));
+                Ok(())
+            }
+        }
+
+        let mut value = Counter {
+            count: NonZeroU8::new(1).unwrap(),
+            total: 1,
+        };
+
+        let mut visitor = Zero::default();
+        try_visit_mut(
+            &mut visitor,
+            &mut value,
+            <Counter as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap();
+
+        assert_eq!(value.count.get(), 1);
+        assert_eq!(value.total, 0);
+        assert_eq!(
+            visitor.0,
+            [format!(
+                ".count: expected `{}`, found `u8` at `.count`",
+                core::any::type_name::<NonZeroU8>()
+            )]
+        );
+    }
+
+    #[test]
+    fn visit_mut_error() {
+        let mut value = Foo {
+            a: "a".to_owned(),
+            b: 1,
+            c: Vec::new(),
+        };
+        value.a = "error".to_owned();
+
+        let err = try_visit_mut(
+            &mut Sanitize::default(),
+            &mut value,
+            <Foo as DescribeType>::type_descriptor().get_type(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ".a");
+    }
 }
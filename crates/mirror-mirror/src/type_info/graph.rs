@@ -16,7 +16,7 @@ use crate::STATIC_RANDOM_STATE;
 #[derive(Clone, Copy, Hash, PartialEq, PartialOrd, Ord, Eq, Debug)]
 #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct NodeId(u64);
+pub struct NodeId(pub(super) u64);
 
 impl NodeId {
     fn new<T>() -> Self
@@ -98,6 +98,7 @@ pub enum TypeNode {
     List(ListNode),
     Array(ArrayNode),
     Map(MapNode),
+    Set(SetNode),
     Scalar(ScalarNode),
     Opaque(OpaqueNode),
 }
@@ -119,6 +120,7 @@ impl_from! { Enum(EnumNode) }
 impl_from! { List(ListNode) }
 impl_from! { Array(ArrayNode) }
 impl_from! { Map(MapNode) }
+impl_from! { Set(SetNode) }
 impl_from! { Scalar(ScalarNode) }
 impl_from! { Opaque(OpaqueNode) }
 
@@ -462,6 +464,27 @@ impl MapNode {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetNode {
+    pub(super) type_name: String,
+    pub(super) element_type_id: NodeId,
+}
+
+impl SetNode {
+    pub(crate) fn new<S, T>(graph: &mut TypeGraph) -> Self
+    where
+        S: DescribeType,
+        T: DescribeType,
+    {
+        Self {
+            type_name: type_name::<S>().to_owned(),
+            element_type_id: T::build(graph),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
 #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
@@ -483,6 +506,17 @@ pub enum ScalarNode {
     f32,
     f64,
     String,
+    NonZeroUsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
 }
 
 macro_rules! scalar_typed {
@@ -504,6 +538,25 @@ scalar_typed! {
     bool char String
 }
 
+// `NonZero*` types are described as their own scalars since they have no default value and don't
+// allow zero. They're `Reflect`ed in `foreign_impls::via_scalar`.
+macro_rules! non_zero_scalar_typed {
+    ($($ty:ident)*) => {
+        $(
+            impl DescribeType for core::num::$ty {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    graph.get_or_build_node_with::<Self, _>(|_graph| ScalarNode::$ty)
+                }
+            }
+        )*
+    };
+}
+
+non_zero_scalar_typed! {
+    NonZeroUsize NonZeroU8 NonZeroU16 NonZeroU32 NonZeroU64 NonZeroU128
+    NonZeroI8 NonZeroI16 NonZeroI32 NonZeroI64 NonZeroI128
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -0,0 +1,820 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use super::*;
+use crate::enum_::VariantField as ValueVariantField;
+use crate::enum_::VariantKind;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl TypeDescriptor {
+    /// Export the type as a [JSON Schema] (draft 2020-12) describing the JSON produced by
+    /// [`ReflectSerializer`].
+    ///
+    /// Structs become objects, or `null` if they have no fields, enums become a `oneOf` of their variants tagged by name, and lists,
+    /// arrays, maps, and sets become arrays and objects. Integers are limited to the range of
+    /// their type. Fields of type `Option` aren't required, and other fields are.
+    ///
+    /// Structs, tuple structs, and enums are put in `$defs`, keyed by their [`NodeId`], and
+    /// referenced with `$ref`, which supports recursive types. Doc comments become `description`s
+    /// and `meta` entries are kept as an `x-meta` object.
+    ///
+    /// Opaque types can be any JSON value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::{DescribeType, Reflect};
+    ///
+    /// /// A point in space.
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Point {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let schema = <Point as DescribeType>::type_descriptor().to_json_schema();
+    /// assert!(schema.contains(r#""description": "A point in space.""#));
+    /// ```
+    ///
+    /// [JSON Schema]: https://json-schema.org
+    /// [`ReflectSerializer`]: crate::reflect_serde::ReflectSerializer
+    pub fn to_json_schema(&self) -> String {
+        let mut builder = SchemaBuilder::default();
+        let root = builder.schema(self.get_type());
+
+        let mut schema = vec![("$schema".to_owned(), Json::from(DRAFT))];
+        match root {
+            Json::Object(entries) => schema.extend(entries),
+            _ => unreachable!("schemas are objects"),
+        }
+        if !builder.defs.is_empty() {
+            let defs = builder
+                .defs
+                .into_iter()
+                .map(|(id, def)| (def_name(id), def))
+                .collect();
+            schema.push(("$defs".to_owned(), Json::Object(defs)));
+        }
+
+        let mut out = String::new();
+        Json::Object(schema).write(&mut out, 0).unwrap();
+        out
+    }
+}
+
+#[derive(Default)]
+struct SchemaBuilder {
+    defs: BTreeMap<NodeId, Json>,
+}
+
+impl SchemaBuilder {
+    fn schema(&mut self, ty: Type<'_>) -> Json {
+        match ty {
+            Type::Struct(struct_ty) => self.reference(struct_ty.node.id, |this| {
+                let mut schema = if struct_ty.fields_len() == 0 {
+                    // serialized like `()`
+                    object([("type", "null".into())])
+                } else {
+                    this.fields_schema(struct_ty.field_types())
+                };
+                annotate(
+                    &mut schema,
+                    Some(struct_ty.type_name()),
+                    struct_ty.docs(),
+                    &struct_ty.node.metadata,
+                );
+                schema
+            }),
+            Type::TupleStruct(tuple_struct_ty) => self.reference(tuple_struct_ty.node.id, |this| {
+                let mut schema = match tuple_struct_ty.fields_len() {
+                    // newtype structs are serialized as their field
+                    1 => {
+                        let field_ty = tuple_struct_ty.field_type_at(0).unwrap();
+                        let mut schema = this.schema(field_ty.get_type());
+                        annotate(&mut schema, None, field_ty.docs(), &field_ty.node.metadata);
+                        schema
+                    }
+                    _ => this.unnamed_fields_schema(tuple_struct_ty.field_types()),
+                };
+                annotate(
+                    &mut schema,
+                    Some(tuple_struct_ty.type_name()),
+                    tuple_struct_ty.docs(),
+                    &tuple_struct_ty.node.metadata,
+                );
+                schema
+            }),
+            Type::Enum(enum_ty) if enum_ty.is_option() => {
+                let some_ty = enum_ty
+                    .variant("Some")
+                    .and_then(|variant_ty| variant_ty.field_type_at(0))
+                    .unwrap()
+                    .get_type();
+                object([(
+                    "anyOf",
+                    Json::Array(vec![
+                        self.schema(some_ty),
+                        object([("type", "null".into())]),
+                    ]),
+                )])
+            }
+            Type::Enum(enum_ty) => self.reference(enum_ty.node.id, |this| {
+                let variants = enum_ty
+                    .variants()
+                    .map(|variant_ty| this.variant_schema(variant_ty))
+                    .collect();
+                let mut schema = object([("oneOf", Json::Array(variants))]);
+                annotate(
+                    &mut schema,
+                    Some(enum_ty.type_name()),
+                    enum_ty.docs(),
+                    &enum_ty.node.metadata,
+                );
+                schema
+            }),
+            Type::Tuple(tuple_ty) => {
+                if tuple_ty.fields_len() == 0 {
+                    object([("type", "null".into())])
+                } else {
+                    self.unnamed_fields_schema(tuple_ty.field_types())
+                }
+            }
+            Type::List(list_ty) => object([
+                ("type", "array".into()),
+                ("items", self.schema(list_ty.element_type())),
+            ]),
+            Type::Array(array_ty) => object([
+                ("type", "array".into()),
+                ("items", self.schema(array_ty.element_type())),
+                ("minItems", Json::Number(array_ty.len().to_string())),
+                ("maxItems", Json::Number(array_ty.len().to_string())),
+            ]),
+            Type::Map(map_ty) => object([
+                ("type", "object".into()),
+                ("additionalProperties", self.schema(map_ty.value_type())),
+            ]),
+            Type::Set(set_ty) => object([
+                ("type", "array".into()),
+                ("items", self.schema(set_ty.element_type())),
+                ("uniqueItems", Json::Bool(true)),
+            ]),
+            Type::Scalar(scalar_ty) => scalar_schema(scalar_ty),
+            Type::Opaque(opaque_ty) => {
+                let mut schema = object([]);
+                annotate(
+                    &mut schema,
+                    Some(opaque_ty.type_name()),
+                    &[],
+                    &opaque_ty.node.metadata,
+                );
+                schema
+            }
+        }
+    }
+
+    /// Put the schema built by `f` in `$defs`, if it isn't there already, and refer to it.
+    fn reference(&mut self, id: NodeId, f: impl FnOnce(&mut Self) -> Json) -> Json {
+        if !self.defs.contains_key(&id) {
+            // insert a placeholder first so recursive types refer to it instead of looping
+            self.defs.insert(id, Json::Null);
+            let def = f(self);
+            self.defs.insert(id, def);
+        }
+        object([("$ref", format!("#/$defs/{}", def_name(id)).into())])
+    }
+
+    fn variant_schema(&mut self, variant_ty: Variant<'_>) -> Json {
+        let name = variant_ty.name();
+        let (mut schema, metadata) = match variant_ty {
+            // unit variants are serialized as their name, the others as `{ "Name": fields }`
+            Variant::Unit(unit_ty) => (object([("const", name.into())]), &unit_ty.node.metadata),
+            Variant::Tuple(tuple_ty) => {
+                let fields = match tuple_ty.fields_len() {
+                    1 => {
+                        let field_ty = tuple_ty.field_type_at(0).unwrap();
+                        let mut schema = self.schema(field_ty.get_type());
+                        annotate(&mut schema, None, field_ty.docs(), &field_ty.node.metadata);
+                        schema
+                    }
+                    _ => self.unnamed_fields_schema(tuple_ty.field_types()),
+                };
+                (tagged(name, fields), &tuple_ty.node.metadata)
+            }
+            Variant::Struct(struct_ty) => {
+                let fields = self.fields_schema(struct_ty.field_types());
+                (tagged(name, fields), &struct_ty.node.metadata)
+            }
+        };
+        annotate(&mut schema, None, variant_ty.docs(), metadata);
+        schema
+    }
+
+    fn fields_schema<'a>(&mut self, field_tys: impl Iterator<Item = NamedField<'a>>) -> Json {
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        for field_ty in field_tys {
+            let mut schema = self.schema(field_ty.get_type());
+            annotate(&mut schema, None, field_ty.docs(), &field_ty.node.metadata);
+            properties.push((field_ty.name().to_owned(), schema));
+
+            // missing options are deserialized as `None`
+            let is_option =
+                matches!(field_ty.get_type(), Type::Enum(enum_ty) if enum_ty.is_option());
+            if !is_option {
+                required.push(Json::from(field_ty.name()));
+            }
+        }
+
+        object([
+            ("type", "object".into()),
+            ("properties", Json::Object(properties)),
+            ("required", Json::Array(required)),
+            ("additionalProperties", Json::Bool(false)),
+        ])
+    }
+
+    fn unnamed_fields_schema<'a>(
+        &mut self,
+        field_tys: impl Iterator<Item = UnnamedField<'a>>,
+    ) -> Json {
+        let items = field_tys
+            .map(|field_ty| {
+                let mut schema = self.schema(field_ty.get_type());
+                annotate(&mut schema, None, field_ty.docs(), &field_ty.node.metadata);
+                schema
+            })
+            .collect::<Vec<_>>();
+        let len = Json::Number(items.len().to_string());
+        object([
+            ("type", "array".into()),
+            ("prefixItems", Json::Array(items)),
+            ("items", Json::Bool(false)),
+            ("minItems", len.clone()),
+            ("maxItems", len),
+        ])
+    }
+}
+
+fn scalar_schema(scalar_ty: ScalarType) -> Json {
+    fn integer(min: impl ToString, max: impl ToString) -> Json {
+        object([
+            ("type", "integer".into()),
+            ("minimum", Json::Number(min.to_string())),
+            ("maximum", Json::Number(max.to_string())),
+        ])
+    }
+
+    match scalar_ty {
+        // `usize` is serialized as `u64`
+        ScalarType::usize | ScalarType::u64 => integer(u64::MIN, u64::MAX),
+        ScalarType::u8 => integer(u8::MIN, u8::MAX),
+        ScalarType::u16 => integer(u16::MIN, u16::MAX),
+        ScalarType::u32 => integer(u32::MIN, u32::MAX),
+        ScalarType::u128 => integer(u128::MIN, u128::MAX),
+        ScalarType::i8 => integer(i8::MIN, i8::MAX),
+        ScalarType::i16 => integer(i16::MIN, i16::MAX),
+        ScalarType::i32 => integer(i32::MIN, i32::MAX),
+        ScalarType::i64 => integer(i64::MIN, i64::MAX),
+        ScalarType::i128 => integer(i128::MIN, i128::MAX),
+        ScalarType::f32 | ScalarType::f64 => object([("type", "number".into())]),
+        ScalarType::bool => object([("type", "boolean".into())]),
+        ScalarType::char => object([
+            ("type", "string".into()),
+            ("minLength", Json::Number("1".to_owned())),
+            ("maxLength", Json::Number("1".to_owned())),
+        ]),
+        ScalarType::String => object([("type", "string".into())]),
+        ScalarType::NonZeroUsize | ScalarType::NonZeroU64 => integer(1, u64::MAX),
+        ScalarType::NonZeroU8 => integer(1, u8::MAX),
+        ScalarType::NonZeroU16 => integer(1, u16::MAX),
+        ScalarType::NonZeroU32 => integer(1, u32::MAX),
+        ScalarType::NonZeroU128 => integer(1, u128::MAX),
+        ScalarType::NonZeroI8 => non_zero(integer(i8::MIN, i8::MAX)),
+        ScalarType::NonZeroI16 => non_zero(integer(i16::MIN, i16::MAX)),
+        ScalarType::NonZeroI32 => non_zero(integer(i32::MIN, i32::MAX)),
+        ScalarType::NonZeroI64 => non_zero(integer(i64::MIN, i64::MAX)),
+        ScalarType::NonZeroI128 => non_zero(integer(i128::MIN, i128::MAX)),
+    }
+}
+
+/// Exclude zero from an integer schema that includes negative numbers.
+fn non_zero(schema: Json) -> Json {
+    let Json::Object(mut properties) = schema else {
+        return schema;
+    };
+    properties.push((
+        "not".to_owned(),
+        object([("const", Json::Number("0".to_owned()))]),
+    ));
+    Json::Object(properties)
+}
+
+fn tagged(name: &str, schema: Json) -> Json {
+    object([
+        ("type", "object".into()),
+        ("properties", Json::Object(vec![(name.to_owned(), schema)])),
+        ("required", Json::Array(vec![name.into()])),
+        ("additionalProperties", Json::Bool(false)),
+    ])
+}
+
+/// Add a title, description, and metadata to `schema`.
+fn annotate(
+    schema: &mut Json,
+    type_name: Option<&str>,
+    docs: &[String],
+    metadata: &BTreeMap<String, Value>,
+) {
+    let Json::Object(entries) = schema else {
+        unreachable!("schemas are objects")
+    };
+
+    let mut annotations = Vec::new();
+    if let Some(type_name) = type_name {
+        annotations.push(("title".to_owned(), short_type_name(type_name).into()));
+    }
+    if let Some(docs) = docs_to_string(docs) {
+        annotations.push(("description".to_owned(), docs.into()));
+    }
+    if !metadata.is_empty() {
+        let metadata = metadata
+            .iter()
+            .map(|(key, value)| (key.clone(), value_to_json(value)))
+            .collect();
+        annotations.push(("x-meta".to_owned(), Json::Object(metadata)));
+    }
+
+    // annotations of fields override those of their types, and go first so they're easy to see
+    entries.retain(|(key, _)| !annotations.iter().any(|(annotation, _)| annotation == key));
+    entries.splice(0..0, annotations);
+}
+
+/// Join doc comment lines, removing the space after `///`.
+pub(super) fn docs_to_string(docs: &[String]) -> Option<String> {
+    if docs.is_empty() {
+        return None;
+    }
+    let lines = docs
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+fn def_name(id: NodeId) -> String {
+    id.0.to_string()
+}
+
+/// Convert a value to JSON in the same shape as `ReflectSerializer`.
+fn value_to_json(value: &dyn Reflect) -> Json {
+    match value.reflect_ref() {
+        ReflectRef::Struct(struct_) => Json::Object(
+            struct_
+                .fields()
+                .map(|(name, value)| (name.to_owned(), value_to_json(value)))
+                .collect(),
+        ),
+        ReflectRef::TupleStruct(tuple_struct) => {
+            let mut fields = tuple_struct.fields().map(value_to_json).collect::<Vec<_>>();
+            if fields.len() == 1 {
+                fields.pop().unwrap()
+            } else {
+                Json::Array(fields)
+            }
+        }
+        ReflectRef::Tuple(tuple) if tuple.fields_len() == 0 => Json::Null,
+        ReflectRef::Tuple(tuple) => Json::Array(tuple.fields().map(value_to_json).collect()),
+        ReflectRef::Enum(enum_) => {
+            let name = enum_.variant_name();
+            let mut fields = enum_.fields().map(|field| match field {
+                ValueVariantField::Struct(name, value) => (name.to_owned(), value_to_json(value)),
+                ValueVariantField::Tuple(value) => (String::new(), value_to_json(value)),
+            });
+            let fields = match enum_.variant_kind() {
+                VariantKind::Unit if name == "None" => return Json::Null,
+                VariantKind::Unit => return Json::from(name),
+                VariantKind::Tuple if name == "Some" && enum_.fields_len() == 1 => {
+                    return fields.next().unwrap().1
+                }
+                VariantKind::Tuple if enum_.fields_len() == 1 => fields.next().unwrap().1,
+                VariantKind::Tuple => Json::Array(fields.map(|(_, value)| value).collect()),
+                VariantKind::Struct => Json::Object(fields.collect()),
+            };
+            Json::Object(vec![(name.to_owned(), fields)])
+        }
+        ReflectRef::List(list) => Json::Array(list.iter().map(value_to_json).collect()),
+        ReflectRef::Array(array) => Json::Array(array.iter().map(value_to_json).collect()),
+        ReflectRef::Map(map) => Json::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let key = match key.reflect_ref() {
+                        ReflectRef::Scalar(ScalarRef::String(key)) => key.as_str().to_owned(),
+                        _ => match value_to_json(key) {
+                            Json::String(key) | Json::Number(key) => key,
+                            key => {
+                                let mut out = String::new();
+                                key.write(&mut out, 0).unwrap();
+                                out
+                            }
+                        },
+                    };
+                    (key, value_to_json(value))
+                })
+                .collect(),
+        ),
+        ReflectRef::Set(set) => Json::Array(set.iter().map(value_to_json).collect()),
+        ReflectRef::Scalar(scalar) => match scalar {
+            ScalarRef::usize(n) => Json::Number(n.to_string()),
+            ScalarRef::u8(n) => Json::Number(n.to_string()),
+            ScalarRef::u16(n) => Json::Number(n.to_string()),
+            ScalarRef::u32(n) => Json::Number(n.to_string()),
+            ScalarRef::u64(n) => Json::Number(n.to_string()),
+            ScalarRef::u128(n) => Json::Number(n.to_string()),
+            ScalarRef::i8(n) => Json::Number(n.to_string()),
+            ScalarRef::i16(n) => Json::Number(n.to_string()),
+            ScalarRef::i32(n) => Json::Number(n.to_string()),
+            ScalarRef::i64(n) => Json::Number(n.to_string()),
+            ScalarRef::i128(n) => Json::Number(n.to_string()),
+            ScalarRef::f32(n) => float(n.into()),
+            ScalarRef::f64(n) => float(n),
+            ScalarRef::bool(b) => Json::Bool(b),
+            ScalarRef::char(c) => Json::String(c.to_string()),
+            ScalarRef::String(s) => Json::String(s.as_str().to_owned()),
+        },
+        ReflectRef::Opaque(_) => Json::Null,
+    }
+}
+
+fn float(n: f64) -> Json {
+    // JSON doesn't support infinity or NaN
+    if n.is_finite() {
+        Json::Number(format!("{n:?}"))
+    } else {
+        Json::Null
+    }
+}
+
+fn object<const N: usize>(entries: [(&str, Json); N]) -> Json {
+    Json::Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect(),
+    )
+}
+
+/// A JSON value that keeps the order of object keys.
+#[derive(Debug, Clone)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl Json {
+    /// Write `self` indented by two spaces per level.
+    fn write(&self, out: &mut String, indent: usize) -> fmt::Result {
+        fn newline(out: &mut String, indent: usize) {
+            out.push('\n');
+            for _ in 0..indent {
+                out.push_str("  ");
+            }
+        }
+
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => write!(out, "{b}")?,
+            Json::Number(n) => out.push_str(n),
+            Json::String(s) => write_string(out, s)?,
+            Json::Array(elements) if elements.is_empty() => out.push_str("[]"),
+            Json::Array(elements) => {
+                out.push('[');
+                for (index, element) in elements.iter().enumerate() {
+                    if index != 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    element.write(out, indent + 1)?;
+                }
+                newline(out, indent);
+                out.push(']');
+            }
+            Json::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Json::Object(entries) => {
+                out.push('{');
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index != 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    write_string(out, key)?;
+                    out.push_str(": ");
+                    value.write(out, indent + 1)?;
+                }
+                newline(out, indent);
+                out.push('}');
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_string(out: &mut String, s: &str) -> fmt::Result {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use serde_json::json;
+
+    use super::*;
+    use crate::DescribeType;
+
+    /// A saved game.
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), meta(version = 2))]
+    struct Save {
+        /// The player's name.
+        name: String,
+        level: u8,
+        position: [f32; 3],
+        inventory: Vec<Item>,
+        scores: BTreeMap<String, i64>,
+        best: Option<u32>,
+        id: Id,
+        unit: (),
+        marker: Marker,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        /// Deals damage.
+        Sword {
+            damage: u32,
+        },
+        Potion(u16),
+        Pair(bool, char),
+        Key,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Id(#[reflect(meta(hidden = true))] usize);
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Marker;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Tree {
+        children: Vec<Tree>,
+    }
+
+    fn schema<T: DescribeType>() -> serde_json::Value {
+        serde_json::from_str(&T::type_descriptor().to_json_schema()).unwrap()
+    }
+
+    /// Resolve a `$ref` against the `$defs` of the root schema.
+    fn resolve<'a>(
+        root: &'a serde_json::Value,
+        schema: &'a serde_json::Value,
+    ) -> &'a serde_json::Value {
+        let reference = schema["$ref"].as_str().unwrap();
+        root.pointer(reference.strip_prefix('#').unwrap()).unwrap()
+    }
+
+    #[test]
+    fn struct_() {
+        let root = schema::<Save>();
+        assert_eq!(root["$schema"], DRAFT);
+
+        let save = resolve(&root, &root);
+        assert_eq!(save["title"], "Save");
+        assert_eq!(save["description"], "A saved game.");
+        assert_eq!(save["x-meta"], json!({ "version": 2 }));
+        assert_eq!(save["type"], "object");
+        assert_eq!(save["additionalProperties"], false);
+        assert_eq!(
+            save["required"],
+            json!([
+                "name",
+                "level",
+                "position",
+                "inventory",
+                "scores",
+                "id",
+                "unit",
+                "marker"
+            ]),
+        );
+
+        let properties = &save["properties"];
+        assert_eq!(
+            properties["name"],
+            json!({ "description": "The player's name.", "type": "string" }),
+        );
+        assert_eq!(
+            properties["level"],
+            json!({ "type": "integer", "minimum": 0, "maximum": 255 }),
+        );
+        assert_eq!(
+            properties["position"],
+            json!({
+                "type": "array",
+                "items": { "type": "number" },
+                "minItems": 3,
+                "maxItems": 3,
+            }),
+        );
+        assert_eq!(
+            properties["scores"],
+            json!({
+                "type": "object",
+                "additionalProperties": {
+                    "type": "integer",
+                    "minimum": i64::MIN,
+                    "maximum": i64::MAX,
+                },
+            }),
+        );
+        assert_eq!(
+            properties["best"],
+            json!({
+                "anyOf": [
+                    { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+                    { "type": "null" },
+                ],
+            }),
+        );
+        assert_eq!(properties["unit"], json!({ "type": "null" }));
+        assert_eq!(
+            resolve(&root, &properties["marker"]),
+            &json!({ "title": "Marker", "type": "null" }),
+        );
+
+        let id = resolve(&root, &properties["id"]);
+        assert_eq!(
+            id,
+            &json!({
+                "title": "Id",
+                "x-meta": { "hidden": true },
+                "type": "integer",
+                "minimum": 0,
+                "maximum": u64::MAX,
+            }),
+        );
+    }
+
+    #[test]
+    fn enum_() {
+        let root = schema::<Save>();
+        let save = resolve(&root, &root);
+        let inventory = &save["properties"]["inventory"];
+        assert_eq!(inventory["type"], "array");
+
+        let item = resolve(&root, &inventory["items"]);
+        assert_eq!(item["title"], "Item");
+        assert_eq!(
+            item["oneOf"],
+            json!([
+                {
+                    "description": "Deals damage.",
+                    "type": "object",
+                    "properties": {
+                        "Sword": {
+                            "type": "object",
+                            "properties": {
+                                "damage": { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+                            },
+                            "required": ["damage"],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": ["Sword"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Potion": { "type": "integer", "minimum": 0, "maximum": u16::MAX },
+                    },
+                    "required": ["Potion"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Pair": {
+                            "type": "array",
+                            "prefixItems": [
+                                { "type": "boolean" },
+                                { "type": "string", "minLength": 1, "maxLength": 1 },
+                            ],
+                            "items": false,
+                            "minItems": 2,
+                            "maxItems": 2,
+                        },
+                    },
+                    "required": ["Pair"],
+                    "additionalProperties": false,
+                },
+                { "const": "Key" },
+            ]),
+        );
+    }
+
+    #[test]
+    fn recursive() {
+        let root = schema::<Tree>();
+        let tree = resolve(&root, &root);
+        assert_eq!(tree["title"], "Tree");
+        assert_eq!(
+            tree["properties"]["children"]["items"]["$ref"],
+            root["$ref"]
+        );
+        assert_eq!(root["$defs"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn scalar_backed_types() {
+        assert_eq!(schema::<core::time::Duration>(), schema::<u128>());
+        assert_eq!(
+            schema::<core::num::NonZeroU8>(),
+            json!({ "$schema": DRAFT, "type": "integer", "minimum": 1, "maximum": 255 })
+        );
+        assert_eq!(
+            schema::<core::num::NonZeroI8>(),
+            json!({
+                "$schema": DRAFT,
+                "type": "integer",
+                "minimum": -128,
+                "maximum": 127,
+                "not": { "const": 0 },
+            })
+        );
+    }
+
+    #[test]
+    fn no_defs_for_anonymous_types() {
+        let root = schema::<Vec<(u8, String)>>();
+        assert_eq!(
+            root,
+            json!({
+                "$schema": DRAFT,
+                "type": "array",
+                "items": {
+                    "type": "array",
+                    "prefixItems": [
+                        { "type": "integer", "minimum": 0, "maximum": 255 },
+                        { "type": "string" },
+                    ],
+                    "items": false,
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+            }),
+        );
+    }
+}
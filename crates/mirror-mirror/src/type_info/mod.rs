@@ -4,6 +4,7 @@ use core::iter::Peekable;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -25,6 +26,9 @@ use crate::Value;
 pub mod graph;
 pub mod pretty_print;
 
+mod json_schema;
+mod typescript;
+
 #[cfg(feature = "std")]
 mod simple_type_name;
 
@@ -153,6 +157,10 @@ impl TypeDescriptor {
         self.get_type().as_map()
     }
 
+    pub fn as_set(&self) -> Option<SetType<'_>> {
+        self.get_type().as_set()
+    }
+
     pub fn as_scalar(&self) -> Option<ScalarType> {
         self.get_type().as_scalar()
     }
@@ -187,6 +195,7 @@ pub enum Type<'a> {
     List(ListType<'a>),
     Array(ArrayType<'a>),
     Map(MapType<'a>),
+    Set(SetType<'a>),
     Scalar(ScalarType),
     Opaque(OpaqueType<'a>),
 }
@@ -243,6 +252,13 @@ impl<'a> Type<'a> {
                 };
                 Type::Map(node)
             }
+            TypeNode::Set(node) => {
+                let node = SetType {
+                    node: WithId::new(id, node),
+                    graph,
+                };
+                Type::Set(node)
+            }
             TypeNode::Scalar(scalar) => {
                 let node = match scalar {
                     ScalarNode::usize => ScalarType::usize,
@@ -261,6 +277,17 @@ impl<'a> Type<'a> {
                     ScalarNode::f32 => ScalarType::f32,
                     ScalarNode::f64 => ScalarType::f64,
                     ScalarNode::String => ScalarType::String,
+                    ScalarNode::NonZeroUsize => ScalarType::NonZeroUsize,
+                    ScalarNode::NonZeroU8 => ScalarType::NonZeroU8,
+                    ScalarNode::NonZeroU16 => ScalarType::NonZeroU16,
+                    ScalarNode::NonZeroU32 => ScalarType::NonZeroU32,
+                    ScalarNode::NonZeroU64 => ScalarType::NonZeroU64,
+                    ScalarNode::NonZeroU128 => ScalarType::NonZeroU128,
+                    ScalarNode::NonZeroI8 => ScalarType::NonZeroI8,
+                    ScalarNode::NonZeroI16 => ScalarType::NonZeroI16,
+                    ScalarNode::NonZeroI32 => ScalarType::NonZeroI32,
+                    ScalarNode::NonZeroI64 => ScalarType::NonZeroI64,
+                    ScalarNode::NonZeroI128 => ScalarType::NonZeroI128,
                 };
                 Type::Scalar(node)
             }
@@ -283,6 +310,7 @@ impl<'a> Type<'a> {
             Type::List(inner) => inner.type_name(),
             Type::Array(inner) => inner.type_name(),
             Type::Map(inner) => inner.type_name(),
+            Type::Set(inner) => inner.type_name(),
             Type::Scalar(inner) => inner.type_name(),
             Type::Opaque(inner) => inner.type_name(),
         }
@@ -297,6 +325,7 @@ impl<'a> Type<'a> {
             Type::List(inner) => inner.into_type_info_at_path(),
             Type::Array(inner) => inner.into_type_info_at_path(),
             Type::Map(inner) => inner.into_type_info_at_path(),
+            Type::Set(inner) => inner.into_type_info_at_path(),
             Type::Scalar(inner) => inner.into_type_info_at_path(),
             Type::Opaque(inner) => inner.into_type_info_at_path(),
         }
@@ -311,7 +340,8 @@ impl<'a> Type<'a> {
             Type::List(inner) => Some(inner.default_value()),
             Type::Array(inner) => inner.default_value(),
             Type::Map(inner) => Some(inner.default_value()),
-            Type::Scalar(inner) => Some(inner.default_value()),
+            Type::Set(inner) => Some(inner.default_value()),
+            Type::Scalar(inner) => inner.default_value(),
             Type::Opaque(inner) => inner.default_value(),
         }
     }
@@ -325,6 +355,7 @@ impl<'a> Type<'a> {
             Type::List(inner) => inner.has_default_value(),
             Type::Array(inner) => inner.has_default_value(),
             Type::Map(inner) => inner.has_default_value(),
+            Type::Set(inner) => inner.has_default_value(),
             Type::Scalar(inner) => inner.has_default_value(),
             Type::Opaque(inner) => inner.has_default_value(),
         }
@@ -339,6 +370,7 @@ impl<'a> Type<'a> {
             Type::List(inner) => Cow::Owned(inner.into_type_descriptor()),
             Type::Array(inner) => Cow::Owned(inner.into_type_descriptor()),
             Type::Map(inner) => Cow::Owned(inner.into_type_descriptor()),
+            Type::Set(inner) => Cow::Owned(inner.into_type_descriptor()),
             Type::Scalar(inner) => match inner {
                 ScalarType::usize => <usize as DescribeType>::type_descriptor(),
                 ScalarType::u8 => <u8 as DescribeType>::type_descriptor(),
@@ -356,6 +388,35 @@ impl<'a> Type<'a> {
                 ScalarType::f32 => <f32 as DescribeType>::type_descriptor(),
                 ScalarType::f64 => <f64 as DescribeType>::type_descriptor(),
                 ScalarType::String => <String as DescribeType>::type_descriptor(),
+                ScalarType::NonZeroUsize => {
+                    <core::num::NonZeroUsize as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroU8 => <core::num::NonZeroU8 as DescribeType>::type_descriptor(),
+                ScalarType::NonZeroU16 => {
+                    <core::num::NonZeroU16 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroU32 => {
+                    <core::num::NonZeroU32 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroU64 => {
+                    <core::num::NonZeroU64 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroU128 => {
+                    <core::num::NonZeroU128 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroI8 => <core::num::NonZeroI8 as DescribeType>::type_descriptor(),
+                ScalarType::NonZeroI16 => {
+                    <core::num::NonZeroI16 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroI32 => {
+                    <core::num::NonZeroI32 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroI64 => {
+                    <core::num::NonZeroI64 as DescribeType>::type_descriptor()
+                }
+                ScalarType::NonZeroI128 => {
+                    <core::num::NonZeroI128 as DescribeType>::type_descriptor()
+                }
             },
             Type::Opaque(inner) => Cow::Owned(inner.into_type_descriptor()),
         }
@@ -410,6 +471,13 @@ impl<'a> Type<'a> {
         }
     }
 
+    pub fn as_set(self) -> Option<SetType<'a>> {
+        match self {
+            Type::Set(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
     pub fn as_scalar(self) -> Option<ScalarType> {
         match self {
             Type::Scalar(inner) => Some(inner),
@@ -467,6 +535,12 @@ impl<'a> GetTypePath<'a> for MapType<'a> {
     }
 }
 
+impl<'a> GetTypePath<'a> for SetType<'a> {
+    fn type_at(self, key_path: &KeyPath) -> Option<TypeAtPath<'a>> {
+        self.into_type_info_at_path().type_at(key_path)
+    }
+}
+
 impl GetTypePath<'static> for ScalarType {
     fn type_at(self, key_path: &KeyPath) -> Option<TypeAtPath<'static>> {
         self.into_type_info_at_path().type_at(key_path)
@@ -479,17 +553,26 @@ impl<'a> GetTypePath<'a> for Variant<'a> {
     }
 }
 
+/// The name of a type without its path or generics, such as `Foo` for `my_crate::Foo<i32>`.
+///
+/// This is the name derived impls of serde traits use.
+pub(crate) fn short_type_name(type_name: &str) -> &str {
+    let path = type_name.split('<').next().unwrap_or(type_name);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
 mod private {
     use super::*;
 
     pub trait Sealed {}
 
     impl Sealed for TypeDescriptor {}
-    impl<'a> Sealed for &'a TypeDescriptor {}
+    impl Sealed for &TypeDescriptor {}
     impl<'a> Sealed for TupleType<'a> {}
     impl<'a> Sealed for ListType<'a> {}
     impl<'a> Sealed for ArrayType<'a> {}
     impl<'a> Sealed for MapType<'a> {}
+    impl<'a> Sealed for SetType<'a> {}
     impl Sealed for ScalarType {}
     impl Sealed for Type<'_> {}
     impl Sealed for StructType<'_> {}
@@ -527,9 +610,12 @@ impl<'a> GetMeta<'a> for Type<'a> {
             Type::TupleStruct(inner) => inner.meta(key),
             Type::Enum(inner) => inner.meta(key),
             Type::Opaque(inner) => inner.meta(key),
-            Type::Tuple(_) | Type::List(_) | Type::Array(_) | Type::Map(_) | Type::Scalar(_) => {
-                None
-            }
+            Type::Tuple(_)
+            | Type::List(_)
+            | Type::Array(_)
+            | Type::Map(_)
+            | Type::Set(_)
+            | Type::Scalar(_) => None,
         }
     }
 
@@ -542,6 +628,7 @@ impl<'a> GetMeta<'a> for Type<'a> {
             | Type::List(_)
             | Type::Array(_)
             | Type::Map(_)
+            | Type::Set(_)
             | Type::Scalar(_)
             | Type::Opaque(_) => &[],
         }
@@ -606,6 +693,17 @@ pub enum ScalarType {
     f32,
     f64,
     String,
+    NonZeroUsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
 }
 
 impl ScalarType {
@@ -627,15 +725,58 @@ impl ScalarType {
             ScalarType::f32 => type_name::<f32>(),
             ScalarType::f64 => type_name::<f64>(),
             ScalarType::String => type_name::<String>(),
+            ScalarType::NonZeroUsize => type_name::<core::num::NonZeroUsize>(),
+            ScalarType::NonZeroU8 => type_name::<core::num::NonZeroU8>(),
+            ScalarType::NonZeroU16 => type_name::<core::num::NonZeroU16>(),
+            ScalarType::NonZeroU32 => type_name::<core::num::NonZeroU32>(),
+            ScalarType::NonZeroU64 => type_name::<core::num::NonZeroU64>(),
+            ScalarType::NonZeroU128 => type_name::<core::num::NonZeroU128>(),
+            ScalarType::NonZeroI8 => type_name::<core::num::NonZeroI8>(),
+            ScalarType::NonZeroI16 => type_name::<core::num::NonZeroI16>(),
+            ScalarType::NonZeroI32 => type_name::<core::num::NonZeroI32>(),
+            ScalarType::NonZeroI64 => type_name::<core::num::NonZeroI64>(),
+            ScalarType::NonZeroI128 => type_name::<core::num::NonZeroI128>(),
+        }
+    }
+
+    /// The scalar a `NonZero*` type contains, such as `u8` for `NonZeroU8`, otherwise `self`.
+    ///
+    /// Values of `NonZero*` types are reflected as the scalar they contain.
+    pub fn underlying(self) -> ScalarType {
+        match self {
+            ScalarType::NonZeroUsize => ScalarType::usize,
+            ScalarType::NonZeroU8 => ScalarType::u8,
+            ScalarType::NonZeroU16 => ScalarType::u16,
+            ScalarType::NonZeroU32 => ScalarType::u32,
+            ScalarType::NonZeroU64 => ScalarType::u64,
+            ScalarType::NonZeroU128 => ScalarType::u128,
+            ScalarType::NonZeroI8 => ScalarType::i8,
+            ScalarType::NonZeroI16 => ScalarType::i16,
+            ScalarType::NonZeroI32 => ScalarType::i32,
+            ScalarType::NonZeroI64 => ScalarType::i64,
+            ScalarType::NonZeroI128 => ScalarType::i128,
+            other => other,
         }
     }
 
+    /// Whether this is one of the `NonZero*` types.
+    pub fn is_non_zero(self) -> bool {
+        self.underlying() != self
+    }
+
+    /// Whether `value` is a valid value of this type, assuming it's already of the `underlying`
+    /// type. That is, whether it isn't zero if this is a `NonZero*` type.
+    pub(crate) fn allows(self, value: &Value) -> bool {
+        !self.is_non_zero() || self.underlying().default_value().as_ref() != Some(value)
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'static> {
         TypeAtPath::Scalar(self)
     }
 
-    pub fn default_value(self) -> Value {
-        match self {
+    /// The default value of the scalar, or `None` for `NonZero*` types which don't have one.
+    pub fn default_value(self) -> Option<Value> {
+        let value = match self {
             ScalarType::usize => usize::default().to_value(),
             ScalarType::u8 => u8::default().to_value(),
             ScalarType::u16 => u16::default().to_value(),
@@ -652,11 +793,23 @@ impl ScalarType {
             ScalarType::f32 => f32::default().to_value(),
             ScalarType::f64 => f64::default().to_value(),
             ScalarType::String => String::default().to_value(),
-        }
+            ScalarType::NonZeroUsize
+            | ScalarType::NonZeroU8
+            | ScalarType::NonZeroU16
+            | ScalarType::NonZeroU32
+            | ScalarType::NonZeroU64
+            | ScalarType::NonZeroU128
+            | ScalarType::NonZeroI8
+            | ScalarType::NonZeroI16
+            | ScalarType::NonZeroI32
+            | ScalarType::NonZeroI64
+            | ScalarType::NonZeroI128 => return None,
+        };
+        Some(value)
     }
 
     pub fn has_default_value(&self) -> bool {
-        true
+        !self.is_non_zero()
     }
 }
 
@@ -872,6 +1025,11 @@ impl<'a> EnumType<'a> {
         self.variants().find(|variant| variant.name() == name)
     }
 
+    /// Whether this is `Option<T>`, which serde and friends treat specially.
+    pub(crate) fn is_option(self) -> bool {
+        self.type_name().starts_with("core::option::Option<")
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         TypeAtPath::Enum(self)
     }
@@ -1347,6 +1505,41 @@ impl<'a> MapType<'a> {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SetType<'a> {
+    node: WithId<&'a SetNode>,
+    graph: &'a TypeGraph,
+}
+
+impl<'a> SetType<'a> {
+    pub fn type_name(self) -> &'a str {
+        &self.node.type_name
+    }
+
+    pub fn element_type(self) -> Type<'a> {
+        Type::new(self.node.element_type_id, self.graph)
+    }
+
+    fn into_type_info_at_path(self) -> TypeAtPath<'a> {
+        TypeAtPath::Set(self)
+    }
+
+    pub fn into_type_descriptor(self) -> TypeDescriptor {
+        TypeDescriptor {
+            root: self.node.id,
+            graph: self.graph.clone(),
+        }
+    }
+
+    pub fn default_value(self) -> Value {
+        BTreeSet::<()>::new().to_value()
+    }
+
+    pub fn has_default_value(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct OpaqueType<'a> {
     node: WithId<&'a OpaqueNode>,
@@ -1389,6 +1582,7 @@ pub enum TypeAtPath<'a> {
     List(ListType<'a>),
     Array(ArrayType<'a>),
     Map(MapType<'a>),
+    Set(SetType<'a>),
     Scalar(ScalarType),
     Opaque(OpaqueType<'a>),
 }
@@ -1405,6 +1599,7 @@ impl<'a> GetMeta<'a> for TypeAtPath<'a> {
             | TypeAtPath::List(_)
             | TypeAtPath::Array(_)
             | TypeAtPath::Map(_)
+            | TypeAtPath::Set(_)
             | TypeAtPath::Scalar(_) => None,
         }
     }
@@ -1419,6 +1614,7 @@ impl<'a> GetMeta<'a> for TypeAtPath<'a> {
             | TypeAtPath::List(_)
             | TypeAtPath::Array(_)
             | TypeAtPath::Map(_)
+            | TypeAtPath::Set(_)
             | TypeAtPath::Scalar(_)
             | TypeAtPath::Opaque(_) => &[],
         }
@@ -1436,7 +1632,8 @@ impl<'a> TypeAtPath<'a> {
             TypeAtPath::List(inner) => Some(inner.default_value()),
             TypeAtPath::Array(inner) => inner.default_value(),
             TypeAtPath::Map(inner) => Some(inner.default_value()),
-            TypeAtPath::Scalar(inner) => Some(inner.default_value()),
+            TypeAtPath::Set(inner) => Some(inner.default_value()),
+            TypeAtPath::Scalar(inner) => inner.default_value(),
             TypeAtPath::Opaque(inner) => inner.default_value(),
         }
     }
@@ -1451,6 +1648,7 @@ impl<'a> TypeAtPath<'a> {
             TypeAtPath::List(inner) => inner.has_default_value(),
             TypeAtPath::Array(inner) => inner.has_default_value(),
             TypeAtPath::Map(inner) => inner.has_default_value(),
+            TypeAtPath::Set(inner) => inner.has_default_value(),
             TypeAtPath::Scalar(inner) => inner.has_default_value(),
             TypeAtPath::Opaque(inner) => inner.has_default_value(),
         }
@@ -1466,6 +1664,7 @@ impl<'a> TypeAtPath<'a> {
             TypeAtPath::List(inner) => inner.type_name(),
             TypeAtPath::Array(inner) => inner.type_name(),
             TypeAtPath::Map(inner) => inner.type_name(),
+            TypeAtPath::Set(inner) => inner.type_name(),
             TypeAtPath::Scalar(inner) => inner.type_name(),
             TypeAtPath::Opaque(inner) => inner.type_name(),
         }
@@ -1527,6 +1726,13 @@ impl<'a> TypeAtPath<'a> {
         }
     }
 
+    pub fn as_set(self) -> Option<SetType<'a>> {
+        match self {
+            Self::Set(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
     pub fn as_scalar(self) -> Option<ScalarType> {
         match self {
             Self::Scalar(inner) => Some(inner),
@@ -1568,6 +1774,7 @@ impl<'a> GetTypePath<'a> for TypeAtPath<'a> {
                     | TypeAtPath::List(_)
                     | TypeAtPath::Array(_)
                     | TypeAtPath::Map(_)
+                    | TypeAtPath::Set(_)
                     | TypeAtPath::Scalar(_)
                     | TypeAtPath::Opaque(_) => return None,
                 },
@@ -1590,12 +1797,14 @@ impl<'a> GetTypePath<'a> for TypeAtPath<'a> {
                     | TypeAtPath::List(_)
                     | TypeAtPath::Array(_)
                     | TypeAtPath::Map(_)
+                    | TypeAtPath::Set(_)
                     | TypeAtPath::Scalar(_)
                     | TypeAtPath::Opaque(_) => return None,
                 },
                 // ["foo"] or [0]
                 Key::Get(key) => match type_info {
                     TypeAtPath::Map(map) => map.value_type().into_type_info_at_path(),
+                    TypeAtPath::Set(set) => set.element_type().into_type_info_at_path(),
                     TypeAtPath::List(list) => {
                         if value_to_usize(key).is_some() {
                             list.element_type().into_type_info_at_path()
@@ -1637,6 +1846,7 @@ impl<'a> GetTypePath<'a> for TypeAtPath<'a> {
                     | TypeAtPath::List(_)
                     | TypeAtPath::Array(_)
                     | TypeAtPath::Map(_)
+                    | TypeAtPath::Set(_)
                     | TypeAtPath::Scalar(_)
                     | TypeAtPath::Opaque(_) => return None,
                 },
@@ -43,6 +43,7 @@ impl<'a> PrettyPrintRoot for Type<'a> {
             Type::List(inner) => inner.pretty_root_fmt(f),
             Type::Array(inner) => inner.pretty_root_fmt(f),
             Type::Map(inner) => inner.pretty_root_fmt(f),
+            Type::Set(inner) => inner.pretty_root_fmt(f),
             Type::Scalar(inner) => inner.pretty_root_fmt(f),
             Type::Opaque(inner) => inner.pretty_root_fmt(f),
         }
@@ -203,6 +204,15 @@ impl<'a> PrettyPrintRoot for MapType<'a> {
     }
 }
 
+impl<'a> PrettyPrintRoot for SetType<'a> {
+    fn pretty_root_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('{')?;
+        simple_type_name_fmt(self.element_type().type_name(), f)?;
+        f.write_char('}')?;
+        Ok(())
+    }
+}
+
 impl PrettyPrintRoot for ScalarType {
     fn pretty_root_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -222,6 +232,17 @@ impl PrettyPrintRoot for ScalarType {
             ScalarType::f32 => f.write_str("f32")?,
             ScalarType::f64 => f.write_str("f64")?,
             ScalarType::String => f.write_str("String")?,
+            ScalarType::NonZeroUsize => f.write_str("NonZeroUsize")?,
+            ScalarType::NonZeroU8 => f.write_str("NonZeroU8")?,
+            ScalarType::NonZeroU16 => f.write_str("NonZeroU16")?,
+            ScalarType::NonZeroU32 => f.write_str("NonZeroU32")?,
+            ScalarType::NonZeroU64 => f.write_str("NonZeroU64")?,
+            ScalarType::NonZeroU128 => f.write_str("NonZeroU128")?,
+            ScalarType::NonZeroI8 => f.write_str("NonZeroI8")?,
+            ScalarType::NonZeroI16 => f.write_str("NonZeroI16")?,
+            ScalarType::NonZeroI32 => f.write_str("NonZeroI32")?,
+            ScalarType::NonZeroI64 => f.write_str("NonZeroI64")?,
+            ScalarType::NonZeroI128 => f.write_str("NonZeroI128")?,
         }
         Ok(())
     }
@@ -236,8 +257,6 @@ impl<'a> PrettyPrintRoot for OpaqueType<'a> {
 
 #[cfg(test)]
 mod tests {
-    use core::time::Duration;
-
     use super::*;
     use crate::{DescribeType, Reflect};
 
@@ -385,6 +404,14 @@ mod tests {
         assert_eq!(println_and_format!("{pp}"), r#"[String: i32]"#);
     }
 
+    #[test]
+    fn set() {
+        let type_descriptor = <BTreeSet<String> as DescribeType>::type_descriptor();
+        let pp = type_descriptor.pretty_print_root();
+
+        assert_eq!(println_and_format!("{pp}"), r#"{String}"#);
+    }
+
     #[test]
     fn scalar() {
         let type_descriptor = <String as DescribeType>::type_descriptor();
@@ -395,9 +422,9 @@ mod tests {
 
     #[test]
     fn opaque() {
-        let type_descriptor = <Duration as DescribeType>::type_descriptor();
+        let type_descriptor = <crate::Value as DescribeType>::type_descriptor();
         let pp = type_descriptor.pretty_print_root();
 
-        assert_eq!(println_and_format!("{pp}"), r#"Duration"#);
+        assert_eq!(println_and_format!("{pp}"), r#"Value"#);
     }
 }
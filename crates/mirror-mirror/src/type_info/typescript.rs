@@ -0,0 +1,476 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::json_schema::docs_to_string;
+use super::*;
+
+const INDENT: &str = "  ";
+
+impl TypeDescriptor {
+    /// Generate TypeScript declarations (as in a `.d.ts` file) for the JSON produced by
+    /// [`ReflectSerializer`].
+    ///
+    /// Every struct, tuple struct, and enum reachable from the type gets an exported declaration,
+    /// named after the type without its path or generics. If two types end up with the same name
+    /// the later one gets a numeric suffix, such as `Foo2`.
+    ///
+    /// - Structs become interfaces, or type aliases of `null` if they have no fields.
+    /// - Tuple structs become type aliases, of their field if there is only one and of a tuple
+    ///   otherwise.
+    /// - Enums become unions of their variants, tagged by name. Unit variants are string literals.
+    /// - `Option<T>` becomes `T | null`, lists, arrays, and sets become arrays, and maps become
+    ///   `Record<string, V>`.
+    /// - All numbers become `number`, and opaque types become `unknown`.
+    ///
+    /// Doc comments on types, fields, and variants are kept as JSDoc comments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::{DescribeType, Reflect};
+    ///
+    /// /// A point in space.
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Point {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let typescript = <Point as DescribeType>::type_descriptor().to_typescript();
+    ///
+    /// assert_eq!(
+    ///     typescript,
+    ///     "/** A point in space. */\n\
+    ///      export interface Point {\n  x: number;\n  y: number;\n}\n",
+    /// );
+    /// ```
+    ///
+    /// [`ReflectSerializer`]: crate::reflect_serde::ReflectSerializer
+    pub fn to_typescript(&self) -> String {
+        let mut generator = Generator::default();
+        generator.type_expr(self.get_type(), 0);
+
+        let mut declarations = Vec::new();
+        while let Some(ty) = generator.queue.pop_front() {
+            declarations.push(generator.declaration(ty));
+        }
+
+        declarations.join("\n")
+    }
+}
+
+#[derive(Default)]
+struct Generator<'a> {
+    names: BTreeMap<NodeId, String>,
+    used_names: BTreeSet<String>,
+    /// Named types that need a declaration, in the order they were found.
+    queue: VecDeque<Type<'a>>,
+}
+
+impl<'a> Generator<'a> {
+    /// The name of a struct, tuple struct, or enum, queueing its declaration the first time it's
+    /// seen.
+    fn name(&mut self, id: NodeId, type_name: &str, ty: Type<'a>) -> String {
+        if let Some(name) = self.names.get(&id) {
+            return name.clone();
+        }
+
+        let short_name = short_type_name(type_name);
+        let mut name = short_name.to_owned();
+        let mut suffix = 2;
+        while self.used_names.contains(&name) {
+            name = format!("{short_name}{suffix}");
+            suffix += 1;
+        }
+
+        self.used_names.insert(name.clone());
+        self.names.insert(id, name.clone());
+        self.queue.push_back(ty);
+        name
+    }
+
+    fn declaration(&mut self, ty: Type<'a>) -> String {
+        let (docs, body) = match ty {
+            Type::Struct(struct_ty) => {
+                let name = self.name(struct_ty.node.id, struct_ty.type_name(), ty);
+                // structs without fields are serialized like `()`
+                let body = if struct_ty.fields_len() == 0 {
+                    format!("export type {name} = null;\n")
+                } else {
+                    let fields = self.fields(struct_ty.field_types(), 0);
+                    format!("export interface {name} {fields}\n")
+                };
+                (struct_ty.docs(), body)
+            }
+            Type::TupleStruct(tuple_struct_ty) => {
+                let name = self.name(tuple_struct_ty.node.id, tuple_struct_ty.type_name(), ty);
+                // newtype structs are serialized as their field
+                let alias = match tuple_struct_ty.fields_len() {
+                    1 => {
+                        let field_ty = tuple_struct_ty.field_type_at(0).unwrap();
+                        self.type_expr(field_ty.get_type(), 0)
+                    }
+                    _ => self.tuple(tuple_struct_ty.field_types(), 0),
+                };
+                let body = format!("export type {name} = {alias};\n");
+                (tuple_struct_ty.docs(), body)
+            }
+            Type::Enum(enum_ty) => {
+                let name = self.name(enum_ty.node.id, enum_ty.type_name(), ty);
+                let mut body = format!("export type {name} =");
+                if enum_ty.variants_len() == 0 {
+                    body.push_str(" never");
+                }
+                for variant_ty in enum_ty.variants() {
+                    body.push('\n');
+                    body.push_str(&doc_comment(variant_ty.docs(), 1));
+                    body.push_str(INDENT);
+                    body.push_str("| ");
+                    body.push_str(&self.variant(variant_ty, 1));
+                }
+                body.push_str(";\n");
+                (enum_ty.docs(), body)
+            }
+            Type::Tuple(_)
+            | Type::List(_)
+            | Type::Array(_)
+            | Type::Map(_)
+            | Type::Set(_)
+            | Type::Scalar(_)
+            | Type::Opaque(_) => unreachable!("only named types are declared"),
+        };
+        format!("{}{body}", doc_comment(docs, 0))
+    }
+
+    /// The TypeScript type of `ty`, as it appears at the given level of indentation.
+    fn type_expr(&mut self, ty: Type<'a>, indent: usize) -> String {
+        match ty {
+            Type::Struct(struct_ty) => self.name(struct_ty.node.id, struct_ty.type_name(), ty),
+            Type::TupleStruct(tuple_struct_ty) => {
+                self.name(tuple_struct_ty.node.id, tuple_struct_ty.type_name(), ty)
+            }
+            Type::Enum(enum_ty) if enum_ty.is_option() => {
+                let some_ty = enum_ty
+                    .variant("Some")
+                    .and_then(|variant_ty| variant_ty.field_type_at(0))
+                    .unwrap()
+                    .get_type();
+                format!("{} | null", self.type_expr(some_ty, indent))
+            }
+            Type::Enum(enum_ty) => self.name(enum_ty.node.id, enum_ty.type_name(), ty),
+            Type::Tuple(tuple_ty) => {
+                if tuple_ty.fields_len() == 0 {
+                    "null".to_owned()
+                } else {
+                    self.tuple(tuple_ty.field_types(), indent)
+                }
+            }
+            Type::List(list_ty) => array_of(self.type_expr(list_ty.element_type(), indent)),
+            Type::Array(array_ty) => array_of(self.type_expr(array_ty.element_type(), indent)),
+            Type::Set(set_ty) => array_of(self.type_expr(set_ty.element_type(), indent)),
+            // serde_json turns all map keys into strings
+            Type::Map(map_ty) => {
+                format!(
+                    "Record<string, {}>",
+                    self.type_expr(map_ty.value_type(), indent)
+                )
+            }
+            Type::Scalar(scalar_ty) => match scalar_ty {
+                ScalarType::usize
+                | ScalarType::u8
+                | ScalarType::u16
+                | ScalarType::u32
+                | ScalarType::u64
+                | ScalarType::u128
+                | ScalarType::i8
+                | ScalarType::i16
+                | ScalarType::i32
+                | ScalarType::i64
+                | ScalarType::i128
+                | ScalarType::f32
+                | ScalarType::f64
+                | ScalarType::NonZeroUsize
+                | ScalarType::NonZeroU8
+                | ScalarType::NonZeroU16
+                | ScalarType::NonZeroU32
+                | ScalarType::NonZeroU64
+                | ScalarType::NonZeroU128
+                | ScalarType::NonZeroI8
+                | ScalarType::NonZeroI16
+                | ScalarType::NonZeroI32
+                | ScalarType::NonZeroI64
+                | ScalarType::NonZeroI128 => "number",
+                ScalarType::bool => "boolean",
+                ScalarType::char | ScalarType::String => "string",
+            }
+            .to_owned(),
+            Type::Opaque(_) => "unknown".to_owned(),
+        }
+    }
+
+    fn variant(&mut self, variant_ty: Variant<'a>, indent: usize) -> String {
+        let name = variant_ty.name();
+        // unit variants are serialized as their name, the others as `{ "Name": fields }`
+        let fields = match variant_ty {
+            Variant::Unit(_) => return format!("\"{name}\""),
+            Variant::Tuple(tuple_ty) => match tuple_ty.fields_len() {
+                1 => {
+                    let field_ty = tuple_ty.field_type_at(0).unwrap();
+                    self.type_expr(field_ty.get_type(), indent + 1)
+                }
+                _ => self.tuple(tuple_ty.field_types(), indent + 1),
+            },
+            Variant::Struct(struct_ty) => self.fields(struct_ty.field_types(), indent + 1),
+        };
+
+        let name = property_name(name);
+        if fields.contains('\n') {
+            let outer = INDENT.repeat(indent);
+            let inner = INDENT.repeat(indent + 1);
+            format!("{{\n{inner}{name}: {fields};\n{outer}}}")
+        } else {
+            format!("{{ {name}: {fields} }}")
+        }
+    }
+
+    /// An object type with one property per field.
+    fn fields(&mut self, field_tys: impl Iterator<Item = NamedField<'a>>, indent: usize) -> String {
+        let inner = INDENT.repeat(indent + 1);
+        let mut out = String::from("{\n");
+        for field_ty in field_tys {
+            out.push_str(&doc_comment(field_ty.docs(), indent + 1));
+            out.push_str(&inner);
+            out.push_str(&property_name(field_ty.name()));
+            out.push_str(": ");
+            out.push_str(&self.type_expr(field_ty.get_type(), indent + 1));
+            out.push_str(";\n");
+        }
+
+        if out == "{\n" {
+            return "{}".to_owned();
+        }
+        out.push_str(&INDENT.repeat(indent));
+        out.push('}');
+        out
+    }
+
+    fn tuple(
+        &mut self,
+        field_tys: impl Iterator<Item = UnnamedField<'a>>,
+        indent: usize,
+    ) -> String {
+        let fields = field_tys
+            .map(|field_ty| self.type_expr(field_ty.get_type(), indent))
+            .collect::<Vec<_>>();
+        format!("[{}]", fields.join(", "))
+    }
+}
+
+/// `T[]`, or `Array<T>` when `T` would need parentheses.
+fn array_of(element: String) -> String {
+    let is_simple = element
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '[' | ']'));
+    if is_simple {
+        format!("{element}[]")
+    } else {
+        format!("Array<{element}>")
+    }
+}
+
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .map_or(false, |c| c.is_alphabetic() || matches!(c, '_' | '$'))
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '$'));
+    if is_identifier {
+        name.to_owned()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+fn doc_comment(docs: &[String], indent: usize) -> String {
+    let Some(docs) = docs_to_string(docs) else {
+        return String::new();
+    };
+    // don't let the docs end the comment early
+    let docs = docs.replace("*/", "*\\/");
+
+    let indent = INDENT.repeat(indent);
+    let mut lines = docs.lines();
+    match (lines.next(), lines.next()) {
+        (None, _) => String::new(),
+        (Some(line), None) => format!("{indent}/** {line} */\n"),
+        _ => {
+            let mut out = format!("{indent}/**\n");
+            for line in docs.lines() {
+                if line.is_empty() {
+                    out.push_str(&format!("{indent} *\n"));
+                } else {
+                    out.push_str(&format!("{indent} * {line}\n"));
+                }
+            }
+            out.push_str(&format!("{indent} */\n"));
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::collections::BTreeSet;
+
+    use super::*;
+    use crate::DescribeType;
+
+    /// A saved game.
+    ///
+    /// Written when the player quits.
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Save {
+        /// The player's name.
+        name: String,
+        level: u8,
+        position: [f32; 3],
+        inventory: Vec<Item>,
+        flags: BTreeSet<char>,
+        scores: BTreeMap<String, i64>,
+        best: Option<u32>,
+        history: Vec<Option<Id>>,
+        pair: (bool, f64),
+        id: Id,
+        unit: (),
+        marker: Marker,
+        other: other::Item,
+    }
+
+    /// Something the player carries.
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        /// Deals damage.
+        Sword {
+            /// Per hit.
+            damage: u32,
+        },
+        Potion(u16),
+        Pair(bool, char),
+        Key,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Id(usize);
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Marker;
+
+    mod other {
+        use crate::Reflect;
+
+        #[derive(Reflect, Clone, Debug)]
+        #[reflect(crate_name(crate))]
+        pub(super) struct Item(pub(super) i8, pub(super) i8);
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Tree {
+        children: Vec<Tree>,
+    }
+
+    #[test]
+    fn declarations() {
+        let typescript = <Save as DescribeType>::type_descriptor().to_typescript();
+        assert_eq!(
+            typescript,
+            r#"/**
+ * A saved game.
+ *
+ * Written when the player quits.
+ */
+export interface Save {
+  /** The player's name. */
+  name: string;
+  level: number;
+  position: number[];
+  inventory: Item[];
+  flags: string[];
+  scores: Record<string, number>;
+  best: number | null;
+  history: Array<Id | null>;
+  pair: [boolean, number];
+  id: Id;
+  unit: null;
+  marker: Marker;
+  other: Item2;
+}
+
+/** Something the player carries. */
+export type Item =
+  /** Deals damage. */
+  | {
+    Sword: {
+      /** Per hit. */
+      damage: number;
+    };
+  }
+  | { Potion: number }
+  | { Pair: [boolean, string] }
+  | "Key";
+
+export type Id = number;
+
+export type Marker = null;
+
+export type Item2 = [number, number];
+"#
+        );
+    }
+
+    #[test]
+    fn recursive() {
+        let typescript = <Tree as DescribeType>::type_descriptor().to_typescript();
+        assert_eq!(
+            typescript,
+            "export interface Tree {\n  children: Tree[];\n}\n"
+        );
+    }
+
+    #[test]
+    fn scalar_backed_types() {
+        #[derive(Reflect, Clone, Debug)]
+        #[reflect(crate_name(crate))]
+        struct Timer {
+            elapsed: core::time::Duration,
+            ticks: core::num::NonZeroU32,
+        }
+
+        assert_eq!(
+            <Timer as DescribeType>::type_descriptor().to_typescript(),
+            "export interface Timer {\n  elapsed: number;\n  ticks: number;\n}\n"
+        );
+    }
+
+    #[test]
+    fn only_named_types_are_declared() {
+        assert_eq!(
+            <Vec<(u8, String)> as DescribeType>::type_descriptor().to_typescript(),
+            ""
+        );
+        assert_eq!(
+            <Option<Id> as DescribeType>::type_descriptor().to_typescript(),
+            "export type Id = number;\n"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::any::Any;
@@ -59,6 +60,7 @@ pub enum Value {
     TupleValue(TupleValue),
     List(Vec<Value>),
     Map(BTreeMap<Value, Value>),
+    Set(BTreeSet<Value>),
 }
 
 impl FromReflect for Value {
@@ -92,6 +94,7 @@ enum OrdEqHashValue<'a> {
     TupleValue(&'a TupleValue),
     List(&'a [Value]),
     Map(&'a BTreeMap<Value, Value>),
+    Set(&'a BTreeSet<Value>),
 }
 
 impl<'a> From<&'a Value> for OrdEqHashValue<'a> {
@@ -119,6 +122,7 @@ impl<'a> From<&'a Value> for OrdEqHashValue<'a> {
             Value::TupleValue(inner) => OrdEqHashValue::TupleValue(inner),
             Value::List(inner) => OrdEqHashValue::List(inner),
             Value::Map(inner) => OrdEqHashValue::Map(inner),
+            Value::Set(inner) => OrdEqHashValue::Set(inner),
         }
     }
 }
@@ -177,6 +181,7 @@ macro_rules! for_each_variant {
             Value::TupleValue($inner) => $expr,
             Value::List($inner) => $expr,
             Value::Map($inner) => $expr,
+            Value::Set($inner) => $expr,
         }
     };
 }
@@ -234,6 +239,7 @@ impl Reflect for Value {
             Value::TupleValue(inner) => ReflectOwned::Tuple(Box::new(inner)),
             Value::List(inner) => ReflectOwned::List(Box::new(inner)),
             Value::Map(inner) => ReflectOwned::Map(Box::new(inner)),
+            Value::Set(inner) => ReflectOwned::Set(Box::new(inner)),
         }
     }
 
@@ -261,6 +267,7 @@ impl Reflect for Value {
             Value::TupleValue(inner) => ReflectRef::Tuple(inner),
             Value::List(inner) => ReflectRef::List(inner),
             Value::Map(inner) => ReflectRef::Map(inner),
+            Value::Set(inner) => ReflectRef::Set(inner),
         }
     }
 
@@ -288,6 +295,7 @@ impl Reflect for Value {
             Value::TupleValue(inner) => ReflectMut::Tuple(inner),
             Value::List(inner) => ReflectMut::List(inner),
             Value::Map(inner) => ReflectMut::Map(inner),
+            Value::Set(inner) => ReflectMut::Set(inner),
         }
     }
 
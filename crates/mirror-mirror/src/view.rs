@@ -0,0 +1,487 @@
+use alloc::borrow::ToOwned;
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::struct_::StructValue;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// A read-only reflected value that may borrow data.
+///
+/// [`Reflect`] requires `'static` so it cannot be implemented for borrowed data such as `&[T]` or
+/// `&str`. `ReflectView` can, which allows inspecting data borrowed from a buffer or an arena
+/// without copying it into a [`Value`] first.
+///
+/// Slices and strings can be viewed directly through [`View`]. Types that contain borrowed data
+/// can implement [`StructView`] or [`ArrayView`].
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::{Reflect, view::{ReflectView, StructView, View}};
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Vertex {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// // borrows from some buffer so cannot implement `Reflect`
+/// #[derive(Debug)]
+/// struct Mesh<'a> {
+///     name: &'a str,
+///     vertices: &'a [Vertex],
+/// }
+///
+/// impl StructView for Mesh<'_> {
+///     fn type_name(&self) -> &str {
+///         "Mesh"
+///     }
+///
+///     fn field_at(&self, index: usize) -> Option<ReflectView<'_>> {
+///         match index {
+///             0 => Some(self.name.view()),
+///             1 => Some(self.vertices.view()),
+///             _ => None,
+///         }
+///     }
+///
+///     fn name_at(&self, index: usize) -> Option<&str> {
+///         ["name", "vertices"].get(index).copied()
+///     }
+///
+///     fn fields_len(&self) -> usize {
+///         2
+///     }
+/// }
+///
+/// let vertices = [Vertex { x: 1.0, y: 2.0 }, Vertex { x: 3.0, y: 4.0 }];
+/// let mesh = Mesh {
+///     name: "triangle",
+///     vertices: &vertices,
+/// };
+/// let view = ReflectView::Struct(&mesh);
+///
+/// assert_eq!(view.field("name").unwrap().as_str(), Some("triangle"));
+///
+/// let y = view
+///     .field("vertices")
+///     .and_then(|vertices| vertices.get(1))
+///     .and_then(|vertex| vertex.field("y"))
+///     .and_then(|y| y.as_reflect())
+///     .and_then(|y| y.downcast_ref::<f32>());
+/// assert_eq!(y, Some(&4.0));
+/// ```
+#[derive(Clone, Copy)]
+pub enum ReflectView<'a> {
+    /// A value that implements [`Reflect`].
+    Reflect(&'a dyn Reflect),
+    /// A borrowed string.
+    Str(&'a str),
+    /// A borrowed array, such as a slice.
+    Array(&'a dyn ArrayView),
+    /// A borrowed struct.
+    Struct(&'a dyn StructView),
+}
+
+impl<'a> ReflectView<'a> {
+    /// Get the element at `index` of an array or list.
+    pub fn get(self, index: usize) -> Option<ReflectView<'a>> {
+        match self {
+            ReflectView::Reflect(value) => match value.reflect_ref() {
+                ReflectRef::Array(array) => array.get(index).map(ReflectView::Reflect),
+                ReflectRef::List(list) => list.get(index).map(ReflectView::Reflect),
+                ReflectRef::Struct(_)
+                | ReflectRef::TupleStruct(_)
+                | ReflectRef::Tuple(_)
+                | ReflectRef::Enum(_)
+                | ReflectRef::Map(_)
+                | ReflectRef::Set(_)
+                | ReflectRef::Scalar(_)
+                | ReflectRef::Opaque(_) => None,
+            },
+            ReflectView::Array(array) => array.get(index),
+            ReflectView::Str(_) | ReflectView::Struct(_) => None,
+        }
+    }
+
+    /// Get the field called `name` of a struct.
+    pub fn field(self, name: &str) -> Option<ReflectView<'a>> {
+        match self {
+            ReflectView::Reflect(value) => value
+                .reflect_ref()
+                .as_struct()?
+                .field(name)
+                .map(ReflectView::Reflect),
+            ReflectView::Struct(struct_) => struct_.field(name),
+            ReflectView::Str(_) | ReflectView::Array(_) => None,
+        }
+    }
+
+    /// Get the string if this is a borrowed string or a `String`.
+    pub fn as_str(self) -> Option<&'a str> {
+        match self {
+            ReflectView::Reflect(value) => match value.reflect_ref() {
+                ReflectRef::Scalar(ScalarRef::String(string)) => Some(string.as_str()),
+                _ => None,
+            },
+            ReflectView::Str(string) => Some(string),
+            ReflectView::Array(_) | ReflectView::Struct(_) => None,
+        }
+    }
+
+    /// Get the value if it implements [`Reflect`].
+    pub fn as_reflect(self) -> Option<&'a dyn Reflect> {
+        match self {
+            ReflectView::Reflect(value) => Some(value),
+            ReflectView::Str(_) | ReflectView::Array(_) | ReflectView::Struct(_) => None,
+        }
+    }
+
+    /// Copy the value into a [`Value`].
+    ///
+    /// Strings become [`Value::String`], arrays [`Value::List`], and structs
+    /// [`Value::StructValue`].
+    pub fn to_value(self) -> Value {
+        match self {
+            ReflectView::Reflect(value) => value.to_value(),
+            ReflectView::Str(string) => string.to_owned().into(),
+            ReflectView::Array(array) => {
+                Value::List(array.iter().map(ReflectView::to_value).collect())
+            }
+            ReflectView::Struct(struct_) => {
+                let mut value = StructValue::with_capacity(struct_.fields_len());
+                for (name, field) in struct_.fields() {
+                    value.set_field(name, field.to_value());
+                }
+                value.into()
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ReflectView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectView::Reflect(value) => value.debug(f),
+            ReflectView::Str(string) => fmt::Debug::fmt(string, f),
+            ReflectView::Array(array) => f.debug_list().entries(array.iter()).finish(),
+            ReflectView::Struct(struct_) => {
+                let mut f = f.debug_struct(struct_.type_name());
+                for (name, field) in struct_.fields() {
+                    f.field(name, &field);
+                }
+                f.finish()
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a dyn Reflect> for ReflectView<'a> {
+    fn from(value: &'a dyn Reflect) -> Self {
+        ReflectView::Reflect(value)
+    }
+}
+
+impl<'a> From<&'a str> for ReflectView<'a> {
+    fn from(string: &'a str) -> Self {
+        ReflectView::Str(string)
+    }
+}
+
+/// A read-only array of borrowed values.
+///
+/// Implemented for `&[T]` where `T` implements [`View`].
+pub trait ArrayView {
+    fn get(&self, index: usize) -> Option<ReflectView<'_>>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> dyn ArrayView + 'a {
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            index: 0,
+            array: self,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for dyn ArrayView + 'a {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ReflectView::Array(self).fmt(f)
+    }
+}
+
+/// Iterator over the elements of an [`ArrayView`].
+#[derive(Debug)]
+pub struct Iter<'a> {
+    index: usize,
+    array: &'a dyn ArrayView,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = ReflectView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.array.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {
+    fn len(&self) -> usize {
+        self.array.len() - self.index
+    }
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+/// A read-only struct of borrowed values.
+pub trait StructView {
+    /// The name of the struct, used when debug formatting.
+    fn type_name(&self) -> &str;
+
+    fn field(&self, name: &str) -> Option<ReflectView<'_>> {
+        let index = (0..self.fields_len()).find(|index| self.name_at(*index) == Some(name))?;
+        self.field_at(index)
+    }
+
+    fn field_at(&self, index: usize) -> Option<ReflectView<'_>>;
+
+    fn name_at(&self, index: usize) -> Option<&str>;
+
+    fn fields_len(&self) -> usize;
+}
+
+impl<'a> dyn StructView + 'a {
+    pub fn fields(&self) -> FieldsIter<'_> {
+        FieldsIter {
+            index: 0,
+            struct_: self,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for dyn StructView + 'a {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ReflectView::Struct(self).fmt(f)
+    }
+}
+
+/// Iterator over the names and values of the fields of a [`StructView`].
+#[derive(Debug)]
+pub struct FieldsIter<'a> {
+    index: usize,
+    struct_: &'a dyn StructView,
+}
+
+impl<'a> Iterator for FieldsIter<'a> {
+    type Item = (&'a str, ReflectView<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name = self.struct_.name_at(self.index)?;
+        let value = self.struct_.field_at(self.index)?;
+        self.index += 1;
+        Some((name, value))
+    }
+}
+
+impl<'a> ExactSizeIterator for FieldsIter<'a> {
+    fn len(&self) -> usize {
+        self.struct_.fields_len() - self.index
+    }
+}
+
+impl<'a> FusedIterator for FieldsIter<'a> {}
+
+/// Types that can be viewed as a [`ReflectView`].
+///
+/// Implemented for all types that implement [`Reflect`], for `&str`, and for `&[T]` where `T`
+/// implements `View`.
+pub trait View {
+    fn view(&self) -> ReflectView<'_>;
+}
+
+impl<T> View for T
+where
+    T: Reflect,
+{
+    fn view(&self) -> ReflectView<'_> {
+        ReflectView::Reflect(self.as_reflect())
+    }
+}
+
+impl View for &str {
+    fn view(&self) -> ReflectView<'_> {
+        ReflectView::Str(self)
+    }
+}
+
+impl<T> View for &[T]
+where
+    T: View,
+{
+    fn view(&self) -> ReflectView<'_> {
+        ReflectView::Array(self)
+    }
+}
+
+impl<T> ArrayView for &[T]
+where
+    T: View,
+{
+    fn get(&self, index: usize) -> Option<ReflectView<'_>> {
+        <[T]>::get(self, index).map(View::view)
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::DescribeType;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        n: i32,
+        names: Vec<String>,
+    }
+
+    struct Borrowed<'a> {
+        name: &'a str,
+        foos: &'a [Foo],
+        tags: &'a [&'a str],
+    }
+
+    impl StructView for Borrowed<'_> {
+        fn type_name(&self) -> &str {
+            "Borrowed"
+        }
+
+        fn field_at(&self, index: usize) -> Option<ReflectView<'_>> {
+            match index {
+                0 => Some(self.name.view()),
+                1 => Some(self.foos.view()),
+                2 => Some(self.tags.view()),
+                _ => None,
+            }
+        }
+
+        fn name_at(&self, index: usize) -> Option<&str> {
+            ["name", "foos", "tags"].get(index).copied()
+        }
+
+        fn fields_len(&self) -> usize {
+            3
+        }
+    }
+
+    fn foos() -> Vec<Foo> {
+        Vec::from([
+            Foo {
+                n: 1,
+                names: Vec::from(["a".to_owned()]),
+            },
+            Foo {
+                n: 2,
+                names: Vec::new(),
+            },
+        ])
+    }
+
+    #[test]
+    fn slices() {
+        let numbers = [1, 2, 3];
+        let slice = &numbers[..];
+        let view = slice.view();
+
+        assert_eq!(
+            view.get(1)
+                .and_then(ReflectView::as_reflect)
+                .and_then(|n| n.downcast_ref::<i32>()),
+            Some(&2)
+        );
+        assert!(view.get(3).is_none());
+        assert!(view.field("n").is_none());
+        assert_eq!(view.to_value(), Vec::from([1, 2, 3]).to_value());
+
+        let strings = ["a", "b"];
+        let slice = &strings[..];
+        let view = slice.view();
+        assert_eq!(view.get(0).unwrap().as_str(), Some("a"));
+        assert_eq!(
+            view.to_value(),
+            Vec::from(["a".to_owned(), "b".to_owned()]).to_value()
+        );
+    }
+
+    #[test]
+    fn structs() {
+        let foos = foos();
+        let tags = ["x", "y"];
+        let borrowed = Borrowed {
+            name: "borrowed",
+            foos: &foos,
+            tags: &tags,
+        };
+        let view = ReflectView::Struct(&borrowed);
+
+        assert_eq!(view.field("name").unwrap().as_str(), Some("borrowed"));
+        assert!(view.field("missing").is_none());
+        assert!(view.get(0).is_none());
+
+        // owned values inside borrowed ones are reflected as usual
+        let names = view
+            .field("foos")
+            .and_then(|foos| foos.get(0))
+            .and_then(|foo| foo.field("names"))
+            .unwrap();
+        assert_eq!(names.get(0).unwrap().as_str(), Some("a"));
+        assert_eq!(
+            names.as_reflect().unwrap().type_descriptor().type_name(),
+            <Vec<String> as DescribeType>::type_descriptor().type_name()
+        );
+
+        let value = view.to_value();
+        let value = value.as_struct().unwrap();
+        assert_eq!(
+            Vec::<Foo>::from_reflect(value.field("foos").unwrap()).unwrap(),
+            foos
+        );
+        assert_eq!(
+            value.field("tags").unwrap().to_value(),
+            Vec::from(["x".to_owned(), "y".to_owned()]).to_value()
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let foos = foos();
+        let tags = ["x"];
+        let borrowed = Borrowed {
+            name: "borrowed",
+            foos: &foos[1..],
+            tags: &tags,
+        };
+
+        assert_eq!(
+            format!("{:?}", ReflectView::Struct(&borrowed)),
+            r#"Borrowed { name: "borrowed", foos: [Foo { n: 2, names: [] }], tags: ["x"] }"#
+        );
+    }
+}